@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use bitwarden_crypto::{EncString, KeyDecryptable, SymmetricCryptoKey};
 use chrono::Utc;
+use log::warn;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -50,7 +51,39 @@ pub(crate) async fn login_access_token(
         }
     }
 
-    let response = request_access_token(client, &access_token).await?;
+    let response =
+        match request_access_token(client, &access_token).await {
+            Ok(response) => response,
+            // When the server is unreachable, the keys in the state file still allow the secrets
+            // cache to be decrypted
+            Err(Error::Reqwest(e)) if client.internal.is_secrets_cache_enabled() => {
+                let Some(organization_id) = input.state_file.as_deref().and_then(|state_file| {
+                    load_key_from_state(client, state_file, &access_token).ok()
+                }) else {
+                    return Err(Error::Reqwest(e));
+                };
+
+                warn!("Unable to reach the server, only cached secrets will be available: {e}");
+
+                client
+                    .internal
+                    .set_login_method(LoginMethod::ServiceAccount(
+                        ServiceAccountLoginMethod::AccessToken {
+                            access_token,
+                            organization_id,
+                            state_file: input.state_file.clone(),
+                        },
+                    ));
+
+                return Ok(AccessTokenLoginResponse {
+                    authenticated: true,
+                    reset_master_password: false,
+                    force_password_reset: false,
+                    two_factor: None,
+                });
+            }
+            Err(e) => return Err(e),
+        };
 
     if let IdentityTokenResponse::Payload(r) = &response {
         // Extract the encrypted payload and use the access token encryption key to decrypt it
@@ -142,6 +175,25 @@ fn load_tokens_from_state(
     Err(Error::InvalidStateFile)
 }
 
+/// Only load the encryption key from the state file, ignoring the expiration of the stored token
+fn load_key_from_state(
+    client: &Client,
+    state_file: &Path,
+    access_token: &AccessToken,
+) -> Result<Uuid> {
+    let client_state = state::get(state_file, access_token)?;
+
+    let token: JWTToken = client_state.token.parse()?;
+    let organization_id: Uuid = require!(token.organization)
+        .parse()
+        .map_err(|_| "Bad organization id.")?;
+    let encryption_key = SymmetricCryptoKey::try_from(client_state.encryption_key)?;
+
+    client.internal.initialize_crypto_single_key(encryption_key);
+
+    Ok(organization_id)
+}
+
 /// Login to Bitwarden with access token
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
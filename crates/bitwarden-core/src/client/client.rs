@@ -79,6 +79,10 @@ impl Client {
                 })),
                 external_client,
                 encryption_settings: RwLock::new(None),
+                #[cfg(feature = "secrets")]
+                secrets_cache: RwLock::new(None),
+                #[cfg(feature = "internal")]
+                stores: RwLock::new(Default::default()),
             },
         }
     }
@@ -1,4 +1,9 @@
 use std::sync::{Arc, RwLock};
+#[cfg(feature = "internal")]
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
 
 #[cfg(any(feature = "internal", feature = "secrets"))]
 use bitwarden_crypto::SymmetricCryptoKey;
@@ -9,6 +14,8 @@ use uuid::Uuid;
 
 #[cfg(feature = "secrets")]
 use super::login_method::ServiceAccountLoginMethod;
+#[cfg(feature = "secrets")]
+use crate::secrets_manager::cache::{self, SecretsCacheSettings};
 use crate::{
     auth::renew::renew_token,
     client::{encryption_settings::EncryptionSettings, login_method::LoginMethod},
@@ -17,6 +24,7 @@ use crate::{
 };
 #[cfg(feature = "internal")]
 use crate::{
+    auth::JWTToken,
     client::encryption_settings::EncryptionSettingsError,
     client::{flags::Flags, login_method::UserLoginMethod},
     error::Error,
@@ -59,6 +67,13 @@ pub struct InternalClient {
     pub(crate) external_client: reqwest::Client,
 
     pub(super) encryption_settings: RwLock<Option<Arc<EncryptionSettings>>>,
+
+    #[cfg(feature = "secrets")]
+    pub(crate) secrets_cache: RwLock<Option<Arc<SecretsCacheSettings>>>,
+
+    /// Stores registered by the other crates to persist their state, keyed by type
+    #[cfg(feature = "internal")]
+    pub(crate) stores: RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
 }
 
 impl InternalClient {
@@ -96,6 +111,86 @@ impl InternalClient {
         }
     }
 
+    /// Enable or disable the encrypted cache of the last successful secrets sync
+    #[cfg(feature = "secrets")]
+    pub fn set_secrets_cache(&self, settings: Option<SecretsCacheSettings>) {
+        *self.secrets_cache.write().expect("RwLock is not poisoned") = settings.map(Arc::new);
+    }
+
+    #[cfg(feature = "secrets")]
+    pub fn is_secrets_cache_enabled(&self) -> bool {
+        self.secrets_cache
+            .read()
+            .expect("RwLock is not poisoned")
+            .is_some()
+    }
+
+    /// Read the secrets cache, returns `None` when the cache is disabled, missing or too stale
+    #[cfg(feature = "secrets")]
+    pub fn read_secrets_cache<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        let settings = self
+            .secrets_cache
+            .read()
+            .expect("RwLock is not poisoned")
+            .clone()?;
+
+        let login_method = self.login_method.read().expect("RwLock is not poisoned");
+        let Some(LoginMethod::ServiceAccount(ServiceAccountLoginMethod::AccessToken {
+            access_token,
+            ..
+        })) = login_method.as_deref()
+        else {
+            return None;
+        };
+
+        cache::get(&settings, access_token)
+            .map_err(|e| log::warn!("Unable to use the secrets cache: {}", e))
+            .ok()
+    }
+
+    /// Replace the contents of the secrets cache, does nothing when the cache is disabled
+    #[cfg(feature = "secrets")]
+    pub fn write_secrets_cache<T: serde::Serialize>(&self, data: T) -> Result<()> {
+        let Some(settings) = self
+            .secrets_cache
+            .read()
+            .expect("RwLock is not poisoned")
+            .clone()
+        else {
+            return Ok(());
+        };
+
+        let login_method = self.login_method.read().expect("RwLock is not poisoned");
+        match login_method.as_deref() {
+            Some(LoginMethod::ServiceAccount(ServiceAccountLoginMethod::AccessToken {
+                access_token,
+                ..
+            })) => cache::set(&settings, access_token, data),
+            _ => Ok(()),
+        }
+    }
+
+    /// Register a store of type `T`, replacing any existing one. `None` removes the store
+    #[cfg(feature = "internal")]
+    pub fn set_store<T: Any + Send + Sync>(&self, store: Option<Arc<T>>) {
+        let mut stores = self.stores.write().expect("RwLock is not poisoned");
+        match store {
+            Some(store) => stores.insert(TypeId::of::<T>(), store),
+            None => stores.remove(&TypeId::of::<T>()),
+        };
+    }
+
+    /// Get the registered store of type `T`, if any
+    #[cfg(feature = "internal")]
+    pub fn get_store<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.stores
+            .read()
+            .expect("RwLock is not poisoned")
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(|store| store.downcast().ok())
+    }
+
     #[cfg(any(feature = "internal", feature = "secrets"))]
     pub(crate) fn set_login_method(&self, login_method: LoginMethod) {
         use log::debug;
@@ -137,6 +232,14 @@ impl InternalClient {
         is_token_set || is_login_method_set
     }
 
+    /// Id of the logged in user, read from the access token
+    #[cfg(feature = "internal")]
+    pub fn get_user_id(&self) -> Option<Uuid> {
+        let tokens = self.tokens.read().expect("RwLock is not poisoned");
+        let token: JWTToken = tokens.access_token.as_deref()?.parse().ok()?;
+        token.sub.parse().ok()
+    }
+
     #[cfg(feature = "internal")]
     pub fn get_kdf(&self) -> Result<Kdf> {
         match self
@@ -8,12 +8,20 @@ use crate::{
         initialize_org_crypto, initialize_user_crypto, InitOrgCryptoRequest, InitUserCryptoMethod,
         InitUserCryptoRequest,
     },
-    Client,
+    Client, ClientSettings,
 };
 
 impl Client {
     pub async fn init_test_account(account: TestAccount) -> Self {
-        let client = Client::new(None);
+        Self::init_test_account_with_settings(account, None).await
+    }
+
+    /// Same as [Client::init_test_account], connected to the server in `settings`, usually a mock
+    pub async fn init_test_account_with_settings(
+        account: TestAccount,
+        settings: Option<ClientSettings>,
+    ) -> Self {
+        let client = Client::new(settings);
 
         client.internal.load_flags(HashMap::from([(
             "enableCipherKeyEncryption".to_owned(),
@@ -0,0 +1,259 @@
+//! Downloading, decrypting and uploading the encrypted files of attachments and Sends, without
+//! holding them in memory. WASM can't stream request and response bodies, so the files are
+//! buffered there.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{ErrorKind, Read, Write};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use bitwarden_crypto::{decrypt_stream, SymmetricCryptoKey};
+use reqwest::{
+    header::CONTENT_LENGTH,
+    multipart::{Form, Part},
+    Body,
+};
+
+use crate::{
+    error::{Error, Result},
+    Client,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Decrypt the file at `encrypted_file_path` to `decrypted_file_path`. The file is decrypted next
+/// to the destination and only moved over it once it's fully decrypted, so an existing file is
+/// left untouched when the encrypted file is damaged.
+pub fn decrypt_file(
+    key: &SymmetricCryptoKey,
+    encrypted_file_path: &Path,
+    decrypted_file_path: &Path,
+) -> Result<()> {
+    let dir = decrypted_file_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    // Removed when dropped, unless it was persisted
+    let mut decrypted = tempfile::NamedTempFile::new_in(dir)?;
+
+    decrypt_stream(
+        key,
+        BufReader::new(File::open(encrypted_file_path)?),
+        BufWriter::new(decrypted.as_file_mut()),
+    )?;
+    decrypted
+        .persist(decrypted_file_path)
+        .map_err(|e| e.error)?;
+
+    Ok(())
+}
+
+/// Download the encrypted file at `url` next to `destination` and decrypt it with `decrypt`, which
+/// receives the path and size of the encrypted file. The encrypted file is removed afterwards,
+/// whether it could be decrypted or not.
+pub async fn download_and_decrypt<E: From<Error>>(
+    client: &Client,
+    url: &str,
+    destination: &Path,
+    decrypt: impl FnOnce(&Path, u64) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut encrypted_path = destination.as_os_str().to_owned();
+    encrypted_path.push(".encrypted");
+    let encrypted_path = Path::new(&encrypted_path);
+
+    let result = match download(client, url, encrypted_path).await {
+        Ok(size) => decrypt(encrypted_path, size),
+        Err(e) => Err(e.into()),
+    };
+    _ = std::fs::remove_file(encrypted_path);
+
+    result
+}
+
+/// Download the file at `url` to `path`, returning its size
+#[cfg(not(target_arch = "wasm32"))]
+async fn download(client: &Client, url: &str, path: &Path) -> Result<u64> {
+    let mut response = client
+        .internal
+        .get_http_client()
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let mut file = BufWriter::new(File::create(path)?);
+    let mut size = 0;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+        size += chunk.len() as u64;
+    }
+    file.flush()?;
+
+    Ok(size)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn download(client: &Client, url: &str, path: &Path) -> Result<u64> {
+    let contents = client
+        .internal
+        .get_http_client()
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    std::fs::write(path, &contents)?;
+
+    Ok(contents.len() as u64)
+}
+
+/// Upload the encrypted file at `path` to Azure blob storage, using the signed URL returned by the
+/// server
+pub async fn upload_azure(client: &Client, url: &str, path: &Path) -> Result<()> {
+    let (body, size) = file_body(path)?;
+
+    client
+        .internal
+        .get_http_client()
+        .put(url)
+        .header("x-ms-version", "2020-04-08")
+        .header("x-ms-blob-type", "BlockBlob")
+        .header(
+            "x-ms-date",
+            chrono::Utc::now()
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string(),
+        )
+        // Azure requires the length up front, streamed bodies are otherwise sent chunked
+        .header(CONTENT_LENGTH, size)
+        .body(body)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+/// Upload the encrypted file at `path` to a self-hosted server, which receives the files on the API
+/// itself. `api_path` is relative to the API base URL.
+pub async fn upload_self_hosted(
+    client: &Client,
+    api_path: &str,
+    file_name: String,
+    path: &Path,
+) -> Result<()> {
+    let part = file_part(path)?.file_name(file_name);
+    let config = client.internal.get_api_configurations().await;
+
+    let form = Form::new().part("data", part);
+    let mut request = config
+        .api
+        .client
+        .post(format!("{}/{api_path}", config.api.base_path))
+        .multipart(form);
+    if let Some(token) = &config.api.oauth_access_token {
+        request = request.bearer_auth(token);
+    }
+    request.send().await?.error_for_status()?;
+
+    Ok(())
+}
+
+/// A request body reading the file at `path` in chunks, along with the size of the file
+#[cfg(not(target_arch = "wasm32"))]
+fn file_body(path: &Path) -> Result<(Body, u64)> {
+    let file = File::open(path)?;
+    let size = file.metadata()?.len();
+
+    let mut reader = BufReader::new(file);
+    let chunks = std::iter::from_fn(move || {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        let read = loop {
+            match reader.read(&mut buf) {
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        match read {
+            Ok(0) => None,
+            Ok(read) => {
+                buf.truncate(read);
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    });
+
+    Ok((Body::wrap_stream(futures_util::stream::iter(chunks)), size))
+}
+
+/// A request body with the contents of the file at `path`, along with the size of the file
+#[cfg(target_arch = "wasm32")]
+fn file_body(path: &Path) -> Result<(Body, u64)> {
+    let contents = std::fs::read(path)?;
+    let size = contents.len() as u64;
+
+    Ok((Body::from(contents), size))
+}
+
+/// A multipart form part reading the file at `path` in chunks
+#[cfg(not(target_arch = "wasm32"))]
+fn file_part(path: &Path) -> Result<Part> {
+    let (body, size) = file_body(path)?;
+    Ok(Part::stream_with_length(body, size))
+}
+
+/// A multipart form part with the contents of the file at `path`
+#[cfg(target_arch = "wasm32")]
+fn file_part(path: &Path) -> Result<Part> {
+    Ok(Part::bytes(std::fs::read(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use bitwarden_crypto::encrypt_stream;
+
+    use super::*;
+
+    #[test]
+    fn test_decrypt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+
+        let mut encrypted = Cursor::new(Vec::new());
+        encrypt_stream(&key, b"new contents".as_slice(), &mut encrypted).unwrap();
+        let encrypted_path = dir.path().join("file.encrypted");
+        std::fs::write(&encrypted_path, encrypted.get_ref()).unwrap();
+
+        let decrypted_path = dir.path().join("file.txt");
+        std::fs::write(&decrypted_path, "old contents").unwrap();
+
+        // A damaged file leaves the existing destination untouched
+        let mut damaged = encrypted.into_inner();
+        let last = damaged.len() - 1;
+        damaged[last] ^= 1;
+        let damaged_path = dir.path().join("damaged.encrypted");
+        std::fs::write(&damaged_path, damaged).unwrap();
+
+        assert!(decrypt_file(&key, &damaged_path, &decrypted_path).is_err());
+        assert_eq!(
+            std::fs::read_to_string(&decrypted_path).unwrap(),
+            "old contents"
+        );
+
+        decrypt_file(&key, &encrypted_path, &decrypted_path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&decrypted_path).unwrap(),
+            "new contents"
+        );
+        // Only the encrypted files and the destination remain
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+}
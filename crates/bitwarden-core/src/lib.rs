@@ -10,6 +10,8 @@ pub mod client;
 mod error;
 pub use error::{validate_only_whitespaces, Error, MissingFieldError, VaultLocked};
 #[cfg(feature = "internal")]
+pub mod files;
+#[cfg(feature = "internal")]
 pub mod mobile;
 #[cfg(feature = "internal")]
 pub mod platform;
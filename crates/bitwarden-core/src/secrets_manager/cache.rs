@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+
+use bitwarden_crypto::{EncString, KeyDecryptable, KeyEncryptable};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    auth::AccessToken,
+    error::{Error, Result},
+};
+
+const CACHE_VERSION: u32 = 1;
+
+/// Opt-in encrypted cache of the last successful secrets sync, used when the server is
+/// unreachable
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsCacheSettings {
+    /// File where the encrypted cache is stored
+    pub cache_file: PathBuf,
+    /// Maximum age in seconds of a cache that is still used, unlimited when not set
+    pub max_staleness_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CacheState<T> {
+    version: u32,
+    synced_date: DateTime<Utc>,
+    data: T,
+}
+
+pub fn get<T: DeserializeOwned>(
+    settings: &SecretsCacheSettings,
+    access_token: &AccessToken,
+) -> Result<T> {
+    let file_content = std::fs::read_to_string(&settings.cache_file)?;
+
+    let encrypted_cache: EncString = file_content.parse()?;
+    let decrypted_cache: String = encrypted_cache.decrypt_with_key(&access_token.encryption_key)?;
+    let cache: CacheState<T> = serde_json::from_str(&decrypted_cache)?;
+
+    if cache.version != CACHE_VERSION {
+        return Err(Error::InvalidStateFileVersion);
+    }
+
+    if let Some(max_staleness) = settings.max_staleness_seconds {
+        let age = Utc::now().signed_duration_since(cache.synced_date);
+        if age.num_seconds() > max_staleness as i64 {
+            return Err(format!(
+                "The secrets cache is older than the maximum of {} seconds",
+                max_staleness
+            )
+            .into());
+        }
+    }
+
+    Ok(cache.data)
+}
+
+pub fn set<T: Serialize>(
+    settings: &SecretsCacheSettings,
+    access_token: &AccessToken,
+    data: T,
+) -> Result<()> {
+    let cache = CacheState {
+        version: CACHE_VERSION,
+        synced_date: Utc::now(),
+        data,
+    };
+
+    let serialized_cache: String = serde_json::to_string(&cache)?;
+    let encrypted_cache: EncString =
+        serialized_cache.encrypt_with_key(&access_token.encryption_key)?;
+
+    std::fs::write(&settings.cache_file, encrypted_cache.to_string())
+        .map_err(|_| "Failure writing to the secrets cache file.".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    const ACCESS_TOKEN: &str = "0.ec2c1d46-6a4b-4751-a310-af9601317f2d.C2IgxjjLF7qSshsbwe8JGcbM075YXw:X8vbvA0bduihIDe/qrzIQQ==";
+
+    fn settings(dir: &Path, max_staleness_seconds: Option<u64>) -> SecretsCacheSettings {
+        SecretsCacheSettings {
+            cache_file: dir.join("cache"),
+            max_staleness_seconds,
+        }
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let access_token: AccessToken = ACCESS_TOKEN.parse().unwrap();
+
+        set(&settings(dir.path(), None), &access_token, vec!["secret"]).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join("cache")).unwrap();
+        assert!(!content.contains("secret"));
+
+        let data: Vec<String> = get(&settings(dir.path(), Some(60)), &access_token).unwrap();
+        assert_eq!(data, vec!["secret"]);
+    }
+
+    #[test]
+    fn test_cache_too_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let access_token: AccessToken = ACCESS_TOKEN.parse().unwrap();
+
+        let cache = CacheState {
+            version: CACHE_VERSION,
+            synced_date: Utc::now() - chrono::Duration::seconds(120),
+            data: vec!["secret"],
+        };
+        let encrypted: EncString = serde_json::to_string(&cache)
+            .unwrap()
+            .encrypt_with_key(&access_token.encryption_key)
+            .unwrap();
+        std::fs::write(dir.path().join("cache"), encrypted.to_string()).unwrap();
+
+        let result: Result<Vec<String>> = get(&settings(dir.path(), Some(60)), &access_token);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Internal error: The secrets cache is older than the maximum of 60 seconds"
+        );
+
+        let data: Vec<String> = get(&settings(dir.path(), None), &access_token).unwrap();
+        assert_eq!(data, vec!["secret"]);
+    }
+}
@@ -41,6 +41,9 @@ pub enum CryptoError {
 
     #[error("Number is zero")]
     ZeroNumber,
+
+    #[error("IO error, {0}")]
+    Io(#[from] std::io::Error),
 }
 
 #[derive(Debug, Error)]
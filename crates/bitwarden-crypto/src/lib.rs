@@ -55,6 +55,8 @@
 //!
 //! ## Crate features
 //!
+//! - `async` - Enables [`encrypt_stream_async`] and [`decrypt_stream_async`], streaming encryption
+//!   for Tokio's async IO traits.
 //! - `no-memory-hardening` - Disables memory hardening which ensures that allocated memory is
 //!   zeroed on drop. This feature primarily exists in case you do not want to use the standard
 //!   allocator, and we advise to still define a `global_allocator` using the
@@ -76,6 +78,10 @@ mod keys;
 pub use keys::*;
 mod rsa;
 pub use crate::rsa::RsaKeyPair;
+mod stream;
+pub use stream::{decrypt_stream, encrypt_stream};
+#[cfg(feature = "async")]
+pub use stream::{decrypt_stream_async, encrypt_stream_async};
 mod util;
 pub use util::{generate_random_alphanumeric, generate_random_bytes, pbkdf2};
 mod wordlist;
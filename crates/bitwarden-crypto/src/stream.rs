@@ -0,0 +1,479 @@
+//! # Streaming encryption
+//!
+//! Encrypt and decrypt data too large to comfortably be held in memory, such as attachments and
+//! file Sends. The encrypted output uses the same layout as [EncString::to_buffer] for
+//! `AesCbc256_HmacSha256_B64`, so data encrypted with either API can be decrypted with the other:
+//!
+//! ```text
+//! [type: 1 byte][iv: 16 bytes][mac: 32 bytes][data]
+//! ```
+//!
+//! Since the MAC precedes the data, encrypting requires a seekable output to fill in the MAC once
+//! all the data has been processed. Decrypting requires a seekable input, as the MAC is verified
+//! before any decrypted data is written. The input must not be modified while decrypting.
+//!
+//! [EncString::to_buffer]: crate::EncString::to_buffer
+
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use generic_array::GenericArray;
+use hmac::Mac;
+use rand::RngCore;
+
+use crate::{
+    error::{CryptoError, EncStringParseError, Result},
+    util::PbkdfSha256Hmac,
+    SymmetricCryptoKey,
+};
+
+/// The `AesCbc256_HmacSha256_B64` encryption type
+const ENC_TYPE: u8 = 2;
+const IV_LEN: usize = 16;
+const MAC_LEN: usize = 32;
+const HEADER_LEN: usize = 1 + IV_LEN + MAC_LEN;
+const BLOCK_SIZE: usize = 16;
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Encrypt everything read from `reader` with `key`, writing the encrypted buffer to `writer`.
+/// Returns the size of the encrypted buffer.
+pub fn encrypt_stream<R: Read, W: Write + Seek>(
+    key: &SymmetricCryptoKey,
+    mut reader: R,
+    mut writer: W,
+) -> Result<u64> {
+    let mut encryptor = Encryptor::new(key)?;
+    let start = writer.stream_position()?;
+    writer.write_all(&encryptor.header())?;
+
+    let mut written = HEADER_LEN as u64;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let encrypted = encryptor.update(&buf[..read]);
+        writer.write_all(&encrypted)?;
+        written += encrypted.len() as u64;
+    }
+
+    let (encrypted, mac) = encryptor.finalize();
+    writer.write_all(&encrypted)?;
+    written += encrypted.len() as u64;
+
+    writer.seek(SeekFrom::Start(start + 1 + IV_LEN as u64))?;
+    writer.write_all(&mac)?;
+    writer.seek(SeekFrom::Start(start + written))?;
+    writer.flush()?;
+
+    Ok(written)
+}
+
+/// Decrypt an encrypted buffer read from `reader` with `key`, writing the decrypted data to
+/// `writer`. Returns the size of the decrypted data.
+///
+/// Nothing is written unless the MAC is valid.
+pub fn decrypt_stream<R: Read + Seek, W: Write>(
+    key: &SymmetricCryptoKey,
+    mut reader: R,
+    mut writer: W,
+) -> Result<u64> {
+    let mut header = [0u8; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    let (iv, mac) = parse_header(&header)?;
+    let data_start = reader.stream_position()?;
+
+    let mut hmac = new_hmac(key, &iv)?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => hmac.update(&buf[..read]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    hmac.verify_slice(&mac)
+        .map_err(|_| CryptoError::InvalidMac)?;
+
+    reader.seek(SeekFrom::Start(data_start))?;
+    let mut decryptor = Decryptor::new(key, &iv);
+    let mut written = 0;
+    loop {
+        let read = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let decrypted = decryptor.update(&buf[..read]);
+        writer.write_all(&decrypted)?;
+        written += decrypted.len() as u64;
+    }
+
+    let decrypted = decryptor.finalize()?;
+    writer.write_all(&decrypted)?;
+    written += decrypted.len() as u64;
+    writer.flush()?;
+
+    Ok(written)
+}
+
+/// Async version of [encrypt_stream]
+#[cfg(feature = "async")]
+pub async fn encrypt_stream_async<R, W>(
+    key: &SymmetricCryptoKey,
+    mut reader: R,
+    mut writer: W,
+) -> Result<u64>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin,
+{
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let mut encryptor = Encryptor::new(key)?;
+    let start = writer.stream_position().await?;
+    writer.write_all(&encryptor.header()).await?;
+
+    let mut written = HEADER_LEN as u64;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        let encrypted = encryptor.update(&buf[..read]);
+        writer.write_all(&encrypted).await?;
+        written += encrypted.len() as u64;
+    }
+
+    let (encrypted, mac) = encryptor.finalize();
+    writer.write_all(&encrypted).await?;
+    written += encrypted.len() as u64;
+
+    writer
+        .seek(SeekFrom::Start(start + 1 + IV_LEN as u64))
+        .await?;
+    writer.write_all(&mac).await?;
+    writer.seek(SeekFrom::Start(start + written)).await?;
+    writer.flush().await?;
+
+    Ok(written)
+}
+
+/// Async version of [decrypt_stream]
+#[cfg(feature = "async")]
+pub async fn decrypt_stream_async<R, W>(
+    key: &SymmetricCryptoKey,
+    mut reader: R,
+    mut writer: W,
+) -> Result<u64>
+where
+    R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin,
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let mut header = [0u8; HEADER_LEN];
+    reader.read_exact(&mut header).await?;
+    let (iv, mac) = parse_header(&header)?;
+    let data_start = reader.stream_position().await?;
+
+    let mut hmac = new_hmac(key, &iv)?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        hmac.update(&buf[..read]);
+    }
+    hmac.verify_slice(&mac)
+        .map_err(|_| CryptoError::InvalidMac)?;
+
+    reader.seek(SeekFrom::Start(data_start)).await?;
+    let mut decryptor = Decryptor::new(key, &iv);
+    let mut written = 0;
+    loop {
+        let read = reader.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        let decrypted = decryptor.update(&buf[..read]);
+        writer.write_all(&decrypted).await?;
+        written += decrypted.len() as u64;
+    }
+
+    let decrypted = decryptor.finalize()?;
+    writer.write_all(&decrypted).await?;
+    written += decrypted.len() as u64;
+    writer.flush().await?;
+
+    Ok(written)
+}
+
+fn new_hmac(key: &SymmetricCryptoKey, iv: &[u8; IV_LEN]) -> Result<PbkdfSha256Hmac> {
+    let mac_key = key.mac_key.as_ref().ok_or(CryptoError::InvalidMac)?;
+    let mut hmac =
+        PbkdfSha256Hmac::new_from_slice(mac_key).expect("hmac new_from_slice should not fail");
+    hmac.update(iv);
+    Ok(hmac)
+}
+
+fn parse_header(header: &[u8; HEADER_LEN]) -> Result<([u8; IV_LEN], [u8; MAC_LEN])> {
+    if header[0] != ENC_TYPE {
+        return Err(EncStringParseError::InvalidTypeSymm {
+            enc_type: header[0].to_string(),
+            parts: 1,
+        }
+        .into());
+    }
+
+    let iv = header[1..1 + IV_LEN].try_into().expect("Valid length");
+    let mac = header[1 + IV_LEN..].try_into().expect("Valid length");
+    Ok((iv, mac))
+}
+
+/// AES-256-CBC encryption of data received in chunks of any size, computing the MAC as it goes
+struct Encryptor {
+    cipher: cbc::Encryptor<aes::Aes256>,
+    hmac: PbkdfSha256Hmac,
+    iv: [u8; IV_LEN],
+    /// Data that doesn't fill a complete block yet
+    pending: Vec<u8>,
+}
+
+impl Encryptor {
+    fn new(key: &SymmetricCryptoKey) -> Result<Self> {
+        let mut iv = [0u8; IV_LEN];
+        rand::thread_rng().fill_bytes(&mut iv);
+
+        Ok(Self {
+            cipher: cbc::Encryptor::new(&key.key, &iv.into()),
+            hmac: new_hmac(key, &iv)?,
+            iv,
+            pending: Vec::with_capacity(BLOCK_SIZE),
+        })
+    }
+
+    /// The buffer header, with the MAC left empty as it's only known once all data is encrypted
+    fn header(&self) -> [u8; HEADER_LEN] {
+        let mut header = [0u8; HEADER_LEN];
+        header[0] = ENC_TYPE;
+        header[1..1 + IV_LEN].copy_from_slice(&self.iv);
+        header
+    }
+
+    /// Encrypt all the complete blocks received so far
+    fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(data);
+        let len = self.pending.len() - self.pending.len() % BLOCK_SIZE;
+
+        let mut encrypted: Vec<u8> = self.pending.drain(..len).collect();
+        for block in encrypted.chunks_exact_mut(BLOCK_SIZE) {
+            self.cipher
+                .encrypt_block_mut(GenericArray::from_mut_slice(block));
+        }
+        self.hmac.update(&encrypted);
+
+        encrypted
+    }
+
+    /// Pad and encrypt the remaining data, returning it together with the MAC
+    fn finalize(mut self) -> (Vec<u8>, [u8; MAC_LEN]) {
+        let encrypted = self.cipher.encrypt_padded_vec_mut::<Pkcs7>(&self.pending);
+        self.hmac.update(&encrypted);
+
+        let mut mac = [0u8; MAC_LEN];
+        mac.copy_from_slice(&self.hmac.finalize().into_bytes());
+        (encrypted, mac)
+    }
+}
+
+/// AES-256-CBC decryption of data received in chunks of any size. The MAC must be verified
+/// separately, before decrypting.
+struct Decryptor {
+    cipher: cbc::Decryptor<aes::Aes256>,
+    /// Data that doesn't fill a complete block yet, and the last block which contains the padding
+    pending: Vec<u8>,
+}
+
+impl Decryptor {
+    fn new(key: &SymmetricCryptoKey, iv: &[u8; IV_LEN]) -> Self {
+        Self {
+            cipher: cbc::Decryptor::new(&key.key, GenericArray::from_slice(iv)),
+            pending: Vec::with_capacity(2 * BLOCK_SIZE),
+        }
+    }
+
+    /// Decrypt the complete blocks received so far, except the last one
+    fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(data);
+        let len = self.pending.len().saturating_sub(1) / BLOCK_SIZE * BLOCK_SIZE;
+
+        let mut decrypted: Vec<u8> = self.pending.drain(..len).collect();
+        for block in decrypted.chunks_exact_mut(BLOCK_SIZE) {
+            self.cipher
+                .decrypt_block_mut(GenericArray::from_mut_slice(block));
+        }
+
+        decrypted
+    }
+
+    /// Decrypt the last block and remove the padding
+    fn finalize(self) -> Result<Vec<u8>> {
+        self.cipher
+            .decrypt_padded_vec_mut::<Pkcs7>(&self.pending)
+            .map_err(|_| CryptoError::KeyDecrypt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{EncString, KeyDecryptable, KeyEncryptable};
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn encrypt(key: &SymmetricCryptoKey, data: &[u8]) -> Vec<u8> {
+        let mut encrypted = Cursor::new(Vec::new());
+        let size = encrypt_stream(key, data, &mut encrypted).unwrap();
+        assert_eq!(size, encrypted.get_ref().len() as u64);
+        encrypted.into_inner()
+    }
+
+    #[test]
+    fn test_stream_roundtrip() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+
+        for len in [
+            0,
+            1,
+            15,
+            16,
+            17,
+            CHUNK_SIZE - 1,
+            CHUNK_SIZE,
+            3 * CHUNK_SIZE + 5,
+        ] {
+            let data = test_data(len);
+            let encrypted = encrypt(&key, &data);
+
+            let mut decrypted = Vec::new();
+            let size = decrypt_stream(&key, Cursor::new(encrypted), &mut decrypted).unwrap();
+
+            assert_eq!(size, len as u64);
+            assert_eq!(decrypted, data);
+        }
+    }
+
+    #[test]
+    fn test_stream_compatible_with_buffer() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let data = test_data(CHUNK_SIZE + 100);
+
+        // Streamed encryption can be read as a buffer
+        let encrypted = encrypt(&key, &data);
+        let enc_string = EncString::from_buffer(&encrypted).unwrap();
+        let decrypted: Vec<u8> = enc_string.decrypt_with_key(&key).unwrap();
+        assert_eq!(decrypted, data);
+
+        // And buffers can be decrypted as a stream
+        let encrypted = data.as_slice().encrypt_with_key(&key).unwrap();
+        let mut decrypted = Vec::new();
+        decrypt_stream(
+            &key,
+            Cursor::new(encrypted.to_buffer().unwrap()),
+            &mut decrypted,
+        )
+        .unwrap();
+        assert_eq!(decrypted, data);
+    }
+
+    #[test]
+    fn test_stream_invalid_mac() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let mut encrypted = encrypt(&key, &test_data(100));
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 1;
+
+        let mut decrypted = Vec::new();
+        let result = decrypt_stream(&key, Cursor::new(encrypted), &mut decrypted);
+
+        assert!(matches!(result, Err(CryptoError::InvalidMac)));
+        assert!(decrypted.is_empty());
+    }
+
+    #[test]
+    fn test_stream_wrong_key() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let other_key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let encrypted = encrypt(&key, &test_data(100));
+
+        let result = decrypt_stream(&other_key, Cursor::new(encrypted), Vec::new());
+        assert!(matches!(result, Err(CryptoError::InvalidMac)));
+    }
+
+    #[test]
+    fn test_stream_unsupported_type() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let mut encrypted = encrypt(&key, &test_data(100));
+        encrypted[0] = 0;
+
+        let result = decrypt_stream(&key, Cursor::new(encrypted), Vec::new());
+        assert!(matches!(
+            result,
+            Err(CryptoError::EncString(
+                EncStringParseError::InvalidTypeSymm { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_stream_preserves_writer_offset() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let data = test_data(50);
+
+        let mut encrypted = Cursor::new(b"prefix".to_vec());
+        encrypted.seek(SeekFrom::End(0)).unwrap();
+        encrypt_stream(&key, data.as_slice(), &mut encrypted).unwrap();
+
+        let encrypted = encrypted.into_inner();
+        assert_eq!(&encrypted[..6], b"prefix");
+
+        let mut decrypted = Vec::new();
+        decrypt_stream(&key, Cursor::new(&encrypted[6..]), &mut decrypted).unwrap();
+        assert_eq!(decrypted, data);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_stream_async_roundtrip() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let data = test_data(2 * CHUNK_SIZE + 3);
+
+        let mut encrypted = Cursor::new(Vec::new());
+        encrypt_stream_async(&key, data.as_slice(), &mut encrypted)
+            .await
+            .unwrap();
+
+        // Compatible with the blocking API
+        let mut decrypted = Vec::new();
+        decrypt_stream(&key, Cursor::new(encrypted.get_ref()), &mut decrypted).unwrap();
+        assert_eq!(decrypted, data);
+
+        let mut decrypted = Vec::new();
+        decrypt_stream_async(&key, Cursor::new(encrypted.into_inner()), &mut decrypted)
+            .await
+            .unwrap();
+        assert_eq!(decrypted, data);
+    }
+}
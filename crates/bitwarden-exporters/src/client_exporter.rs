@@ -1,9 +1,13 @@
+use std::path::Path;
+
 use bitwarden_core::Client;
 use bitwarden_vault::{Cipher, Collection, Folder};
+use uuid::Uuid;
 
 use crate::{
-    export::{export_organization_vault, export_vault},
-    ExportError, ExportFormat,
+    export::{export_organization_vault, export_vault, export_vault_archive},
+    import::import_vault,
+    ExportError, ExportFormat, ImportError, ImportFormat, ImportResult,
 };
 
 pub struct ClientExporters<'a> {
@@ -24,13 +28,35 @@ impl<'a> ClientExporters<'a> {
         export_vault(self.client, folders, ciphers, format)
     }
 
+    /// Export the personal vault as a zip archive of the JSON export and the decrypted attachments,
+    /// written to `destination`. Organization ciphers are skipped.
+    pub async fn export_vault_archive(
+        &self,
+        folders: Vec<Folder>,
+        ciphers: Vec<Cipher>,
+        destination: &Path,
+    ) -> Result<(), ExportError> {
+        export_vault_archive(self.client, folders, ciphers, destination).await
+    }
+
     pub fn export_organization_vault(
         &self,
+        organization_id: Uuid,
         collections: Vec<Collection>,
         ciphers: Vec<Cipher>,
         format: ExportFormat,
     ) -> Result<String, ExportError> {
-        export_organization_vault(collections, ciphers, format)
+        export_organization_vault(self.client, organization_id, collections, ciphers, format)
+    }
+
+    /// Import the folders and ciphers of a Bitwarden or third party export into the vault of the
+    /// current user
+    pub async fn import_vault(
+        &self,
+        data: Vec<u8>,
+        format: ImportFormat,
+    ) -> Result<ImportResult, ImportError> {
+        import_vault(self.client, &data, format).await
     }
 }
 
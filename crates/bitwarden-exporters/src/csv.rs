@@ -1,21 +1,72 @@
 use std::collections::HashMap;
 
-use csv::Writer;
-use serde::Serializer;
+use chrono::Utc;
+use csv::{Reader, Writer};
+use serde::{Deserialize, Deserializer, Serializer};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::{Cipher, CipherType, Field, Folder};
+use crate::{
+    Card, Cipher, CipherType, Collection, Field, Folder, Identity, Login, LoginUri, SecureNote,
+    SecureNoteType,
+};
 
 #[derive(Debug, Error)]
 pub enum CsvError {
     #[error("CSV error")]
     Csv,
+    #[error("Invalid CSV row {row}: {message}")]
+    InvalidRow { row: u64, message: String },
+    #[error(
+        "Unsupported item type \"{0}\", only logins, notes, cards and identities can be imported \
+         from CSV"
+    )]
+    UnsupportedType(String),
 }
 
 pub(crate) fn export_csv(folders: Vec<Folder>, ciphers: Vec<Cipher>) -> Result<String, CsvError> {
     let folders: HashMap<Uuid, String> = folders.into_iter().map(|f| (f.id, f.name)).collect();
 
+    let rows = ciphers
+        .into_iter()
+        .filter(|c| matches!(c.r#type, CipherType::Login(_) | CipherType::SecureNote(_)))
+        .map(|c| CsvRow::from(CsvAllTypesRow::new(c, &folders)));
+
+    let mut wtr = Writer::from_writer(vec![]);
+    for row in rows {
+        wtr.serialize(row).expect("Serialize should be infallible");
+    }
+
+    String::from_utf8(wtr.into_inner().map_err(|_| CsvError::Csv)?).map_err(|_| CsvError::Csv)
+}
+
+/// Like [export_csv], but cards and identities are exported too, with their properties in
+/// additional `card_` and `identity_` columns. Older importers ignore these columns and import
+/// the cards and identities as logins with only their name, notes and fields.
+pub(crate) fn export_csv_all_types(
+    folders: Vec<Folder>,
+    ciphers: Vec<Cipher>,
+) -> Result<String, CsvError> {
+    let folders: HashMap<Uuid, String> = folders.into_iter().map(|f| (f.id, f.name)).collect();
+
+    let mut wtr = Writer::from_writer(vec![]);
+    for c in ciphers {
+        wtr.serialize(CsvAllTypesRow::new(c, &folders))
+            .expect("Serialize should be infallible");
+    }
+
+    String::from_utf8(wtr.into_inner().map_err(|_| CsvError::Csv)?).map_err(|_| CsvError::Csv)
+}
+
+/// Organization exports have the names of the collections of each cipher instead of its folder,
+/// and no favorites since those are personal
+pub(crate) fn export_organization_csv(
+    collections: Vec<Collection>,
+    ciphers: Vec<Cipher>,
+) -> Result<String, CsvError> {
+    let collections: HashMap<Uuid, String> =
+        collections.into_iter().map(|c| (c.id, c.name)).collect();
+
     let rows = ciphers
         .into_iter()
         .filter(|c| matches!(c.r#type, CipherType::Login(_) | CipherType::SecureNote(_)))
@@ -26,12 +77,13 @@ pub(crate) fn export_csv(folders: Vec<Folder>, ciphers: Vec<Cipher>) -> Result<S
                 None
             };
 
-            CsvRow {
-                folder: c
-                    .folder_id
-                    .and_then(|f| folders.get(&f))
-                    .map(|f| f.to_owned()),
-                favorite: c.favorite,
+            OrganizationCsvRow {
+                collections: c
+                    .collection_ids
+                    .iter()
+                    .filter_map(|id| collections.get(id))
+                    .cloned()
+                    .collect(),
                 r#type: c.r#type.to_string(),
                 name: c.name.to_owned(),
                 notes: c.notes.to_owned(),
@@ -54,6 +106,101 @@ pub(crate) fn export_csv(folders: Vec<Folder>, ciphers: Vec<Cipher>) -> Result<S
     String::from_utf8(wtr.into_inner().map_err(|_| CsvError::Csv)?).map_err(|_| CsvError::Csv)
 }
 
+/// Parse a CSV export. CSV exports don't contain any identifiers, so every folder and cipher gets
+/// a new random id. Custom fields are imported as text fields since their type isn't exported.
+pub(crate) fn import_csv(data: &str) -> Result<(Vec<Folder>, Vec<Cipher>), CsvError> {
+    let mut folders: Vec<Folder> = Vec::new();
+    let mut ciphers = Vec::new();
+
+    let mut rdr = Reader::from_reader(data.as_bytes());
+    for row in rdr.deserialize() {
+        let row: CsvAllTypesRow = row.map_err(|e| CsvError::InvalidRow {
+            row: e.position().map(|p| p.line()).unwrap_or_default(),
+            message: e.to_string(),
+        })?;
+
+        let folder_id = row.folder.filter(|f| !f.is_empty()).map(|name| {
+            match folders.iter().find(|f| f.name == name) {
+                Some(f) => f.id,
+                None => {
+                    let id = Uuid::new_v4();
+                    folders.push(Folder { id, name });
+                    id
+                }
+            }
+        });
+
+        let r#type = match row.r#type.as_str() {
+            "login" => CipherType::Login(Box::new(Login {
+                username: row.login_username,
+                password: row.login_password,
+                login_uris: row
+                    .login_uri
+                    .into_iter()
+                    .map(|uri| LoginUri {
+                        uri: Some(uri),
+                        r#match: None,
+                    })
+                    .collect(),
+                totp: row.login_totp,
+                fido2_credentials: vec![],
+            })),
+            "note" => CipherType::SecureNote(Box::new(SecureNote {
+                r#type: SecureNoteType::Generic,
+            })),
+            "card" => CipherType::Card(Box::new(Card {
+                cardholder_name: row.card_cardholder_name,
+                exp_month: row.card_exp_month,
+                exp_year: row.card_exp_year,
+                code: row.card_code,
+                brand: row.card_brand,
+                number: row.card_number,
+            })),
+            "identity" => CipherType::Identity(Box::new(Identity {
+                title: row.identity_title,
+                first_name: row.identity_first_name,
+                middle_name: row.identity_middle_name,
+                last_name: row.identity_last_name,
+                address1: row.identity_address1,
+                address2: row.identity_address2,
+                address3: row.identity_address3,
+                city: row.identity_city,
+                state: row.identity_state,
+                postal_code: row.identity_postal_code,
+                country: row.identity_country,
+                company: row.identity_company,
+                email: row.identity_email,
+                phone: row.identity_phone,
+                ssn: row.identity_ssn,
+                username: row.identity_username,
+                passport_number: row.identity_passport_number,
+                license_number: row.identity_license_number,
+            })),
+            t => return Err(CsvError::UnsupportedType(t.to_owned())),
+        };
+
+        let now = Utc::now();
+        ciphers.push(Cipher {
+            id: Uuid::new_v4(),
+            folder_id,
+            organization_id: None,
+            collection_ids: vec![],
+            name: row.name,
+            notes: row.notes,
+            r#type,
+            favorite: row.favorite,
+            reprompt: row.reprompt,
+            fields: row.fields,
+            password_history: vec![],
+            revision_date: now,
+            creation_date: now,
+            deleted_date: None,
+        });
+    }
+
+    Ok((folders, ciphers))
+}
+
 /// CSV export format. See <https://bitwarden.com/help/condition-bitwarden-import/#condition-a-csv>
 ///
 /// Be careful when changing this struct to maintain compatibility with old exports.
@@ -75,6 +222,184 @@ struct CsvRow {
     login_totp: Option<String>,
 }
 
+impl From<CsvAllTypesRow> for CsvRow {
+    fn from(row: CsvAllTypesRow) -> Self {
+        CsvRow {
+            folder: row.folder,
+            favorite: row.favorite,
+            r#type: row.r#type,
+            name: row.name,
+            notes: row.notes,
+            fields: row.fields,
+            reprompt: row.reprompt,
+            login_uri: row.login_uri,
+            login_username: row.login_username,
+            login_password: row.login_password,
+            login_totp: row.login_totp,
+        }
+    }
+}
+
+/// The columns of [CsvRow] followed by the `card_` and `identity_` columns, see
+/// [export_csv_all_types]. Imports read every CSV export as this row, the additional columns are
+/// empty in exports of logins and notes only.
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct CsvAllTypesRow {
+    folder: Option<String>,
+    #[serde(
+        serialize_with = "bool_serialize",
+        deserialize_with = "bool_deserialize",
+        default
+    )]
+    favorite: bool,
+    r#type: String,
+    name: String,
+    notes: Option<String>,
+    #[serde(
+        serialize_with = "fields_serialize",
+        deserialize_with = "fields_deserialize",
+        default
+    )]
+    fields: Vec<Field>,
+    #[serde(deserialize_with = "reprompt_deserialize", default)]
+    reprompt: u8,
+    #[serde(
+        serialize_with = "vec_serialize",
+        deserialize_with = "vec_deserialize",
+        default
+    )]
+    login_uri: Vec<String>,
+    login_username: Option<String>,
+    login_password: Option<String>,
+    login_totp: Option<String>,
+
+    #[serde(default)]
+    card_cardholder_name: Option<String>,
+    #[serde(default)]
+    card_brand: Option<String>,
+    #[serde(default)]
+    card_number: Option<String>,
+    #[serde(default)]
+    card_exp_month: Option<String>,
+    #[serde(default)]
+    card_exp_year: Option<String>,
+    #[serde(default)]
+    card_code: Option<String>,
+
+    #[serde(default)]
+    identity_title: Option<String>,
+    #[serde(default)]
+    identity_first_name: Option<String>,
+    #[serde(default)]
+    identity_middle_name: Option<String>,
+    #[serde(default)]
+    identity_last_name: Option<String>,
+    #[serde(default)]
+    identity_address1: Option<String>,
+    #[serde(default)]
+    identity_address2: Option<String>,
+    #[serde(default)]
+    identity_address3: Option<String>,
+    #[serde(default)]
+    identity_city: Option<String>,
+    #[serde(default)]
+    identity_state: Option<String>,
+    #[serde(default)]
+    identity_postal_code: Option<String>,
+    #[serde(default)]
+    identity_country: Option<String>,
+    #[serde(default)]
+    identity_company: Option<String>,
+    #[serde(default)]
+    identity_email: Option<String>,
+    #[serde(default)]
+    identity_phone: Option<String>,
+    #[serde(default)]
+    identity_ssn: Option<String>,
+    #[serde(default)]
+    identity_username: Option<String>,
+    #[serde(default)]
+    identity_passport_number: Option<String>,
+    #[serde(default)]
+    identity_license_number: Option<String>,
+}
+
+impl CsvAllTypesRow {
+    fn new(cipher: Cipher, folders: &HashMap<Uuid, String>) -> Self {
+        let mut row = CsvAllTypesRow {
+            folder: cipher
+                .folder_id
+                .and_then(|f| folders.get(&f))
+                .map(|f| f.to_owned()),
+            favorite: cipher.favorite,
+            r#type: cipher.r#type.to_string(),
+            name: cipher.name,
+            notes: cipher.notes,
+            fields: cipher.fields,
+            reprompt: cipher.reprompt,
+            ..Default::default()
+        };
+
+        match cipher.r#type {
+            CipherType::Login(login) => {
+                row.login_uri = login.login_uris.into_iter().flat_map(|u| u.uri).collect();
+                row.login_username = login.username;
+                row.login_password = login.password;
+                row.login_totp = login.totp;
+            }
+            CipherType::SecureNote(_) => {}
+            CipherType::Card(card) => {
+                row.card_cardholder_name = card.cardholder_name;
+                row.card_brand = card.brand;
+                row.card_number = card.number;
+                row.card_exp_month = card.exp_month;
+                row.card_exp_year = card.exp_year;
+                row.card_code = card.code;
+            }
+            CipherType::Identity(identity) => {
+                row.identity_title = identity.title;
+                row.identity_first_name = identity.first_name;
+                row.identity_middle_name = identity.middle_name;
+                row.identity_last_name = identity.last_name;
+                row.identity_address1 = identity.address1;
+                row.identity_address2 = identity.address2;
+                row.identity_address3 = identity.address3;
+                row.identity_city = identity.city;
+                row.identity_state = identity.state;
+                row.identity_postal_code = identity.postal_code;
+                row.identity_country = identity.country;
+                row.identity_company = identity.company;
+                row.identity_email = identity.email;
+                row.identity_phone = identity.phone;
+                row.identity_ssn = identity.ssn;
+                row.identity_username = identity.username;
+                row.identity_passport_number = identity.passport_number;
+                row.identity_license_number = identity.license_number;
+            }
+        }
+
+        row
+    }
+}
+
+/// CSV format of organization exports
+#[derive(serde::Serialize)]
+struct OrganizationCsvRow {
+    #[serde(serialize_with = "vec_serialize")]
+    collections: Vec<String>,
+    r#type: String,
+    name: String,
+    notes: Option<String>,
+    #[serde(serialize_with = "fields_serialize")]
+    fields: Vec<Field>,
+    reprompt: u8,
+    #[serde(serialize_with = "vec_serialize")]
+    login_uri: Vec<String>,
+    login_username: Option<String>,
+    login_password: Option<String>,
+    login_totp: Option<String>,
+}
+
 fn vec_serialize<S>(x: &[String], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -93,19 +418,73 @@ fn fields_serialize<S>(x: &[Field], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    s.serialize_str(
-        x.iter()
-            .map(|f| {
-                format!(
-                    "{}: {}",
-                    f.name.to_owned().unwrap_or_default(),
-                    f.value.to_owned().unwrap_or_default()
-                )
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
-            .as_str(),
-    )
+    s.serialize_str(fields_to_string(x).as_str())
+}
+
+/// Fields are exported as one `name: value` pair per line
+fn fields_to_string(fields: &[Field]) -> String {
+    fields
+        .iter()
+        .map(|f| {
+            format!(
+                "{}: {}",
+                f.name.to_owned().unwrap_or_default(),
+                f.value.to_owned().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn vec_deserialize<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(d)?;
+    Ok(s.split(',')
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .map(|u| u.to_owned())
+        .collect())
+}
+
+fn bool_deserialize<'de, D>(d: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(d)?;
+    Ok(s == "1" || s.eq_ignore_ascii_case("true"))
+}
+
+fn reprompt_deserialize<'de, D>(d: D) -> Result<u8, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(d)?;
+    if s.is_empty() {
+        return Ok(0);
+    }
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+/// Fields are exported as one `name: value` pair per line
+fn fields_deserialize<'de, D>(d: D) -> Result<Vec<Field>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(d)?;
+    Ok(s.lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let (name, value) = l.split_once(": ").unwrap_or((l, ""));
+            Field {
+                name: Some(name.to_owned()),
+                value: (!value.is_empty()).then(|| value.to_owned()),
+                r#type: 0,
+                linked_id: None,
+            }
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -129,6 +508,8 @@ mod tests {
             Cipher {
                 id: "d55d65d7-c161-40a4-94ca-b0d20184d91a".parse().unwrap(),
                 folder_id: None,
+                organization_id: None,
+                collection_ids: vec![],
                 name: "test@bitwarden.com".to_string(),
                 notes: None,
                 r#type: CipherType::Login(Box::new(Login {
@@ -139,10 +520,12 @@ mod tests {
                         r#match: None,
                     }],
                     totp: None,
+                    fido2_credentials: vec![],
                 })),
                 favorite: false,
                 reprompt: 0,
                 fields: vec![],
+                password_history: vec![],
                 revision_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
                 creation_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
                 deleted_date: None,
@@ -150,6 +533,8 @@ mod tests {
             Cipher {
                 id: "7dd81bd0-cc72-4f42-96e7-b0fc014e71a3".parse().unwrap(),
                 folder_id: Some("583e7665-0126-4d37-9139-b0d20184dd86".parse().unwrap()),
+                organization_id: None,
+                collection_ids: vec![],
                 name: "Steam Account".to_string(),
                 notes: None,
                 r#type: CipherType::Login(Box::new(Login {
@@ -160,6 +545,7 @@ mod tests {
                         r#match: None,
                     }],
                     totp: Some("steam://ABCD123".to_string()),
+                    fido2_credentials: vec![],
                 })),
                 favorite: true,
                 reprompt: 0,
@@ -177,6 +563,7 @@ mod tests {
                         linked_id: None,
                     },
                 ],
+                password_history: vec![],
                 revision_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
                 creation_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
                 deleted_date: None,
@@ -200,6 +587,8 @@ mod tests {
         let ciphers = vec![Cipher {
             id: "d55d65d7-c161-40a4-94ca-b0d20184d91a".parse().unwrap(),
             folder_id: None,
+            organization_id: None,
+            collection_ids: vec![],
             name: "My Card".to_string(),
             notes: None,
             r#type: CipherType::Card(Box::new(Card {
@@ -213,6 +602,7 @@ mod tests {
             favorite: false,
             reprompt: 0,
             fields: vec![],
+            password_history: vec![],
             revision_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
             creation_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
             deleted_date: None,
@@ -229,6 +619,8 @@ mod tests {
         let ciphers = vec![Cipher {
             id: "d55d65d7-c161-40a4-94ca-b0d20184d91a".parse().unwrap(),
             folder_id: None,
+            organization_id: None,
+            collection_ids: vec![],
             name: "My Identity".to_string(),
             notes: None,
             r#type: CipherType::Identity(Box::new(Identity {
@@ -254,6 +646,7 @@ mod tests {
             favorite: false,
             reprompt: 0,
             fields: vec![],
+            password_history: vec![],
             revision_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
             creation_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
             deleted_date: None,
@@ -263,4 +656,166 @@ mod tests {
 
         assert_eq!(csv, "");
     }
+
+    #[test]
+    fn test_import_csv() {
+        let csv = [
+            "folder,favorite,type,name,notes,fields,reprompt,login_uri,login_username,login_password,login_totp",
+            ",,login,test@bitwarden.com,,,0,https://google.com,test@bitwarden.com,Abc123,",
+            "Test Folder B,1,login,Steam Account,,\"Test: v\nHidden: asdfer\",0,\"https://steampowered.com,https://store.steampowered.com\",steam,3Pvb8u7EfbV*nJ,steam://ABCD123",
+            "Test Folder B,,note,My note,Very secure!,,1,,,,",
+            "",
+        ].join("\n");
+
+        let (folders, ciphers) = import_csv(&csv).unwrap();
+
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].name, "Test Folder B");
+
+        assert_eq!(ciphers.len(), 3);
+        assert_eq!(ciphers[0].folder_id, None);
+        assert!(!ciphers[0].favorite);
+
+        let steam = &ciphers[1];
+        assert_eq!(steam.folder_id, Some(folders[0].id));
+        assert!(steam.favorite);
+        assert_eq!(steam.fields.len(), 2);
+        assert_eq!(steam.fields[1].name.as_deref(), Some("Hidden"));
+        assert_eq!(steam.fields[1].value.as_deref(), Some("asdfer"));
+        let CipherType::Login(login) = &steam.r#type else {
+            panic!("Expected a login");
+        };
+        assert_eq!(login.username.as_deref(), Some("steam"));
+        assert_eq!(login.password.as_deref(), Some("3Pvb8u7EfbV*nJ"));
+        assert_eq!(login.totp.as_deref(), Some("steam://ABCD123"));
+        assert_eq!(login.login_uris.len(), 2);
+
+        let note = &ciphers[2];
+        assert_eq!(note.folder_id, Some(folders[0].id));
+        assert_eq!(note.notes.as_deref(), Some("Very secure!"));
+        assert_eq!(note.reprompt, 1);
+        assert!(matches!(note.r#type, CipherType::SecureNote(_)));
+    }
+
+    #[test]
+    fn test_import_csv_round_trip() {
+        let csv = [
+            "folder,favorite,type,name,notes,fields,reprompt,login_uri,login_username,login_password,login_totp",
+            ",,login,test@bitwarden.com,,,0,https://google.com,test@bitwarden.com,Abc123,",
+            "Test Folder B,1,login,Steam Account,,\"Test: v\nHidden: asdfer\",0,https://steampowered.com,steam,3Pvb8u7EfbV*nJ,steam://ABCD123",
+            "",
+        ].join("\n");
+
+        let (folders, ciphers) = import_csv(&csv).unwrap();
+
+        assert_eq!(export_csv(folders, ciphers).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_import_csv_unsupported_type() {
+        let csv = [
+            "folder,favorite,type,name,notes,fields,reprompt,login_uri,login_username,login_password,login_totp",
+            ",,sshkey,My Key,,,0,,,,",
+        ].join("\n");
+
+        assert!(matches!(
+            import_csv(&csv),
+            Err(CsvError::UnsupportedType(t)) if t == "sshkey"
+        ));
+    }
+
+    #[test]
+    fn test_export_csv_all_types() {
+        let ciphers = vec![
+            Cipher {
+                id: "d55d65d7-c161-40a4-94ca-b0d20184d91a".parse().unwrap(),
+                folder_id: None,
+                organization_id: None,
+                collection_ids: vec![],
+                name: "My Card".to_string(),
+                notes: None,
+                r#type: CipherType::Card(Box::new(Card {
+                    cardholder_name: Some("John Doe".to_string()),
+                    exp_month: Some("1".to_string()),
+                    exp_year: Some("2032".to_string()),
+                    code: Some("123".to_string()),
+                    brand: Some("Visa".to_string()),
+                    number: Some("4111111111111111".to_string()),
+                })),
+                favorite: false,
+                reprompt: 1,
+                fields: vec![],
+                password_history: vec![],
+                revision_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
+                creation_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
+                deleted_date: None,
+            },
+            Cipher {
+                id: "7dd81bd0-cc72-4f42-96e7-b0fc014e71a3".parse().unwrap(),
+                folder_id: None,
+                organization_id: None,
+                collection_ids: vec![],
+                name: "My Identity".to_string(),
+                notes: Some("Passport".to_string()),
+                r#type: CipherType::Identity(Box::new(Identity {
+                    title: Some("Mr".to_string()),
+                    first_name: Some("John".to_string()),
+                    middle_name: None,
+                    last_name: Some("Doe".to_string()),
+                    address1: None,
+                    address2: None,
+                    address3: None,
+                    city: Some("Oslo".to_string()),
+                    state: None,
+                    postal_code: None,
+                    country: Some("NO".to_string()),
+                    company: None,
+                    email: Some("john@example.com".to_string()),
+                    phone: None,
+                    ssn: None,
+                    username: None,
+                    passport_number: Some("X1234567".to_string()),
+                    license_number: None,
+                })),
+                favorite: true,
+                reprompt: 0,
+                fields: vec![],
+                password_history: vec![],
+                revision_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
+                creation_date: "2024-01-30T11:28:20.036Z".parse().unwrap(),
+                deleted_date: None,
+            },
+        ];
+
+        let csv = export_csv_all_types(vec![], ciphers).unwrap();
+        let expected = [
+            "folder,favorite,type,name,notes,fields,reprompt,login_uri,login_username,login_password,login_totp,\
+card_cardholder_name,card_brand,card_number,card_exp_month,card_exp_year,card_code,\
+identity_title,identity_first_name,identity_middle_name,identity_last_name,identity_address1,identity_address2,identity_address3,\
+identity_city,identity_state,identity_postal_code,identity_country,identity_company,identity_email,identity_phone,identity_ssn,\
+identity_username,identity_passport_number,identity_license_number",
+            ",,card,My Card,,,1,,,,,John Doe,Visa,4111111111111111,1,2032,123,,,,,,,,,,,,,,,,,,",
+            ",1,identity,My Identity,Passport,,0,,,,,,,,,,,Mr,John,,Doe,,,,Oslo,,,NO,,john@example.com,,,,X1234567,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+
+        // The export can be imported back
+        let (_, ciphers) = import_csv(&csv).unwrap();
+        assert_eq!(ciphers.len(), 2);
+        assert_eq!(ciphers[0].reprompt, 1);
+        let CipherType::Card(card) = &ciphers[0].r#type else {
+            panic!("Expected a card");
+        };
+        assert_eq!(card.number.as_deref(), Some("4111111111111111"));
+        assert_eq!(card.exp_year.as_deref(), Some("2032"));
+        let CipherType::Identity(identity) = &ciphers[1].r#type else {
+            panic!("Expected an identity");
+        };
+        assert_eq!(identity.first_name.as_deref(), Some("John"));
+        assert_eq!(identity.middle_name, None);
+        assert_eq!(identity.passport_number.as_deref(), Some("X1234567"));
+        assert!(ciphers[1].favorite);
+    }
 }
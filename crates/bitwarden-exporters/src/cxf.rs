@@ -0,0 +1,1315 @@
+//! The FIDO Alliance Credential Exchange Format (CXF), used to move credentials and passkeys
+//! between providers.
+//! See <https://fidoalliance.org/specifications-credential-exchange-specifications/>
+//!
+//! Only the unencrypted export is handled here, encrypting it for the Credential Exchange Protocol
+//! (CXP) is left to the clients since it depends on the platform performing the exchange.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use pkcs8::{ObjectIdentifier, PrivateKeyInfo};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    importers::{new_cipher, non_empty, ParsedImport},
+    Account, Card, Cipher, CipherType, Fido2Credential, Field, Folder, Identity, Login, LoginUri,
+    SecureNote, SecureNoteType,
+};
+
+#[derive(Error, Debug)]
+pub enum CxfError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Unsupported CXF version {0}, only version 1 is supported")]
+    UnsupportedVersion(u8),
+}
+
+/// Labels of the identity properties that have no CXF credential and are exported as custom
+/// fields
+const IDENTITY_EMAIL: &str = "Email";
+const IDENTITY_COMPANY: &str = "Company";
+const IDENTITY_SSN: &str = "SSN";
+const IDENTITY_USERNAME: &str = "Username";
+
+/// Algorithm identifier of elliptic curve keys, `id-ecPublicKey`
+const EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+/// Curve identifier of P-256, `prime256v1`
+const P256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+
+pub(crate) fn export_cxf(
+    account: Account,
+    folders: Vec<Folder>,
+    ciphers: Vec<Cipher>,
+) -> Result<String, CxfError> {
+    let collections = folders
+        .into_iter()
+        .map(|f| CxfCollection {
+            id: encode_uuid(f.id),
+            title: f.name,
+            items: ciphers
+                .iter()
+                .filter(|c| c.folder_id == Some(f.id))
+                .map(|c| LinkedItem {
+                    item: encode_uuid(c.id),
+                    account: None,
+                })
+                .collect(),
+            sub_collections: vec![],
+        })
+        .filter(|c| !c.items.is_empty())
+        .collect();
+
+    let header = Header {
+        version: Version { major: 1, minor: 0 },
+        exporter_rp_id: "bitwarden.com".to_owned(),
+        exporter_display_name: "Bitwarden".to_owned(),
+        timestamp: Utc::now().timestamp() as u64,
+        accounts: vec![CxfAccount {
+            id: encode_uuid(account.id),
+            username: String::new(),
+            email: account.email,
+            full_name: account.name,
+            collections,
+            items: ciphers
+                .into_iter()
+                .map(export_item)
+                .collect::<Result<_, _>>()?,
+        }],
+    };
+
+    Ok(serde_json::to_string_pretty(&header)?)
+}
+
+fn export_item(cipher: Cipher) -> Result<Item, CxfError> {
+    let mut credentials = Vec::new();
+    let mut fields = Vec::new();
+    let mut urls = Vec::new();
+
+    match cipher.r#type {
+        CipherType::Login(login) => {
+            if login.username.is_some() || login.password.is_some() {
+                credentials.push(Credential::BasicAuth(BasicAuth {
+                    username: EditableField::optional(FieldType::String, login.username),
+                    password: EditableField::optional(FieldType::ConcealedString, login.password),
+                }));
+            }
+            credentials.extend(
+                login
+                    .fido2_credentials
+                    .into_iter()
+                    .map(|p| Credential::Passkey(p.into())),
+            );
+            if let Some(totp) = login.totp {
+                match Totp::parse(&totp) {
+                    Some(totp) => credentials.push(Credential::Totp(totp)),
+                    // Keep the values that aren't valid TOTP secrets instead of dropping them
+                    None => fields.push(EditableField::new(
+                        FieldType::ConcealedString,
+                        totp,
+                        Some("TOTP".to_owned()),
+                    )),
+                }
+            }
+            urls = login.login_uris.into_iter().filter_map(|u| u.uri).collect();
+        }
+        CipherType::SecureNote(_) => {}
+        CipherType::Card(card) => credentials.push(Credential::CreditCard((*card).into())),
+        CipherType::Identity(identity) => export_identity(*identity, &mut credentials, &mut fields),
+    }
+
+    if let Some(notes) = cipher.notes {
+        credentials.push(Credential::Note(Note {
+            content: EditableField::new(FieldType::String, notes, None),
+        }));
+    }
+
+    fields.extend(cipher.fields.into_iter().filter_map(|f| {
+        let field_type = match f.r#type {
+            0 => FieldType::String,
+            1 => FieldType::ConcealedString,
+            2 => FieldType::Boolean,
+            // Linked fields refer to properties of the item, which CXF has no equivalent for
+            _ => return None,
+        };
+        Some(EditableField::new(
+            field_type,
+            f.value.unwrap_or_default(),
+            f.name,
+        ))
+    }));
+    if !fields.is_empty() {
+        credentials.push(Credential::CustomFields(CustomFields {
+            label: None,
+            fields,
+        }));
+    }
+
+    Ok(Item {
+        id: encode_uuid(cipher.id),
+        creation_at: Some(cipher.creation_date.timestamp() as u64),
+        modified_at: Some(cipher.revision_date.timestamp() as u64),
+        title: cipher.name,
+        favorite: Some(cipher.favorite),
+        scope: (!urls.is_empty()).then(|| CredentialScope {
+            urls,
+            android_apps: vec![],
+        }),
+        credentials: credentials
+            .into_iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn export_identity(
+    identity: Identity,
+    credentials: &mut Vec<Credential>,
+    fields: &mut Vec<EditableField>,
+) {
+    let name = PersonName {
+        title: EditableField::optional(FieldType::String, identity.title),
+        given: EditableField::optional(FieldType::String, identity.first_name),
+        given2: EditableField::optional(FieldType::String, identity.middle_name),
+        surname: EditableField::optional(FieldType::String, identity.last_name),
+    };
+    if name.title.is_some()
+        || name.given.is_some()
+        || name.given2.is_some()
+        || name.surname.is_some()
+    {
+        credentials.push(Credential::PersonName(name));
+    }
+
+    let street = [identity.address1, identity.address2, identity.address3]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let address = Address {
+        street_address: EditableField::optional(FieldType::String, non_empty(&street)),
+        postal_code: EditableField::optional(FieldType::String, identity.postal_code),
+        city: EditableField::optional(FieldType::String, identity.city),
+        territory: EditableField::optional(FieldType::String, identity.state),
+        country: EditableField::optional(FieldType::String, identity.country),
+        tel: EditableField::optional(FieldType::String, identity.phone),
+    };
+    if address.street_address.is_some()
+        || address.postal_code.is_some()
+        || address.city.is_some()
+        || address.territory.is_some()
+        || address.country.is_some()
+        || address.tel.is_some()
+    {
+        credentials.push(Credential::Address(address));
+    }
+
+    if let Some(number) = identity.passport_number {
+        credentials.push(Credential::Passport(Passport {
+            passport_number: Some(EditableField::new(FieldType::String, number, None)),
+        }));
+    }
+    if let Some(number) = identity.license_number {
+        credentials.push(Credential::DriversLicense(DriversLicense {
+            license_number: Some(EditableField::new(FieldType::String, number, None)),
+        }));
+    }
+
+    let values = [
+        (IDENTITY_EMAIL, FieldType::Email, identity.email),
+        (IDENTITY_COMPANY, FieldType::String, identity.company),
+        (IDENTITY_SSN, FieldType::ConcealedString, identity.ssn),
+        (IDENTITY_USERNAME, FieldType::String, identity.username),
+    ];
+    for (label, field_type, value) in values {
+        if let Some(value) = value {
+            fields.push(EditableField::new(
+                field_type,
+                value,
+                Some(label.to_owned()),
+            ));
+        }
+    }
+}
+
+/// Parse a CXF export. Items are mapped to logins when they have a username, password, passkey
+/// or TOTP credential, and otherwise to cards, identities or secure notes. Collections become
+/// folders.
+pub(crate) fn import_cxf(data: &str) -> Result<ParsedImport, CxfError> {
+    let header: Header = serde_json::from_str(data)?;
+    if header.version.major != 1 {
+        return Err(CxfError::UnsupportedVersion(header.version.major));
+    }
+
+    let mut import = ParsedImport::default();
+    let mut row = 0;
+    for account in header.accounts {
+        let mut folders = HashMap::new();
+        for collection in &account.collections {
+            collection_folders(collection, "", &mut folders);
+        }
+
+        for item in account.items {
+            row += 1;
+            let folder = folders.get(&item.id).cloned();
+            import_item(&mut import, row, item, folder);
+        }
+    }
+
+    Ok(import)
+}
+
+/// The folder of every item in a collection, nested collections become `Parent/Child` folders
+fn collection_folders(
+    collection: &CxfCollection,
+    parent: &str,
+    folders: &mut HashMap<String, String>,
+) {
+    let name = if parent.is_empty() {
+        collection.title.clone()
+    } else {
+        format!("{parent}/{}", collection.title)
+    };
+
+    for linked in &collection.items {
+        folders
+            .entry(linked.item.clone())
+            .or_insert_with(|| name.clone());
+    }
+    for sub_collection in &collection.sub_collections {
+        collection_folders(sub_collection, &name, folders);
+    }
+}
+
+fn import_item(import: &mut ParsedImport, row: usize, item: Item, folder: Option<String>) {
+    let creation_date = timestamp(item.creation_at).unwrap_or_else(Utc::now);
+    let revision_date = timestamp(item.modified_at).unwrap_or(creation_date);
+
+    let mut basic_auth = None;
+    let mut passkeys = Vec::new();
+    let mut totp = None;
+    let mut notes = Vec::new();
+    let mut card = None;
+    let mut name = None;
+    let mut address = None;
+    let mut passport = None;
+    let mut drivers_license = None;
+    let mut fields = Vec::new();
+
+    for value in item.credentials {
+        let kind = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("unknown")
+            .to_owned();
+        let Ok(credential) = serde_json::from_value::<Credential>(value) else {
+            import.warn(row, format!("The {kind} credential couldn't be imported"));
+            continue;
+        };
+
+        match credential {
+            Credential::BasicAuth(c) if basic_auth.is_none() => basic_auth = Some(c),
+            Credential::Passkey(c) => match c.into_fido2_credential(creation_date) {
+                Ok(passkey) => passkeys.push(passkey),
+                Err(e) => import.warn(row, e.to_string()),
+            },
+            Credential::Totp(c) if totp.is_none() => totp = Some(c.into_bitwarden()),
+            Credential::Note(c) => notes.push(c.content.value),
+            Credential::CreditCard(c) if card.is_none() => card = Some(c),
+            Credential::PersonName(c) if name.is_none() => name = Some(c),
+            Credential::Address(c) if address.is_none() => address = Some(c),
+            Credential::Passport(c) if passport.is_none() => passport = Some(c),
+            Credential::DriversLicense(c) if drivers_license.is_none() => drivers_license = Some(c),
+            Credential::CustomFields(c) => fields.extend(c.fields.into_iter().map(Field::from)),
+            _ => import.warn(
+                row,
+                format!("Only the first {kind} credential of the item was imported"),
+            ),
+        }
+    }
+
+    let is_identity =
+        name.is_some() || address.is_some() || passport.is_some() || drivers_license.is_some();
+    let r#type = if basic_auth.is_some()
+        || !passkeys.is_empty()
+        || totp.is_some()
+        || item.scope.as_ref().is_some_and(|s| !s.urls.is_empty())
+    {
+        if card.is_some() || is_identity {
+            import.warn(
+                row,
+                "The item is imported as a login, its card and identity details were skipped",
+            );
+        }
+
+        let basic_auth = basic_auth.unwrap_or_default();
+        CipherType::Login(Box::new(Login {
+            username: basic_auth.username.and_then(|f| non_empty(&f.value)),
+            password: basic_auth.password.and_then(|f| non_empty(&f.value)),
+            login_uris: item
+                .scope
+                .map(|s| s.urls)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|uri| LoginUri {
+                    uri: Some(uri),
+                    r#match: None,
+                })
+                .collect(),
+            totp,
+            fido2_credentials: passkeys,
+        }))
+    } else if let Some(card) = card {
+        if is_identity {
+            import.warn(
+                row,
+                "The item is imported as a card, its identity details were skipped",
+            );
+        }
+        CipherType::Card(Box::new(card.into()))
+    } else if is_identity {
+        let mut identity = Identity {
+            title: None,
+            first_name: None,
+            middle_name: None,
+            last_name: None,
+            address1: None,
+            address2: None,
+            address3: None,
+            city: None,
+            state: None,
+            postal_code: None,
+            country: None,
+            company: None,
+            email: None,
+            phone: None,
+            ssn: None,
+            username: None,
+            passport_number: passport.and_then(|p| value(p.passport_number)),
+            license_number: drivers_license.and_then(|d| value(d.license_number)),
+        };
+        if let Some(name) = name {
+            identity.title = value(name.title);
+            identity.first_name = value(name.given);
+            identity.middle_name = value(name.given2);
+            identity.last_name = value(name.surname);
+        }
+        if let Some(address) = address {
+            let street = value(address.street_address).unwrap_or_default();
+            let mut lines = street.lines().filter(|l| !l.trim().is_empty());
+            identity.address1 = lines.next().map(str::to_owned);
+            identity.address2 = lines.next().map(str::to_owned);
+            identity.address3 = non_empty(&lines.collect::<Vec<_>>().join(", "));
+            identity.postal_code = value(address.postal_code);
+            identity.city = value(address.city);
+            identity.state = value(address.territory);
+            identity.country = value(address.country);
+            identity.phone = value(address.tel);
+        }
+
+        // The identity properties exported as custom fields
+        fields.retain(|f| {
+            let property = match f.name.as_deref() {
+                Some(IDENTITY_EMAIL) => &mut identity.email,
+                Some(IDENTITY_COMPANY) => &mut identity.company,
+                Some(IDENTITY_SSN) => &mut identity.ssn,
+                Some(IDENTITY_USERNAME) => &mut identity.username,
+                _ => return true,
+            };
+            if property.is_some() {
+                return true;
+            }
+            *property = f.value.clone();
+            false
+        });
+
+        CipherType::Identity(Box::new(identity))
+    } else {
+        CipherType::SecureNote(Box::new(SecureNote {
+            r#type: SecureNoteType::Generic,
+        }))
+    };
+
+    let mut cipher = new_cipher(import.name(row, non_empty(&item.title)), r#type);
+    cipher.folder_id = folder.and_then(|f| import.folder(&f));
+    cipher.notes = non_empty(&notes.join("\n\n"));
+    cipher.favorite = item.favorite.unwrap_or_default();
+    cipher.fields = fields;
+    cipher.creation_date = creation_date;
+    cipher.revision_date = revision_date;
+    import.ciphers.push(cipher);
+}
+
+fn timestamp(seconds: Option<u64>) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds? as i64, 0)
+}
+
+fn value(field: Option<EditableField>) -> Option<String> {
+    field.and_then(|f| non_empty(&f.value))
+}
+
+/// CXF identifies items with base64url encoded bytes
+fn encode_uuid(id: Uuid) -> String {
+    URL_SAFE_NO_PAD.encode(id.as_bytes())
+}
+
+/// Some exporters pad their base64url values
+fn decode_base64url(value: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD.decode(value.trim_end_matches('=')).ok()
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Header {
+    version: Version,
+    exporter_rp_id: String,
+    exporter_display_name: String,
+    timestamp: u64,
+    accounts: Vec<CxfAccount>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Version {
+    major: u8,
+    minor: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CxfAccount {
+    id: String,
+    username: String,
+    email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_name: Option<String>,
+    #[serde(default)]
+    collections: Vec<CxfCollection>,
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CxfCollection {
+    id: String,
+    title: String,
+    #[serde(default)]
+    items: Vec<LinkedItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sub_collections: Vec<CxfCollection>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinkedItem {
+    item: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Item {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creation_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified_at: Option<u64>,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    favorite: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<CredentialScope>,
+    /// Kept as JSON on import, so the credentials we don't support can be reported instead of
+    /// failing the whole import
+    #[serde(default)]
+    credentials: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CredentialScope {
+    #[serde(default)]
+    urls: Vec<String>,
+    #[serde(default)]
+    android_apps: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum Credential {
+    BasicAuth(BasicAuth),
+    Passkey(Passkey),
+    Totp(Totp),
+    Note(Note),
+    CreditCard(CreditCard),
+    PersonName(PersonName),
+    Address(Address),
+    Passport(Passport),
+    DriversLicense(DriversLicense),
+    CustomFields(CustomFields),
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+enum FieldType {
+    String,
+    ConcealedString,
+    Email,
+    Number,
+    Boolean,
+    Date,
+    YearMonth,
+    /// Country codes, Wi-Fi security types and the types added by later versions are imported as
+    /// text
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EditableField {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    field_type: FieldType,
+    value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+}
+
+impl EditableField {
+    fn new(field_type: FieldType, value: String, label: Option<String>) -> Self {
+        Self {
+            id: None,
+            field_type,
+            value,
+            label,
+        }
+    }
+
+    fn optional(field_type: FieldType, value: Option<String>) -> Option<Self> {
+        value.map(|v| Self::new(field_type, v, None))
+    }
+}
+
+impl From<EditableField> for Field {
+    fn from(field: EditableField) -> Self {
+        Field {
+            name: field.label,
+            value: non_empty(&field.value),
+            r#type: match field.field_type {
+                FieldType::ConcealedString => 1,
+                FieldType::Boolean => 2,
+                _ => 0,
+            },
+            linked_id: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct BasicAuth {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    password: Option<EditableField>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Passkey {
+    credential_id: String,
+    rp_id: String,
+    username: String,
+    user_display_name: String,
+    user_handle: String,
+    /// The private key as a base64url encoded PKCS#8 document
+    key: String,
+}
+
+impl From<Fido2Credential> for Passkey {
+    fn from(credential: Fido2Credential) -> Self {
+        // Bitwarden stores credential ids as GUIDs, or prefixed with `b64.` when they aren't
+        let credential_id = match credential.credential_id.strip_prefix("b64.") {
+            Some(id) => id.to_owned(),
+            None => match Uuid::try_parse(&credential.credential_id) {
+                Ok(id) => encode_uuid(id),
+                Err(_) => URL_SAFE_NO_PAD.encode(credential.credential_id.as_bytes()),
+            },
+        };
+
+        Passkey {
+            credential_id,
+            rp_id: credential.rp_id,
+            username: credential.user_name.unwrap_or_default(),
+            user_display_name: credential.user_display_name.unwrap_or_default(),
+            user_handle: credential.user_handle.unwrap_or_default(),
+            key: credential.key_value,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+enum PasskeyError {
+    #[error("The passkey couldn't be imported, its credential id is invalid")]
+    InvalidCredentialId,
+    #[error("The passkey couldn't be imported, its key is invalid")]
+    InvalidKey,
+    #[error("The passkey couldn't be imported, only ECDSA P-256 keys are supported")]
+    UnsupportedKey,
+}
+
+impl Passkey {
+    /// Bitwarden only supports ECDSA P-256 passkeys, the algorithm and curve of other keys can't be
+    /// stored so they're rejected instead of being imported as unusable P-256 keys
+    fn into_fido2_credential(
+        self,
+        creation_date: DateTime<Utc>,
+    ) -> Result<Fido2Credential, PasskeyError> {
+        let credential_id =
+            decode_base64url(&self.credential_id).ok_or(PasskeyError::InvalidCredentialId)?;
+        let credential_id = match <[u8; 16]>::try_from(credential_id.as_slice()) {
+            Ok(bytes) => Uuid::from_bytes(bytes).to_string(),
+            Err(_) => format!("b64.{}", URL_SAFE_NO_PAD.encode(&credential_id)),
+        };
+
+        let key = decode_base64url(&self.key).ok_or(PasskeyError::InvalidKey)?;
+        let info =
+            PrivateKeyInfo::try_from(key.as_slice()).map_err(|_| PasskeyError::InvalidKey)?;
+        if info.algorithm.oid != EC_PUBLIC_KEY || info.algorithm.parameters_oid().ok() != Some(P256)
+        {
+            return Err(PasskeyError::UnsupportedKey);
+        }
+
+        Ok(Fido2Credential {
+            credential_id,
+            key_type: "public-key".to_owned(),
+            key_algorithm: "ECDSA".to_owned(),
+            key_curve: "P-256".to_owned(),
+            key_value: URL_SAFE_NO_PAD.encode(&key),
+            rp_id: self.rp_id,
+            user_handle: non_empty(&self.user_handle),
+            user_name: non_empty(&self.username),
+            counter: "0".to_owned(),
+            rp_name: None,
+            user_display_name: non_empty(&self.user_display_name),
+            discoverable: "true".to_owned(),
+            creation_date,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Totp {
+    /// Base32 encoded secret
+    secret: String,
+    period: u32,
+    digits: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    /// `sha1`, `sha256`, `sha512` or `steam` for Steam Guard codes
+    algorithm: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issuer: Option<String>,
+}
+
+impl Totp {
+    /// Bitwarden stores either a base32 secret, an `otpauth://` URI or a `steam://` secret
+    fn parse(value: &str) -> Option<Totp> {
+        let value = value.trim();
+        let mut totp = Totp {
+            secret: String::new(),
+            period: 30,
+            digits: 6,
+            username: None,
+            algorithm: "sha1".to_owned(),
+            issuer: None,
+        };
+
+        if let Some(secret) = value.strip_prefix("steam://") {
+            totp.secret = secret.to_owned();
+            totp.digits = 5;
+            totp.algorithm = "steam".to_owned();
+        } else if value.to_lowercase().starts_with("otpauth://") {
+            let url = url::Url::parse(value).ok()?;
+            for (key, value) in url.query_pairs() {
+                match key.to_lowercase().as_str() {
+                    "secret" => totp.secret = value.into_owned(),
+                    "period" => totp.period = value.parse().ok()?,
+                    "digits" => totp.digits = value.parse().ok()?,
+                    "algorithm" => totp.algorithm = value.to_lowercase(),
+                    "issuer" => totp.issuer = non_empty(&value),
+                    _ => {}
+                }
+            }
+
+            // The label is `issuer:username` or only the username
+            let label = url::form_urlencoded::parse(url.path().trim_start_matches('/').as_bytes())
+                .map(|(key, _)| key.into_owned())
+                .next()
+                .unwrap_or_default();
+            let username = match label.split_once(':') {
+                Some((issuer, username)) => {
+                    totp.issuer = totp.issuer.or_else(|| non_empty(issuer));
+                    username
+                }
+                None => label.as_str(),
+            };
+            totp.username = non_empty(username.trim());
+        } else {
+            totp.secret = value.to_owned();
+        }
+
+        totp.secret = totp
+            .secret
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '=')
+            .collect::<String>()
+            .to_uppercase();
+        let is_base32 = totp
+            .secret
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c));
+        (!totp.secret.is_empty() && is_base32).then_some(totp)
+    }
+
+    fn into_bitwarden(self) -> String {
+        let algorithm = self.algorithm.to_lowercase();
+        if algorithm == "steam" {
+            return format!("steam://{}", self.secret);
+        }
+        if algorithm == "sha1"
+            && self.period == 30
+            && self.digits == 6
+            && self.issuer.is_none()
+            && self.username.is_none()
+        {
+            return self.secret;
+        }
+
+        let label = match (&self.issuer, &self.username) {
+            (Some(issuer), Some(username)) => format!("{issuer}:{username}"),
+            (Some(label), None) | (None, Some(label)) => label.to_owned(),
+            (None, None) => String::new(),
+        };
+        let mut url = url::Url::parse("otpauth://totp/").expect("URL is valid");
+        url.set_path(&format!("/{label}"));
+        url.query_pairs_mut()
+            .append_pair("secret", &self.secret)
+            .append_pair("algorithm", &algorithm.to_uppercase())
+            .append_pair("digits", &self.digits.to_string())
+            .append_pair("period", &self.period.to_string());
+        if let Some(issuer) = &self.issuer {
+            url.query_pairs_mut().append_pair("issuer", issuer);
+        }
+        url.to_string()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Note {
+    content: EditableField,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreditCard {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    number: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    full_name: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    card_type: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification_number: Option<EditableField>,
+    /// `YYYY-MM`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expiry_date: Option<EditableField>,
+}
+
+impl From<Card> for CreditCard {
+    fn from(card: Card) -> Self {
+        let expiry_date = match (card.exp_year, card.exp_month) {
+            (Some(year), Some(month)) => {
+                let year = if year.len() == 2 {
+                    format!("20{year}")
+                } else {
+                    year
+                };
+                Some(EditableField::new(
+                    FieldType::YearMonth,
+                    format!("{year}-{month:0>2}"),
+                    None,
+                ))
+            }
+            _ => None,
+        };
+
+        CreditCard {
+            number: EditableField::optional(FieldType::ConcealedString, card.number),
+            full_name: EditableField::optional(FieldType::String, card.cardholder_name),
+            card_type: EditableField::optional(FieldType::String, card.brand),
+            verification_number: EditableField::optional(FieldType::ConcealedString, card.code),
+            expiry_date,
+        }
+    }
+}
+
+impl From<CreditCard> for Card {
+    fn from(card: CreditCard) -> Self {
+        let expiry_date = value(card.expiry_date);
+        let (exp_year, exp_month) = match expiry_date.as_deref().and_then(|d| d.split_once('-')) {
+            Some((year, month)) => (non_empty(year), non_empty(month.trim_start_matches('0'))),
+            None => (None, None),
+        };
+
+        Card {
+            cardholder_name: value(card.full_name),
+            exp_month,
+            exp_year,
+            code: value(card.verification_number),
+            brand: value(card.card_type),
+            number: value(card.number),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersonName {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    given: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    given2: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    surname: Option<EditableField>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Address {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    street_address: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    postal_code: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    city: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    territory: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    country: Option<EditableField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tel: Option<EditableField>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Passport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    passport_number: Option<EditableField>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DriversLicense {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    license_number: Option<EditableField>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CustomFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default)]
+    fields: Vec<EditableField>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PasswordHistory;
+
+    /// PKCS#8 document of an ECDSA P-256 key
+    const P256_KEY: &str = "MIGHAgEAMBMGByqGSM49AgEGCCqGSM49AwEHBG0wawIBAQQg5FDAk5WvmlfDkbJzs7o1HKSXB3V71m4qeBcP0qOQ0kuhRANCAARkiTYctbutXMO4Ai-D-Pgqa6oHB4YfUNvzQBdETRgUWa_SrDlFfxabUGf-YMOXDln2wonlCvCP-fLBkYVJN7AO";
+
+    fn login() -> Cipher {
+        Cipher {
+            id: "25c8c414-b446-48e9-a1bd-b10700bbd740".parse().unwrap(),
+            folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+            organization_id: None,
+            collection_ids: vec![],
+            name: "Bitwarden".to_string(),
+            notes: Some("My note".to_string()),
+            r#type: CipherType::Login(Box::new(Login {
+                username: Some("test@bitwarden.com".to_string()),
+                password: Some("asdfasdfasdf".to_string()),
+                login_uris: vec![LoginUri {
+                    uri: Some("https://vault.bitwarden.com".to_string()),
+                    r#match: None,
+                }],
+                totp: Some(
+                    "otpauth://totp/Bitwarden:test@bitwarden.com?secret=JBSWY3DPEHPK3PXP&digits=8"
+                        .to_string(),
+                ),
+                fido2_credentials: vec![Fido2Credential {
+                    credential_id: "e8d88789-e916-e196-3cbd-81dafae71bbc".to_string(),
+                    key_type: "public-key".to_string(),
+                    key_algorithm: "ECDSA".to_string(),
+                    key_curve: "P-256".to_string(),
+                    key_value: P256_KEY.to_string(),
+                    rp_id: "bitwarden.com".to_string(),
+                    user_handle: Some("YWxpY2U".to_string()),
+                    user_name: Some("test@bitwarden.com".to_string()),
+                    counter: "0".to_string(),
+                    rp_name: Some("Bitwarden".to_string()),
+                    user_display_name: Some("Test".to_string()),
+                    discoverable: "true".to_string(),
+                    creation_date: "2024-01-30T17:55:36Z".parse().unwrap(),
+                }],
+            })),
+            favorite: true,
+            reprompt: 0,
+            fields: vec![
+                Field {
+                    name: Some("Text".to_string()),
+                    value: Some("A".to_string()),
+                    r#type: 0,
+                    linked_id: None,
+                },
+                Field {
+                    name: Some("Hidden".to_string()),
+                    value: Some("B".to_string()),
+                    r#type: 1,
+                    linked_id: None,
+                },
+                Field {
+                    name: Some("Linked".to_string()),
+                    value: None,
+                    r#type: 3,
+                    linked_id: Some(101),
+                },
+            ],
+            password_history: vec![PasswordHistory {
+                password: "old".to_string(),
+                last_used_date: "2024-01-30T17:55:36Z".parse().unwrap(),
+            }],
+            revision_date: "2024-01-30T17:55:36Z".parse().unwrap(),
+            creation_date: "2024-01-30T11:23:54Z".parse().unwrap(),
+            deleted_date: None,
+        }
+    }
+
+    #[test]
+    fn test_export_cxf() {
+        let export = export_cxf(
+            Account {
+                id: "060000fb-0922-4dd3-b170-6e15cb5df8c8".parse().unwrap(),
+                email: "test@bitwarden.com".to_string(),
+                name: Some("Test".to_string()),
+            },
+            vec![Folder {
+                id: "942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap(),
+                name: "Important".to_string(),
+            }],
+            vec![login()],
+        )
+        .unwrap();
+        let export: serde_json::Value = serde_json::from_str(&export).unwrap();
+
+        assert_eq!(export["version"]["major"], 1);
+        let account = &export["accounts"][0];
+        assert_eq!(account["id"], "BgAA-wkiTdOxcG4Vy134yA");
+        assert_eq!(account["email"], "test@bitwarden.com");
+        assert_eq!(account["collections"][0]["title"], "Important");
+        assert_eq!(
+            account["collections"][0]["items"][0]["item"],
+            account["items"][0]["id"]
+        );
+
+        let item = &account["items"][0];
+        assert_eq!(item["title"], "Bitwarden");
+        assert_eq!(item["favorite"], true);
+        assert_eq!(item["creationAt"], 1706613834);
+        assert_eq!(item["scope"]["urls"][0], "https://vault.bitwarden.com");
+
+        let credentials = item["credentials"].as_array().unwrap();
+        let types: Vec<_> = credentials.iter().map(|c| c["type"].as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                Some("basic-auth"),
+                Some("passkey"),
+                Some("totp"),
+                Some("note"),
+                Some("custom-fields")
+            ]
+        );
+        assert_eq!(credentials[0]["password"]["fieldType"], "concealed-string");
+        assert_eq!(credentials[0]["password"]["value"], "asdfasdfasdf");
+        assert_eq!(credentials[1]["credentialId"], "6NiHiekW4ZY8vYHa-ucbvA");
+        assert_eq!(credentials[1]["userHandle"], "YWxpY2U");
+        assert_eq!(credentials[1]["key"], P256_KEY);
+        assert_eq!(credentials[2]["secret"], "JBSWY3DPEHPK3PXP");
+        assert_eq!(credentials[2]["digits"], 8);
+        assert_eq!(credentials[2]["issuer"], "Bitwarden");
+        assert_eq!(credentials[2]["username"], "test@bitwarden.com");
+        assert_eq!(credentials[3]["content"]["value"], "My note");
+        // Linked fields have no equivalent
+        assert_eq!(credentials[4]["fields"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_cxf_round_trip() {
+        let card = Cipher {
+            id: Uuid::new_v4(),
+            folder_id: None,
+            name: "My Card".to_string(),
+            notes: None,
+            r#type: CipherType::Card(Box::new(Card {
+                cardholder_name: Some("John Doe".to_string()),
+                exp_month: Some("1".to_string()),
+                exp_year: Some("32".to_string()),
+                code: Some("123".to_string()),
+                brand: Some("Visa".to_string()),
+                number: Some("4111111111111111".to_string()),
+            })),
+            fields: vec![],
+            ..login()
+        };
+        let identity = Cipher {
+            id: Uuid::new_v4(),
+            folder_id: None,
+            name: "My Identity".to_string(),
+            notes: None,
+            r#type: CipherType::Identity(Box::new(Identity {
+                title: Some("Mr".to_string()),
+                first_name: Some("John".to_string()),
+                middle_name: None,
+                last_name: Some("Doe".to_string()),
+                address1: Some("1 Main Street".to_string()),
+                address2: Some("Apartment 2".to_string()),
+                address3: None,
+                city: Some("Oslo".to_string()),
+                state: None,
+                postal_code: Some("0150".to_string()),
+                country: Some("NO".to_string()),
+                company: Some("Bitwarden".to_string()),
+                email: Some("john@example.com".to_string()),
+                phone: None,
+                ssn: Some("123-45-6789".to_string()),
+                username: None,
+                passport_number: Some("X1234567".to_string()),
+                license_number: None,
+            })),
+            fields: vec![],
+            ..login()
+        };
+        let note = Cipher {
+            id: Uuid::new_v4(),
+            folder_id: None,
+            name: "My Note".to_string(),
+            r#type: CipherType::SecureNote(Box::new(SecureNote {
+                r#type: SecureNoteType::Generic,
+            })),
+            fields: vec![],
+            ..login()
+        };
+
+        let export = export_cxf(
+            Account {
+                id: Uuid::new_v4(),
+                email: "test@bitwarden.com".to_string(),
+                name: None,
+            },
+            vec![Folder {
+                id: "942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap(),
+                name: "Important".to_string(),
+            }],
+            vec![login(), card, identity, note],
+        )
+        .unwrap();
+        let import = import_cxf(&export).unwrap();
+
+        assert!(import.warnings.is_empty());
+        assert_eq!(import.folders.len(), 1);
+        assert_eq!(import.folders[0].name, "Important");
+        assert_eq!(import.ciphers.len(), 4);
+
+        let login = &import.ciphers[0];
+        assert_eq!(login.name, "Bitwarden");
+        assert_eq!(login.folder_id, Some(import.folders[0].id));
+        assert_eq!(login.notes.as_deref(), Some("My note"));
+        assert!(login.favorite);
+        assert_eq!(
+            login.creation_date,
+            "2024-01-30T11:23:54Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(login.fields.len(), 2);
+        assert_eq!(login.fields[1].name.as_deref(), Some("Hidden"));
+        assert_eq!(login.fields[1].r#type, 1);
+        let CipherType::Login(l) = &login.r#type else {
+            panic!("Expected a login");
+        };
+        assert_eq!(l.username.as_deref(), Some("test@bitwarden.com"));
+        assert_eq!(l.password.as_deref(), Some("asdfasdfasdf"));
+        assert_eq!(
+            l.login_uris[0].uri.as_deref(),
+            Some("https://vault.bitwarden.com")
+        );
+        assert_eq!(
+            l.totp.as_deref(),
+            Some("otpauth://totp/Bitwarden:test@bitwarden.com?secret=JBSWY3DPEHPK3PXP&algorithm=SHA1&digits=8&period=30&issuer=Bitwarden")
+        );
+        let passkey = &l.fido2_credentials[0];
+        assert_eq!(
+            passkey.credential_id,
+            "e8d88789-e916-e196-3cbd-81dafae71bbc"
+        );
+        assert_eq!(passkey.key_value, P256_KEY);
+        assert_eq!(passkey.rp_id, "bitwarden.com");
+        assert_eq!(passkey.user_handle.as_deref(), Some("YWxpY2U"));
+        assert_eq!(passkey.user_name.as_deref(), Some("test@bitwarden.com"));
+
+        let CipherType::Card(card) = &import.ciphers[1].r#type else {
+            panic!("Expected a card");
+        };
+        assert_eq!(card.number.as_deref(), Some("4111111111111111"));
+        assert_eq!(card.exp_month.as_deref(), Some("1"));
+        assert_eq!(card.exp_year.as_deref(), Some("2032"));
+        assert_eq!(card.brand.as_deref(), Some("Visa"));
+
+        let CipherType::Identity(identity) = &import.ciphers[2].r#type else {
+            panic!("Expected an identity");
+        };
+        assert_eq!(identity.first_name.as_deref(), Some("John"));
+        assert_eq!(identity.address1.as_deref(), Some("1 Main Street"));
+        assert_eq!(identity.address2.as_deref(), Some("Apartment 2"));
+        assert_eq!(identity.postal_code.as_deref(), Some("0150"));
+        assert_eq!(identity.email.as_deref(), Some("john@example.com"));
+        assert_eq!(identity.company.as_deref(), Some("Bitwarden"));
+        assert_eq!(identity.ssn.as_deref(), Some("123-45-6789"));
+        assert_eq!(identity.passport_number.as_deref(), Some("X1234567"));
+        assert!(import.ciphers[2].fields.is_empty());
+
+        let note = &import.ciphers[3];
+        assert!(matches!(note.r#type, CipherType::SecureNote(_)));
+        assert_eq!(note.notes.as_deref(), Some("My note"));
+    }
+
+    #[test]
+    fn test_import_cxf_warnings() {
+        let data = r#"{
+            "version": { "major": 1, "minor": 0 },
+            "exporterRpId": "example.com",
+            "exporterDisplayName": "Example",
+            "timestamp": 1706613834,
+            "accounts": [{
+                "id": "AAAA",
+                "username": "",
+                "email": "test@example.com",
+                "items": [
+                    {
+                        "id": "AQID",
+                        "title": "Router",
+                        "credentials": [
+                            { "type": "wifi", "ssid": { "fieldType": "string", "value": "Home" } },
+                            { "type": "note", "content": { "fieldType": "string", "value": "Upstairs" } }
+                        ]
+                    },
+                    {
+                        "id": "BAUG",
+                        "title": "",
+                        "credentials": [
+                            { "type": "totp", "secret": "JBSWY3DPEHPK3PXP", "period": 30, "digits": 6, "algorithm": "sha1" },
+                            { "type": "totp", "secret": "GEZDGNBV", "period": 30, "digits": 6, "algorithm": "sha1" },
+                            { "type": "passkey", "credentialId": "AQID", "rpId": "example.com", "username": "", "userDisplayName": "", "userHandle": "", "key": "not base64!" },
+                            { "type": "passkey", "credentialId": "BAUG", "rpId": "example.com", "username": "", "userDisplayName": "", "userHandle": "", "key": "AAECAwQF" },
+                            { "type": "passkey", "credentialId": "BwgJ", "rpId": "example.com", "username": "", "userDisplayName": "", "userHandle": "", "key": "MC4CAQAwBQYDK2VwBCIEIF4gWWEKIGtlpC0Ur0nVUAJyPqFdJkRDKzEe_Wp4xAef" },
+                            { "type": "passkey", "credentialId": "CgsM", "rpId": "example.com", "username": "", "userDisplayName": "", "userHandle": "", "key": "MIG2AgEAMBAGByqGSM49AgEGBSuBBAAiBIGeMIGbAgEBBDBNQS5AxFbg_optmBRtdcTWTV0o4z_T2zg5I2d7zjd6FDr36g_CRn_8ZdcipZXEf5ShZANiAAQgYZJfGaBFIOMM7bYa20olG6-jbLZKnb4fbwZD32FJPURD-HRqGBvw8m01URSFAkF696FCBTgI1c6vcvWAuV6yp5Sm0bZBbU3ZXZyyFE6XljyquVK7wbOJPpeCvnegtEo" }
+                        ]
+                    }
+                ]
+            }]
+        }"#;
+
+        let import = import_cxf(data).unwrap();
+        assert_eq!(import.ciphers.len(), 2);
+
+        let router = &import.ciphers[0];
+        assert!(matches!(router.r#type, CipherType::SecureNote(_)));
+        assert_eq!(router.notes.as_deref(), Some("Upstairs"));
+
+        let CipherType::Login(login) = &import.ciphers[1].r#type else {
+            panic!("Expected a login");
+        };
+        assert_eq!(import.ciphers[1].name, "--");
+        assert_eq!(login.totp.as_deref(), Some("JBSWY3DPEHPK3PXP"));
+        assert!(login.fido2_credentials.is_empty());
+
+        let warnings: Vec<_> = import
+            .warnings
+            .iter()
+            .map(|w| (w.row, w.message.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (1, "The wifi credential couldn't be imported"),
+                (2, "Only the first totp credential of the item was imported"),
+                (2, "The passkey couldn't be imported, its key is invalid"),
+                (2, "The passkey couldn't be imported, its key is invalid"),
+                (
+                    2,
+                    "The passkey couldn't be imported, only ECDSA P-256 keys are supported"
+                ),
+                (
+                    2,
+                    "The passkey couldn't be imported, only ECDSA P-256 keys are supported"
+                ),
+                (2, "The item has no name, it was imported as \"--\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_cxf_unsupported_version() {
+        let data = r#"{ "version": { "major": 2, "minor": 0 }, "exporterRpId": "example.com",
+            "exporterDisplayName": "Example", "timestamp": 0, "accounts": [] }"#;
+        assert!(matches!(
+            import_cxf(data),
+            Err(CxfError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_totp() {
+        let parse = Totp::parse;
+
+        let secret = parse("jbsw y3dp ehpk 3pxp").unwrap();
+        assert_eq!(secret.secret, "JBSWY3DPEHPK3PXP");
+        assert_eq!((secret.period, secret.digits), (30, 6));
+        assert_eq!(secret.into_bitwarden(), "JBSWY3DPEHPK3PXP");
+
+        let steam = parse("steam://JBSWY3DPEHPK3PXP").unwrap();
+        assert_eq!(steam.algorithm, "steam");
+        assert_eq!(steam.digits, 5);
+        assert_eq!(steam.into_bitwarden(), "steam://JBSWY3DPEHPK3PXP");
+
+        let uri = parse(
+            "otpauth://totp/ACME%20Co:john?secret=JBSWY3DPEHPK3PXP&algorithm=SHA256&period=60",
+        )
+        .unwrap();
+        assert_eq!(uri.issuer.as_deref(), Some("ACME Co"));
+        assert_eq!(uri.username.as_deref(), Some("john"));
+        assert_eq!(uri.algorithm, "sha256");
+        assert_eq!(uri.period, 60);
+
+        assert_eq!(parse("not a secret!"), None);
+        assert_eq!(parse("otpauth://totp/label?digits=6"), None);
+    }
+}
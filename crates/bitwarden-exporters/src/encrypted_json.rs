@@ -1,11 +1,15 @@
+use std::num::NonZeroU32;
+
 use base64::{engine::general_purpose::STANDARD, Engine};
-use bitwarden_crypto::{generate_random_bytes, Kdf, KeyEncryptable, PinKey};
-use serde::Serialize;
+use bitwarden_crypto::{
+    generate_random_bytes, EncString, Kdf, KeyDecryptable, KeyEncryptable, PinKey,
+};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
 use crate::{
-    json::{self, export_json},
+    json::{self, export_json, import_json},
     Cipher, Folder,
 };
 
@@ -19,6 +23,13 @@ pub enum EncryptedJsonError {
 
     #[error("Cryptography error, {0}")]
     Crypto(#[from] bitwarden_crypto::CryptoError),
+
+    #[error("Only password protected exports can be imported")]
+    NotPasswordProtected,
+    #[error("Invalid KDF settings")]
+    InvalidKdf,
+    #[error("Wrong password")]
+    WrongPassword,
 }
 
 pub(crate) fn export_encrypted_json(
@@ -27,8 +38,16 @@ pub(crate) fn export_encrypted_json(
     password: String,
     kdf: Kdf,
 ) -> Result<String, EncryptedJsonError> {
-    let decrypted_export = export_json(folders, ciphers)?;
+    password_protect(export_json(folders, ciphers)?, password, kdf)
+}
 
+/// Encrypt an unencrypted JSON export with a key derived from the password, using the KDF
+/// settings of the account
+pub(crate) fn password_protect(
+    decrypted_export: String,
+    password: String,
+    kdf: Kdf,
+) -> Result<String, EncryptedJsonError> {
     let (kdf_type, kdf_iterations, kdf_memory, kdf_parallelism) = match kdf {
         Kdf::PBKDF2 { iterations } => (0, iterations.get(), None, None),
         Kdf::Argon2id {
@@ -64,7 +83,49 @@ pub(crate) fn export_encrypted_json(
     Ok(serde_json::to_string_pretty(&encrypted_export)?)
 }
 
-#[derive(Serialize)]
+/// Parse a password protected JSON export. The key is derived from the password using the salt and
+/// KDF settings stored in the export, and validated before decrypting the vault data.
+pub(crate) fn import_encrypted_json(
+    data: &str,
+    password: String,
+) -> Result<(Vec<Folder>, Vec<Cipher>), EncryptedJsonError> {
+    let export: EncryptedJsonExport = serde_json::from_str(data)?;
+    if !export.password_protected {
+        return Err(EncryptedJsonError::NotPasswordProtected);
+    }
+
+    let iterations =
+        NonZeroU32::new(export.kdf_iterations).ok_or(EncryptedJsonError::InvalidKdf)?;
+    let kdf = match export.kdf_type {
+        0 => Kdf::PBKDF2 { iterations },
+        1 => Kdf::Argon2id {
+            iterations,
+            memory: export
+                .kdf_memory
+                .and_then(NonZeroU32::new)
+                .ok_or(EncryptedJsonError::InvalidKdf)?,
+            parallelism: export
+                .kdf_parallelism
+                .and_then(NonZeroU32::new)
+                .ok_or(EncryptedJsonError::InvalidKdf)?,
+        },
+        _ => return Err(EncryptedJsonError::InvalidKdf),
+    };
+
+    let key = PinKey::derive(password.as_bytes(), export.salt.as_bytes(), &kdf)?;
+
+    let enc_key_validation: EncString = export.enc_key_validation.parse()?;
+    let _: String = enc_key_validation
+        .decrypt_with_key(&key)
+        .map_err(|_| EncryptedJsonError::WrongPassword)?;
+
+    let data: EncString = export.data.parse()?;
+    let data: String = data.decrypt_with_key(&key)?;
+
+    Ok(import_json(&data)?)
+}
+
+#[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct EncryptedJsonExport {
     encrypted: bool,
@@ -81,7 +142,7 @@ pub(crate) struct EncryptedJsonExport {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroU32;
+    use std::{fs, path::PathBuf};
 
     use super::*;
     use crate::{
@@ -99,6 +160,8 @@ mod tests {
                 Cipher {
                     id: "25c8c414-b446-48e9-a1bd-b10700bbd740".parse().unwrap(),
                     folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "Bitwarden".to_string(),
                     notes: Some("My note".to_string()),
@@ -111,6 +174,7 @@ mod tests {
                             r#match: None,
                         }],
                         totp: Some("ABC".to_string()),
+                        fido2_credentials: vec![],
                     })),
 
                     favorite: true,
@@ -148,6 +212,7 @@ mod tests {
                             linked_id: Some(101),
                         },
                     ],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T14:09:33.753Z".parse().unwrap(),
                     creation_date: "2024-01-30T11:23:54.416Z".parse().unwrap(),
@@ -156,6 +221,8 @@ mod tests {
                 Cipher {
                     id: "23f0f877-42b1-4820-a850-b10700bc41eb".parse().unwrap(),
                     folder_id: None,
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "My secure note".to_string(),
                     notes: Some("Very secure!".to_string()),
@@ -168,6 +235,7 @@ mod tests {
                     reprompt: 0,
 
                     fields: vec![],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T11:25:25.466Z".parse().unwrap(),
                     creation_date: "2024-01-30T11:25:25.466Z".parse().unwrap(),
@@ -176,6 +244,8 @@ mod tests {
                 Cipher {
                     id: "3ed8de45-48ee-4e26-a2dc-b10701276c53".parse().unwrap(),
                     folder_id: None,
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "My card".to_string(),
                     notes: None,
@@ -193,6 +263,7 @@ mod tests {
                     reprompt: 0,
 
                     fields: vec![],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
                     creation_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
@@ -201,6 +272,8 @@ mod tests {
                 Cipher {
                     id: "41cc3bc1-c3d9-4637-876c-b10701273712".parse().unwrap(),
                     folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "My identity".to_string(),
                     notes: None,
@@ -230,6 +303,7 @@ mod tests {
                     reprompt: 0,
 
                     fields: vec![],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T17:54:50.706Z".parse().unwrap(),
                     creation_date: "2024-01-30T17:54:50.706Z".parse().unwrap(),
@@ -243,4 +317,56 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    pub fn test_import_round_trip() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("resources");
+        d.push("json_export.json");
+        let expected = fs::read_to_string(d).unwrap();
+
+        let (folders, ciphers) = import_json(&expected).unwrap();
+        let kdf = Kdf::PBKDF2 {
+            iterations: NonZeroU32::new(600_000).unwrap(),
+        };
+        let encrypted =
+            export_encrypted_json(folders, ciphers, "password".to_owned(), kdf).unwrap();
+
+        let (folders, ciphers) = import_encrypted_json(&encrypted, "password".to_owned()).unwrap();
+        let export = export_json(folders, ciphers).unwrap();
+
+        assert_eq!(
+            export.parse::<serde_json::Value>().unwrap(),
+            expected.parse::<serde_json::Value>().unwrap()
+        );
+
+        assert!(matches!(
+            import_encrypted_json(&encrypted, "wrong".to_owned()),
+            Err(EncryptedJsonError::WrongPassword)
+        ));
+    }
+
+    #[test]
+    pub fn test_import_argon2() {
+        let kdf = Kdf::Argon2id {
+            iterations: NonZeroU32::new(3).unwrap(),
+            memory: NonZeroU32::new(16).unwrap(),
+            parallelism: NonZeroU32::new(4).unwrap(),
+        };
+        let encrypted = export_encrypted_json(
+            vec![Folder {
+                id: "942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap(),
+                name: "Important".to_string(),
+            }],
+            vec![],
+            "password".to_owned(),
+            kdf,
+        )
+        .unwrap();
+
+        let (folders, ciphers) = import_encrypted_json(&encrypted, "password".to_owned()).unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].name, "Important");
+        assert!(ciphers.is_empty());
+    }
 }
@@ -13,6 +13,49 @@ pub enum ExportError {
     Json(#[from] crate::json::JsonError),
     #[error("Encrypted JSON error: {0}")]
     EncryptedJsonError(#[from] crate::encrypted_json::EncryptedJsonError),
+    #[error("CXF error: {0}")]
+    Cxf(#[from] crate::CxfError),
+
+    #[error("The collections and ciphers must all belong to the exported organization")]
+    NotAnOrganization,
+    #[error("The export format isn't supported for this export")]
+    UnsupportedFormat,
+
+    #[error(transparent)]
+    Cipher(#[from] bitwarden_vault::CipherError),
+    #[error(transparent)]
+    Attachment(#[from] bitwarden_vault::AttachmentError),
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    BitwardenError(#[from] bitwarden_core::Error),
+    #[error(transparent)]
+    BitwardenCryptoError(#[from] bitwarden_crypto::CryptoError),
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error(transparent)]
+    MissingField(#[from] bitwarden_core::MissingFieldError),
+    #[error(transparent)]
+    VaultLocked(#[from] bitwarden_core::VaultLocked),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] crate::csv::CsvError),
+    #[error("JSON error: {0}")]
+    Json(#[from] crate::json::JsonError),
+    #[error("Encrypted JSON error: {0}")]
+    EncryptedJsonError(#[from] crate::encrypted_json::EncryptedJsonError),
+
+    #[error("CXF error: {0}")]
+    Cxf(#[from] crate::CxfError),
+    #[error(transparent)]
+    Importer(#[from] crate::ImporterError),
+    #[error(transparent)]
+    Cipher(#[from] bitwarden_vault::CipherError),
 
     #[error(transparent)]
     BitwardenError(#[from] bitwarden_core::Error),
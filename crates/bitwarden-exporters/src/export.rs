@@ -1,10 +1,24 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
 use bitwarden_core::Client;
-use bitwarden_crypto::KeyDecryptable;
-use bitwarden_vault::{Cipher, CipherView, Collection, Folder, FolderView};
+use bitwarden_crypto::{KeyContainer, KeyDecryptable, LocateKey};
+use bitwarden_vault::{
+    Cipher, CipherView, ClientVaultExt, Collection, CollectionView, Folder, FolderView,
+};
+use uuid::Uuid;
+use zip::{write::SimpleFileOptions, ZipWriter};
 
 use crate::{
-    csv::export_csv, encrypted_json::export_encrypted_json, json::export_json, ExportError,
-    ExportFormat,
+    csv::{export_csv, export_csv_all_types, export_organization_csv},
+    cxf::export_cxf,
+    encrypted_json::{export_encrypted_json, password_protect},
+    json::{export_account_encrypted_json, export_json, export_organization_json},
+    ExportError, ExportFormat,
 };
 
 pub(crate) fn export_vault(
@@ -19,25 +33,470 @@ pub(crate) fn export_vault(
     let folders: Vec<FolderView> = folders.decrypt_with_key(key)?;
     let folders: Vec<crate::Folder> = folders.into_iter().flat_map(|f| f.try_into()).collect();
 
-    let ciphers: Vec<CipherView> = ciphers.decrypt_with_key(key)?;
-    let ciphers: Vec<crate::Cipher> = ciphers.into_iter().flat_map(|c| c.try_into()).collect();
+    let decrypt = |ciphers: Vec<Cipher>| -> Result<Vec<crate::Cipher>, ExportError> {
+        let ciphers: Vec<CipherView> = ciphers.decrypt_with_key(key)?;
+        Ok(ciphers.into_iter().flat_map(|c| c.try_into()).collect())
+    };
 
     match format {
-        ExportFormat::Csv => Ok(export_csv(folders, ciphers)?),
-        ExportFormat::Json => Ok(export_json(folders, ciphers)?),
+        ExportFormat::Csv => Ok(export_csv(folders, decrypt(ciphers)?)?),
+        ExportFormat::CsvAllTypes => Ok(export_csv_all_types(folders, decrypt(ciphers)?)?),
+        ExportFormat::Json => Ok(export_json(folders, decrypt(ciphers)?)?),
         ExportFormat::EncryptedJson { password } => Ok(export_encrypted_json(
             folders,
-            ciphers,
+            decrypt(ciphers)?,
             password,
             client.internal.get_kdf()?,
         )?),
+        ExportFormat::AccountEncryptedJson => Ok(export_account_encrypted_json(
+            key,
+            Some(folders),
+            None,
+            ciphers,
+        )?),
+        ExportFormat::Cxf { account } => {
+            let ciphers: Vec<CipherView> = ciphers.decrypt_with_key(key)?;
+            let ciphers = ciphers
+                .into_iter()
+                .map(|c| with_passkeys(&enc, c))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect();
+            Ok(export_cxf(account, folders, ciphers)?)
+        }
+    }
+}
+
+/// Decrypted ciphers only contain the encrypted private keys of their passkeys, they are only
+/// decrypted for the formats exporting them
+fn with_passkeys(
+    enc: &dyn KeyContainer,
+    cipher: CipherView,
+) -> Result<Option<crate::Cipher>, ExportError> {
+    let has_passkeys = cipher
+        .login
+        .as_ref()
+        .and_then(|l| l.fido2_credentials.as_ref())
+        .is_some_and(|c| !c.is_empty());
+    let passkeys = if has_passkeys {
+        cipher.get_fido2_credentials(enc)?
+    } else {
+        vec![]
+    };
+
+    let Ok(mut cipher) = crate::Cipher::try_from(cipher) else {
+        return Ok(None);
+    };
+    if let crate::CipherType::Login(login) = &mut cipher.r#type {
+        login.fido2_credentials = passkeys.into_iter().map(|p| p.into()).collect();
     }
+    Ok(Some(cipher))
 }
 
+/// Export the collections and ciphers of an organization, like the organization export of the
+/// web vault. Both are decrypted with the organization key.
 pub(crate) fn export_organization_vault(
-    _collections: Vec<Collection>,
-    _ciphers: Vec<Cipher>,
-    _format: ExportFormat,
+    client: &Client,
+    organization_id: Uuid,
+    collections: Vec<Collection>,
+    ciphers: Vec<Cipher>,
+    format: ExportFormat,
 ) -> Result<String, ExportError> {
-    todo!();
+    let belongs_to_organization = collections
+        .iter()
+        .map(|c| Some(c.organization_id))
+        .chain(ciphers.iter().map(|c| c.organization_id))
+        .all(|id| id == Some(organization_id));
+    if !belongs_to_organization {
+        return Err(ExportError::NotAnOrganization);
+    }
+
+    let enc = client.internal.get_encryption_settings()?;
+
+    let decrypt = || -> Result<(Vec<crate::Collection>, Vec<crate::Cipher>), ExportError> {
+        let collections = collections
+            .iter()
+            .map(|c| -> Result<CollectionView, ExportError> {
+                let key = c.locate_key(&enc, &None)?;
+                Ok(c.decrypt_with_key(key)?)
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flat_map(|c| c.try_into())
+            .collect();
+
+        let ciphers = ciphers
+            .iter()
+            .map(|c| -> Result<CipherView, ExportError> {
+                let key = c.locate_key(&enc, &None)?;
+                Ok(c.decrypt_with_key(key)?)
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flat_map(|c| c.try_into())
+            .collect();
+
+        Ok((collections, ciphers))
+    };
+
+    match format {
+        ExportFormat::Csv => {
+            let (collections, ciphers) = decrypt()?;
+            Ok(export_organization_csv(collections, ciphers)?)
+        }
+        ExportFormat::CsvAllTypes | ExportFormat::Cxf { .. } => Err(ExportError::UnsupportedFormat),
+        ExportFormat::Json => {
+            let (collections, ciphers) = decrypt()?;
+            Ok(export_organization_json(collections, ciphers)?)
+        }
+        ExportFormat::EncryptedJson { password } => {
+            let (collections, ciphers) = decrypt()?;
+            Ok(password_protect(
+                export_organization_json(collections, ciphers)?,
+                password,
+                client.internal.get_kdf()?,
+            )?)
+        }
+        ExportFormat::AccountEncryptedJson => {
+            let key = enc.get_key(&Some(organization_id))?;
+            Ok(export_account_encrypted_json(
+                key,
+                None,
+                Some(collections),
+                ciphers,
+            )?)
+        }
+    }
+}
+
+/// Export the folders and ciphers as JSON in a zip archive, along with the decrypted files of
+/// their attachments. The archive contains `data.json` and the attachments in
+/// `attachments/<cipher id>/<file name>`, like the archive export of the web vault. Like the web
+/// vault, only the personal ciphers are exported, organization ciphers are left out.
+pub(crate) async fn export_vault_archive(
+    client: &Client,
+    folders: Vec<Folder>,
+    mut ciphers: Vec<Cipher>,
+    destination: &Path,
+) -> Result<(), ExportError> {
+    ciphers.retain(|c| c.organization_id.is_none());
+
+    let (folders, views) = {
+        let enc = client.internal.get_encryption_settings()?;
+        let key = enc.get_key(&None)?;
+
+        let folders: Vec<FolderView> = folders.decrypt_with_key(key)?;
+        let views: Vec<CipherView> = ciphers.decrypt_with_key(key)?;
+        (folders, views)
+    };
+
+    let mut names = HashSet::new();
+    let mut attachments = Vec::new();
+    for (cipher, view) in ciphers.into_iter().zip(&views) {
+        let Some(cipher_id) = view.id else {
+            continue;
+        };
+        for attachment in view.attachments.iter().flatten() {
+            let Some(id) = attachment.id.clone() else {
+                continue;
+            };
+
+            // File names come from the user, keep them from escaping the attachments directory
+            let file_name = attachment
+                .file_name
+                .as_deref()
+                .unwrap_or(&id)
+                .replace(['/', '\\'], "_");
+            let mut name = format!("attachments/{cipher_id}/{file_name}");
+            if !names.insert(name.clone()) {
+                name = format!("attachments/{cipher_id}/{id}_{file_name}");
+                names.insert(name.clone());
+            }
+            attachments.push((cipher.clone(), id, name));
+        }
+    }
+
+    let folders: Vec<crate::Folder> = folders.into_iter().flat_map(|f| f.try_into()).collect();
+    let views: Vec<crate::Cipher> = views.into_iter().flat_map(|c| c.try_into()).collect();
+    let data = export_json(folders, views)?;
+
+    let result = write_archive(client, data, attachments, destination).await;
+    if result.is_err() {
+        _ = std::fs::remove_file(destination);
+    }
+    result
+}
+
+async fn write_archive(
+    client: &Client,
+    data: String,
+    attachments: Vec<(Cipher, String, String)>,
+    destination: &Path,
+) -> Result<(), ExportError> {
+    let mut zip = ZipWriter::new(File::create(destination)?);
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("data.json", options)?;
+    zip.write_all(data.as_bytes())?;
+
+    // Attachments are downloaded one at a time next to the archive, so they are never held in
+    // memory
+    let mut download_path = destination.as_os_str().to_owned();
+    download_path.push(".attachment");
+    let download_path = Path::new(&download_path);
+
+    for (cipher, attachment_id, name) in attachments {
+        let result = async {
+            client
+                .vault()
+                .attachments()
+                .download_attachment(cipher, &attachment_id, download_path)
+                .await?;
+
+            zip.start_file(name, options)?;
+            io::copy(&mut File::open(download_path)?, &mut zip)?;
+            Ok::<_, ExportError>(())
+        }
+        .await;
+        _ = std::fs::remove_file(download_path);
+        result?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitwarden_core::client::test_accounts::test_bitwarden_com_account;
+    use bitwarden_crypto::KeyEncryptable;
+    use bitwarden_vault::{CipherRepromptType, CipherType, LoginView, PasswordHistoryView};
+
+    use super::*;
+
+    const ORGANIZATION_ID: &str = "1bc9ac1e-f5aa-45f2-94bf-b181009709b8";
+
+    fn collection(client: &Client, id: &str, name: &str) -> Collection {
+        let enc = client.internal.get_encryption_settings().unwrap();
+        let organization_id = ORGANIZATION_ID.parse().unwrap();
+        let key = enc.get_key(&Some(organization_id)).unwrap();
+
+        CollectionView {
+            id: Some(id.parse().unwrap()),
+            organization_id,
+            name: name.to_owned(),
+            external_id: None,
+            hide_passwords: false,
+            read_only: false,
+        }
+        .encrypt_with_key(key)
+        .unwrap()
+    }
+
+    fn cipher(client: &Client, collection_ids: Vec<Uuid>) -> Cipher {
+        let enc = client.internal.get_encryption_settings().unwrap();
+        let organization_id = ORGANIZATION_ID.parse().unwrap();
+        let key = enc.get_key(&Some(organization_id)).unwrap();
+
+        CipherView {
+            id: Some("fd411a1a-fec8-4070-985d-0e6560860e69".parse().unwrap()),
+            organization_id: Some(organization_id),
+            folder_id: None,
+            collection_ids,
+            key: None,
+            name: "Shared login".to_owned(),
+            notes: None,
+            r#type: CipherType::Login,
+            login: Some(LoginView {
+                username: Some("test@bitwarden.com".to_owned()),
+                password: Some("asdfasdfasdf".to_owned()),
+                password_revision_date: None,
+                uris: None,
+                totp: None,
+                autofill_on_page_load: None,
+                fido2_credentials: None,
+            }),
+            identity: None,
+            card: None,
+            secure_note: None,
+            favorite: false,
+            reprompt: CipherRepromptType::None,
+            organization_use_totp: true,
+            edit: true,
+            view_password: true,
+            local_data: None,
+            attachments: None,
+            fields: None,
+            password_history: None,
+            creation_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
+            deleted_date: None,
+            revision_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
+        }
+        .encrypt_with_key(key)
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_organization_vault() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+
+        let collection_id: Uuid = "66c5ca57-0868-4c7e-902f-b181009709c0".parse().unwrap();
+        let collections = vec![
+            collection(
+                &client,
+                "66c5ca57-0868-4c7e-902f-b181009709c0",
+                "Engineering",
+            ),
+            collection(&client, "a4dc2b92-7a6c-4bd6-b2a3-b181009709c1", "Marketing"),
+        ];
+        let ciphers = vec![cipher(&client, vec![collection_id])];
+
+        let organization_id = ORGANIZATION_ID.parse().unwrap();
+        let csv = export_organization_vault(
+            &client,
+            organization_id,
+            collections.clone(),
+            ciphers.clone(),
+            ExportFormat::Csv,
+        )
+        .unwrap();
+        assert_eq!(
+            csv,
+            "collections,type,name,notes,fields,reprompt,login_uri,login_username,login_password,login_totp
+Engineering,login,Shared login,,,0,,test@bitwarden.com,asdfasdfasdf,
+"
+        );
+
+        let json = export_organization_vault(
+            &client,
+            organization_id,
+            collections.clone(),
+            ciphers.clone(),
+            ExportFormat::Json,
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["encrypted"], false);
+        assert!(json.get("folders").is_none());
+        assert_eq!(json["collections"][1]["name"], "Marketing");
+        assert_eq!(json["collections"][1]["organizationId"], ORGANIZATION_ID);
+        assert_eq!(json["items"][0]["name"], "Shared login");
+        assert_eq!(json["items"][0]["organizationId"], ORGANIZATION_ID);
+        assert_eq!(
+            json["items"][0]["collectionIds"],
+            serde_json::json!([collection_id])
+        );
+
+        let encrypted = export_organization_vault(
+            &client,
+            organization_id,
+            collections,
+            ciphers,
+            ExportFormat::AccountEncryptedJson,
+        )
+        .unwrap();
+        let encrypted: serde_json::Value = serde_json::from_str(&encrypted).unwrap();
+        assert_eq!(encrypted["encrypted"], true);
+        assert!(encrypted["items"][0].get("edit").is_none());
+
+        // Everything is encrypted with the organization key
+        let enc = client.internal.get_encryption_settings().unwrap();
+        let key = enc
+            .get_key(&Some(ORGANIZATION_ID.parse().unwrap()))
+            .unwrap();
+        let decrypt = |value: &serde_json::Value| -> String {
+            let enc_string: bitwarden_crypto::EncString = value.as_str().unwrap().parse().unwrap();
+            enc_string.decrypt_with_key(key).unwrap()
+        };
+        decrypt(&encrypted["encKeyValidation_DO_NOT_EDIT"]);
+        assert_eq!(decrypt(&encrypted["collections"][0]["name"]), "Engineering");
+        assert_eq!(decrypt(&encrypted["items"][0]["name"]), "Shared login");
+    }
+
+    #[tokio::test]
+    async fn test_export_vault_archive() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+        let enc = client.internal.get_encryption_settings().unwrap();
+        let key = enc.get_key(&None).unwrap();
+
+        let org_key = enc
+            .get_key(&Some(ORGANIZATION_ID.parse().unwrap()))
+            .unwrap();
+        let mut view: CipherView = cipher(&client, vec![]).decrypt_with_key(org_key).unwrap();
+        view.organization_id = None;
+        view.name = "Personal login".to_owned();
+        view.password_history = Some(vec![PasswordHistoryView {
+            password: "old password".to_owned(),
+            last_used_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
+        }]);
+        // Organization ciphers are left out of the personal export
+        let ciphers = vec![view.encrypt_with_key(key).unwrap(), cipher(&client, vec![])];
+
+        let destination = std::env::temp_dir().join(format!("{}.zip", Uuid::new_v4()));
+        export_vault_archive(&client, vec![], ciphers, &destination)
+            .await
+            .unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&destination).unwrap()).unwrap();
+        let mut data = String::new();
+        io::Read::read_to_string(&mut archive.by_name("data.json").unwrap(), &mut data).unwrap();
+        std::fs::remove_file(&destination).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&data).unwrap();
+        assert_eq!(json["items"].as_array().unwrap().len(), 1);
+        assert_eq!(json["items"][0]["name"], "Personal login");
+        assert_eq!(
+            json["items"][0]["passwordHistory"][0]["password"],
+            "old password"
+        );
+        assert_eq!(archive.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_organization_vault_mixed_organizations() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+
+        let mut personal = cipher(&client, vec![]);
+        personal.organization_id = None;
+
+        let organization_id = ORGANIZATION_ID.parse().unwrap();
+        assert!(matches!(
+            export_organization_vault(
+                &client,
+                organization_id,
+                vec![],
+                vec![personal],
+                ExportFormat::Json
+            ),
+            Err(ExportError::NotAnOrganization)
+        ));
+        assert!(matches!(
+            export_organization_vault(
+                &client,
+                Uuid::new_v4(),
+                vec![],
+                vec![cipher(&client, vec![])],
+                ExportFormat::Json
+            ),
+            Err(ExportError::NotAnOrganization)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_empty_organization_vault() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+
+        let json = export_organization_vault(
+            &client,
+            ORGANIZATION_ID.parse().unwrap(),
+            vec![],
+            vec![],
+            ExportFormat::Json,
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["collections"], serde_json::json!([]));
+        assert_eq!(json["items"], serde_json::json!([]));
+    }
 }
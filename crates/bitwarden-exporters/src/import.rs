@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use bitwarden_api_api::models::{ImportCiphersRequestModel, Int32Int32KeyValuePair};
+use bitwarden_core::{Client, Error};
+use bitwarden_vault::{CipherView, ClientVaultExt, Fido2CredentialFullView, FolderView};
+use uuid::Uuid;
+
+use crate::{
+    csv::import_csv,
+    cxf::import_cxf,
+    encrypted_json::import_encrypted_json,
+    importers::{
+        import_1pux, import_chrome_csv, import_dashlane, import_firefox_csv, import_keepass_xml,
+        import_lastpass_csv, utf8, ParsedImport,
+    },
+    json::import_json,
+    Cipher, CipherType, Folder, ImportError, ImportFormat, ImportResult,
+};
+
+pub(crate) fn parse_import(data: &[u8], format: ImportFormat) -> Result<ParsedImport, ImportError> {
+    let bitwarden = |(folders, ciphers): (Vec<Folder>, Vec<Cipher>)| ParsedImport {
+        folders,
+        ciphers,
+        warnings: vec![],
+    };
+
+    Ok(match format {
+        ImportFormat::Csv => bitwarden(import_csv(utf8(data)?)?),
+        ImportFormat::Json => bitwarden(import_json(utf8(data)?)?),
+        ImportFormat::EncryptedJson { password } => {
+            bitwarden(import_encrypted_json(utf8(data)?, password)?)
+        }
+        ImportFormat::OnePassword1Pux => import_1pux(data)?,
+        ImportFormat::KeePassXml => import_keepass_xml(data)?,
+        ImportFormat::LastPassCsv => import_lastpass_csv(utf8(data)?)?,
+        ImportFormat::ChromeCsv => import_chrome_csv(utf8(data)?)?,
+        ImportFormat::FirefoxCsv => import_firefox_csv(utf8(data)?)?,
+        ImportFormat::DashlaneCsv => import_dashlane(data)?,
+        ImportFormat::Cxf => import_cxf(utf8(data)?)?,
+    })
+}
+
+pub(crate) async fn import_vault(
+    client: &Client,
+    data: &[u8],
+    format: ImportFormat,
+) -> Result<ImportResult, ImportError> {
+    let import = parse_import(data, format)?;
+    let result = ImportResult {
+        folders: import.folders.len() as u32,
+        ciphers: import.ciphers.len() as u32,
+        warnings: import.warnings,
+    };
+    let request = import_request(client, import.folders, import.ciphers)?;
+
+    let config = client.internal.get_api_configurations().await;
+    bitwarden_api_api::apis::import_ciphers_api::ciphers_import_post(&config.api, Some(request))
+        .await
+        .map_err(Error::from)?;
+
+    Ok(result)
+}
+
+/// Encrypt the imported folders and ciphers with the user key. The server always creates new
+/// folders and ciphers, so the ids are dropped and the folder of each cipher is sent as a
+/// relationship between their indexes in the request.
+fn import_request(
+    client: &Client,
+    folders: Vec<Folder>,
+    ciphers: Vec<Cipher>,
+) -> Result<ImportCiphersRequestModel, ImportError> {
+    let folder_indexes: HashMap<Uuid, usize> =
+        folders.iter().enumerate().map(|(i, f)| (f.id, i)).collect();
+    let folder_relationships = ciphers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let folder = folder_indexes.get(&c.folder_id?)?;
+            Some(Int32Int32KeyValuePair {
+                key: Some(i as i32),
+                value: Some(*folder as i32),
+            })
+        })
+        .collect();
+
+    let enc = client.internal.get_encryption_settings()?;
+    let vault = client.vault();
+    let folders = folders
+        .into_iter()
+        .map(|f| {
+            let view = FolderView {
+                id: None,
+                ..f.into()
+            };
+            Ok(vault.folders().encrypt(view)?.into())
+        })
+        .collect::<Result<_, ImportError>>()?;
+    let ciphers = ciphers
+        .into_iter()
+        .map(|mut c| {
+            // Passkeys are encrypted separately, their private keys aren't part of the view
+            let passkeys: Vec<Fido2CredentialFullView> = match &mut c.r#type {
+                CipherType::Login(login) => std::mem::take(&mut login.fido2_credentials)
+                    .into_iter()
+                    .map(|p| p.into())
+                    .collect(),
+                _ => vec![],
+            };
+
+            let mut view = CipherView {
+                id: None,
+                folder_id: None,
+                ..c.into()
+            };
+            if !passkeys.is_empty() {
+                view.set_new_fido2_credentials(&enc, passkeys)?;
+            }
+            Ok(vault.ciphers().encrypt(view)?.into())
+        })
+        .collect::<Result<_, ImportError>>()?;
+
+    Ok(ImportCiphersRequestModel {
+        folders: Some(folders),
+        ciphers: Some(ciphers),
+        folder_relationships: Some(folder_relationships),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitwarden_core::client::test_accounts::test_bitwarden_com_account;
+    use bitwarden_crypto::EncString;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_import_request() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+
+        let data = include_str!("../resources/json_export.json");
+        let import = parse_import(data.as_bytes(), ImportFormat::Json).unwrap();
+        assert!(import.warnings.is_empty());
+        let request = import_request(&client, import.folders, import.ciphers).unwrap();
+
+        let folders = request.folders.unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].id, None);
+
+        let ciphers = request.ciphers.unwrap();
+        assert_eq!(ciphers.len(), 4);
+        assert!(ciphers[0].name.parse::<EncString>().is_ok());
+        assert!(ciphers.iter().all(|c| c.folder_id.is_none()));
+
+        // The login and the identity are in the "Important" folder
+        let relationships: Vec<_> = request
+            .folder_relationships
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.key.unwrap(), r.value.unwrap()))
+            .collect();
+        assert_eq!(relationships, vec![(0, 0), (3, 0)]);
+    }
+}
@@ -0,0 +1,115 @@
+use super::{name_from_url, new_cipher, read_csv, ImporterError, ParsedImport};
+use crate::{CipherType, Login, LoginUri};
+
+/// Chrome, Edge and other Chromium based browsers export `name,url,username,password,note`
+pub(crate) fn import_chrome_csv(data: &str) -> Result<ParsedImport, ImporterError> {
+    import_browser_csv(data, &[])
+}
+
+/// Firefox exports
+/// `url,username,password,httpRealm,formActionOrigin,guid,timeCreated,timeLastUsed,
+/// timePasswordChanged`
+pub(crate) fn import_firefox_csv(data: &str) -> Result<ParsedImport, ImporterError> {
+    import_browser_csv(
+        data,
+        &[
+            "httpRealm",
+            "formActionOrigin",
+            "guid",
+            "timeCreated",
+            "timeLastUsed",
+            "timePasswordChanged",
+        ],
+    )
+}
+
+/// Both browsers export logins with the same column names, `ignored` are the browser specific
+/// columns that shouldn't become custom fields
+fn import_browser_csv(data: &str, ignored: &[&str]) -> Result<ParsedImport, ImporterError> {
+    let mut import = ParsedImport::default();
+
+    for mut record in read_csv(data, &mut import.warnings)? {
+        let values = &mut record.values;
+        values.discard(ignored);
+
+        let url = values.take("url");
+        let name = values
+            .take("name")
+            .or_else(|| url.as_deref().and_then(name_from_url));
+        let login = Login {
+            username: values.take("username"),
+            password: values.take("password"),
+            login_uris: url
+                .into_iter()
+                .map(|uri| LoginUri {
+                    uri: Some(uri),
+                    r#match: None,
+                })
+                .collect(),
+            totp: None,
+            fido2_credentials: vec![],
+        };
+
+        let mut cipher = new_cipher(
+            import.name(record.line, name),
+            CipherType::Login(Box::new(login)),
+        );
+        cipher.notes = values.take("note").or_else(|| values.take("notes"));
+        cipher.fields = record.values.into_fields();
+        import.ciphers.push(cipher);
+    }
+
+    Ok(import)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_chrome_csv() {
+        let data = "name,url,username,password,note
+example.com,https://www.example.com/login,test@example.com,secret,A note
+,https://accounts.google.com/,test@gmail.com,hunter2,
+,,,orphan,
+";
+        let import = import_chrome_csv(data).unwrap();
+
+        assert_eq!(import.ciphers.len(), 3);
+        let cipher = &import.ciphers[0];
+        assert_eq!(cipher.name, "example.com");
+        assert_eq!(cipher.notes.as_deref(), Some("A note"));
+        let CipherType::Login(login) = &cipher.r#type else {
+            panic!("Expected login")
+        };
+        assert_eq!(login.username.as_deref(), Some("test@example.com"));
+        assert_eq!(login.password.as_deref(), Some("secret"));
+        assert_eq!(
+            login.login_uris[0].uri.as_deref(),
+            Some("https://www.example.com/login")
+        );
+
+        // Unnamed logins are named after their URL
+        assert_eq!(import.ciphers[1].name, "accounts.google.com");
+        assert_eq!(import.ciphers[2].name, "--");
+
+        assert_eq!(import.warnings.len(), 1);
+        assert_eq!(import.warnings[0].row, 4);
+    }
+
+    #[test]
+    fn test_import_firefox_csv() {
+        let data = r#""url","username","password","httpRealm","formActionOrigin","guid","timeCreated","timeLastUsed","timePasswordChanged","extra"
+"https://www.example.com","test","secret",,"https://www.example.com","{5ec0d12f-e194-4279-ae1b-d7d281bb46f0}","1719000000000","1719000000000","1719000000000","kept"
+"#;
+        let import = import_firefox_csv(data).unwrap();
+
+        assert_eq!(import.ciphers.len(), 1);
+        let cipher = &import.ciphers[0];
+        assert_eq!(cipher.name, "example.com");
+        assert_eq!(cipher.fields.len(), 1);
+        assert_eq!(cipher.fields[0].name.as_deref(), Some("extra"));
+        assert_eq!(cipher.fields[0].value.as_deref(), Some("kept"));
+        assert!(import.warnings.is_empty());
+    }
+}
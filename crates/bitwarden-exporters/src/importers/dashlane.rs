@@ -0,0 +1,345 @@
+use super::{
+    is_zip, name_from_url, new_cipher, read_csv, read_zip, split_name, utf8, CsvRecord,
+    ImporterError, ParsedImport,
+};
+use crate::{Card, CipherType, Identity, Login, LoginUri, SecureNote, SecureNoteType};
+
+/// Dashlane exports a zip archive with one CSV file per kind of item. Both the archive and the
+/// individual files can be imported.
+pub(crate) fn import_dashlane(data: &[u8]) -> Result<ParsedImport, ImporterError> {
+    let mut import = ParsedImport::default();
+
+    if is_zip(data) {
+        for (_, contents) in read_zip(data, |name| name.ends_with(".csv"))? {
+            import_file(&mut import, utf8(&contents)?)?;
+        }
+    } else {
+        import_file(&mut import, utf8(data)?)?;
+    }
+
+    Ok(import)
+}
+
+fn import_file(import: &mut ParsedImport, data: &str) -> Result<(), ImporterError> {
+    let headers = csv::Reader::from_reader(data.as_bytes()).headers()?.clone();
+    let has = |column: &str| headers.iter().any(|h| h.trim() == column);
+
+    let import_record: fn(&mut ParsedImport, CsvRecord) = if has("password") && has("url") {
+        credential
+    } else if has("cc_number") {
+        payment
+    } else if has("place_of_issue") {
+        id
+    } else if has("item_name") {
+        personal_info
+    } else if has("title") && has("note") {
+        secure_note
+    } else {
+        return Err(ImporterError::UnsupportedFile(
+            "the file isn't one of the CSV files exported by Dashlane",
+        ));
+    };
+
+    for record in read_csv(data, &mut import.warnings)? {
+        import_record(import, record);
+    }
+
+    Ok(())
+}
+
+/// `credentials.csv`: `username,username2,username3,title,password,note,url,category,otpSecret`
+fn credential(import: &mut ParsedImport, mut record: CsvRecord) {
+    let values = &mut record.values;
+
+    let url = values.take("url");
+    let name = values
+        .take("title")
+        .or_else(|| url.as_deref().and_then(name_from_url));
+    let login = Login {
+        username: values.take("username"),
+        password: values.take("password"),
+        login_uris: url
+            .into_iter()
+            .map(|uri| LoginUri {
+                uri: Some(uri),
+                r#match: None,
+            })
+            .collect(),
+        // Older exports have an `otpauth://` URL instead of the secret
+        totp: values.take("otpSecret").or_else(|| values.take("otpUrl")),
+        fido2_credentials: vec![],
+    };
+
+    let mut cipher = new_cipher(
+        import.name(record.line, name),
+        CipherType::Login(Box::new(login)),
+    );
+    cipher.notes = values.take("note");
+    cipher.folder_id = values.take("category").and_then(|c| import.folder(&c));
+    cipher.fields = record.values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+/// `securenotes.csv`: `title,note`
+fn secure_note(import: &mut ParsedImport, mut record: CsvRecord) {
+    let values = &mut record.values;
+
+    let name = values.take("title");
+    let mut cipher = new_cipher(
+        import.name(record.line, name),
+        CipherType::SecureNote(Box::new(SecureNote {
+            r#type: SecureNoteType::Generic,
+        })),
+    );
+    cipher.notes = values.take("note");
+    cipher.folder_id = values.take("category").and_then(|c| import.folder(&c));
+    cipher.fields = record.values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+/// `payments.csv` contains credit cards and bank accounts:
+/// `type,account_name,account_holder,cc_number,code,expiration_month,expiration_year,
+/// routing_number,account_number,country,issuing_bank`
+fn payment(import: &mut ParsedImport, mut record: CsvRecord) {
+    let values = &mut record.values;
+
+    let kind = values.take("type");
+    let name = values.take("account_name").or_else(|| values.take("name"));
+    let r#type = if kind.as_deref() == Some("bank") {
+        CipherType::SecureNote(Box::new(SecureNote {
+            r#type: SecureNoteType::Generic,
+        }))
+    } else {
+        CipherType::Card(Box::new(Card {
+            cardholder_name: values.take("account_holder"),
+            exp_month: values
+                .take("expiration_month")
+                .map(|m| m.trim_start_matches('0').to_owned()),
+            exp_year: values.take("expiration_year"),
+            code: values.take("code"),
+            brand: None,
+            number: values.take("cc_number"),
+        }))
+    };
+
+    let mut cipher = new_cipher(import.name(record.line, name), r#type);
+    cipher.notes = values.take("note");
+    cipher.fields = record.values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+/// `personalInfo.csv` has a row per name, email, phone number and address:
+/// `type,title,first_name,middle_name,last_name,login,date_of_birth,place_of_birth,email,
+/// email_type,item_name,phone_number,address,country,state,city,zip,...`
+fn personal_info(import: &mut ParsedImport, mut record: CsvRecord) {
+    let values = &mut record.values;
+    values.discard(&["type"]);
+
+    let first_name = values.take("first_name");
+    let last_name = values.take("last_name");
+    let full_name = [first_name.as_deref(), last_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let name = values
+        .take("item_name")
+        .or_else(|| (!full_name.is_empty()).then_some(full_name));
+
+    let identity = Identity {
+        title: values.take("title"),
+        first_name,
+        middle_name: values.take("middle_name"),
+        last_name,
+        address1: values.take("address"),
+        address2: None,
+        address3: None,
+        city: values.take("city"),
+        state: values.take("state"),
+        postal_code: values.take("zip"),
+        country: values.take("country"),
+        company: None,
+        email: values.take("email"),
+        phone: values.take("phone_number"),
+        ssn: None,
+        username: values.take("login"),
+        passport_number: None,
+        license_number: None,
+    };
+
+    let mut cipher = new_cipher(
+        import.name(record.line, name),
+        CipherType::Identity(Box::new(identity)),
+    );
+    cipher.fields = record.values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+/// `ids.csv`: `type,number,name,issue_date,expiration_date,place_of_issue,state`
+fn id(import: &mut ParsedImport, mut record: CsvRecord) {
+    let values = &mut record.values;
+
+    let kind = values.take("type").unwrap_or_default();
+    let full_name = values.take("name");
+    let (first_name, middle_name, last_name) = split_name(full_name.as_deref().unwrap_or(""));
+    let mut identity = Identity {
+        title: None,
+        first_name,
+        middle_name,
+        last_name,
+        address1: None,
+        address2: None,
+        address3: None,
+        city: None,
+        state: None,
+        postal_code: None,
+        country: None,
+        company: None,
+        email: None,
+        phone: None,
+        ssn: None,
+        username: None,
+        passport_number: None,
+        license_number: None,
+    };
+    match kind.as_str() {
+        "passport" => identity.passport_number = values.take("number"),
+        "driving_license" | "license" => identity.license_number = values.take("number"),
+        "social_security" => identity.ssn = values.take("number"),
+        // ID cards and tax numbers only have an equivalent as a custom field
+        _ => {}
+    }
+
+    let name = match (kind.as_str(), full_name) {
+        ("", name) => name,
+        (kind, Some(name)) => Some(format!("{name} ({})", kind.replace('_', " "))),
+        (kind, None) => Some(kind.replace('_', " ")),
+    };
+    let mut cipher = new_cipher(
+        import.name(record.line, name),
+        CipherType::Identity(Box::new(identity)),
+    );
+    cipher.fields = record.values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const CREDENTIALS: &str =
+        "username,username2,username3,title,password,note,url,category,otpSecret
+test@example.com,alias@example.com,,Example,secret,A note,https://example.com,Work,JBSWY3DPEHPK3PXP
+,,,,hunter2,,https://www.github.com,,
+";
+
+    const PAYMENTS: &str = "type,account_name,account_holder,cc_number,code,expiration_month,expiration_year,routing_number,account_number,country,issuing_bank
+credit_card,My card,John Doe,4111111111111111,123,03,2030,,,US,
+bank,My bank,John Doe,,,,,021000021,123456,US,Test Bank
+";
+
+    #[test]
+    fn test_import_credentials() {
+        let import = import_dashlane(CREDENTIALS.as_bytes()).unwrap();
+
+        assert_eq!(import.folders.len(), 1);
+        assert_eq!(import.folders[0].name, "Work");
+        assert_eq!(import.ciphers.len(), 2);
+
+        let cipher = &import.ciphers[0];
+        assert_eq!(cipher.name, "Example");
+        assert_eq!(cipher.notes.as_deref(), Some("A note"));
+        assert_eq!(cipher.folder_id, Some(import.folders[0].id));
+        let CipherType::Login(login) = &cipher.r#type else {
+            panic!("Expected login")
+        };
+        assert_eq!(login.username.as_deref(), Some("test@example.com"));
+        assert_eq!(login.totp.as_deref(), Some("JBSWY3DPEHPK3PXP"));
+        assert_eq!(cipher.fields.len(), 1);
+        assert_eq!(cipher.fields[0].name.as_deref(), Some("username2"));
+
+        assert_eq!(import.ciphers[1].name, "github.com");
+        assert!(import.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_import_payments() {
+        let import = import_dashlane(PAYMENTS.as_bytes()).unwrap();
+        assert_eq!(import.ciphers.len(), 2);
+
+        let CipherType::Card(card) = &import.ciphers[0].r#type else {
+            panic!("Expected card")
+        };
+        assert_eq!(card.cardholder_name.as_deref(), Some("John Doe"));
+        assert_eq!(card.exp_month.as_deref(), Some("3"));
+        assert_eq!(card.exp_year.as_deref(), Some("2030"));
+
+        let bank = &import.ciphers[1];
+        assert_eq!(bank.name, "My bank");
+        assert!(matches!(bank.r#type, CipherType::SecureNote(_)));
+        let fields: Vec<_> = bank.fields.iter().map(|f| f.name.as_deref()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                Some("account_holder"),
+                Some("routing_number"),
+                Some("account_number"),
+                Some("country"),
+                Some("issuing_bank")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_personal_info_and_ids() {
+        let personal = "type,title,first_name,middle_name,last_name,login,date_of_birth,place_of_birth,email,email_type,item_name,phone_number,address,country,state,city,zip
+name,mr,John,,Doe,,1990-01-01,,,,,,,,,,
+email,,,,,,,,john@example.com,personal,Personal email,,,,,,
+";
+        let import = import_dashlane(personal.as_bytes()).unwrap();
+        assert_eq!(import.ciphers[0].name, "John Doe");
+        assert_eq!(
+            import.ciphers[0].fields[0].name.as_deref(),
+            Some("date_of_birth")
+        );
+        let CipherType::Identity(identity) = &import.ciphers[1].r#type else {
+            panic!("Expected identity")
+        };
+        assert_eq!(identity.email.as_deref(), Some("john@example.com"));
+
+        let ids = "type,number,name,issue_date,expiration_date,place_of_issue,state
+passport,X1234567,John Doe,2020-01-01,2030-01-01,US,
+";
+        let import = import_dashlane(ids.as_bytes()).unwrap();
+        assert_eq!(import.ciphers[0].name, "John Doe (passport)");
+        let CipherType::Identity(identity) = &import.ciphers[0].r#type else {
+            panic!("Expected identity")
+        };
+        assert_eq!(identity.passport_number.as_deref(), Some("X1234567"));
+        assert_eq!(identity.last_name.as_deref(), Some("Doe"));
+    }
+
+    #[test]
+    fn test_import_zip() {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("credentials.csv", options).unwrap();
+        zip.write_all(CREDENTIALS.as_bytes()).unwrap();
+        zip.start_file("payments.csv", options).unwrap();
+        zip.write_all(PAYMENTS.as_bytes()).unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        let import = import_dashlane(&data).unwrap();
+        assert_eq!(import.ciphers.len(), 4);
+    }
+
+    #[test]
+    fn test_unsupported_file() {
+        assert!(matches!(
+            import_dashlane(b"a,b\n1,2\n"),
+            Err(ImporterError::UnsupportedFile(_))
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use super::{new_cipher, utf8, ImporterError, ParsedImport, Values};
+use crate::{CipherType, Login, LoginUri};
+
+/// Signature of KeePass 2 databases
+const KDBX_SIGNATURE: [u8; 4] = [0x03, 0xd9, 0xa2, 0x9a];
+
+#[derive(Default)]
+struct Entry {
+    values: Values,
+}
+
+#[derive(Default)]
+struct EntryString {
+    key: String,
+    value: String,
+    protected: bool,
+}
+
+/// Import the `KeePass XML (2.x)` export of KeePass and KeePassXC. Groups become folders, named
+/// after their path below the root group.
+pub(crate) fn import_keepass_xml(data: &[u8]) -> Result<ParsedImport, ImporterError> {
+    if data.starts_with(&KDBX_SIGNATURE) {
+        return Err(ImporterError::UnsupportedFile(
+            "KDBX databases can't be imported directly, export the database as KeePass XML (2.x)",
+        ));
+    }
+
+    let mut reader = Reader::from_str(utf8(data)?);
+    let mut import = ParsedImport::default();
+
+    // The elements from the root to the current one
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    let mut groups: Vec<String> = Vec::new();
+    // Entries keep their previous versions in a nested `History` element
+    let mut history = 0;
+    let mut entry: Option<Entry> = None;
+    let mut string = EntryString::default();
+    let mut row = 0;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                match e.name().as_ref() {
+                    b"Group" => groups.push(String::new()),
+                    b"History" => history += 1,
+                    b"Entry" if history == 0 => entry = Some(Entry::default()),
+                    b"String" => string = EntryString::default(),
+                    b"Value" => string.protected = is_protected(&e)?,
+                    _ => {}
+                }
+                path.push(e.name().as_ref().to_vec());
+            }
+            Event::Empty(e) if e.name().as_ref() == b"Value" => {
+                string.protected = is_protected(&e)?;
+            }
+            Event::Text(e) => append_text(&path, &mut groups, &mut string, &e.unescape()?),
+            Event::CData(e) => append_text(
+                &path,
+                &mut groups,
+                &mut string,
+                &String::from_utf8_lossy(&e),
+            ),
+            Event::End(e) => {
+                path.pop();
+                match e.name().as_ref() {
+                    b"Group" => {
+                        groups.pop();
+                    }
+                    b"History" => history -= 1,
+                    b"String" if history == 0 => {
+                        if let Some(entry) = entry.as_mut() {
+                            let string = std::mem::take(&mut string);
+                            entry.values.insert_field(
+                                &string.key,
+                                &string.key,
+                                string.value,
+                                string.protected,
+                            );
+                        }
+                    }
+                    b"Entry" if history == 0 => {
+                        if let Some(entry) = entry.take() {
+                            row += 1;
+                            import_entry(&mut import, row, &groups, entry);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(import)
+}
+
+fn is_protected(e: &BytesStart) -> Result<bool, ImporterError> {
+    Ok(e.try_get_attribute("ProtectInMemory")
+        .map_err(quick_xml::Error::from)?
+        .is_some_and(|a| a.value.as_ref() == b"True"))
+}
+
+/// Text of the group names and entry strings, the only text content that is imported
+fn append_text(path: &[Vec<u8>], groups: &mut [String], string: &mut EntryString, text: &str) {
+    let [.., parent, element] = path else {
+        return;
+    };
+
+    match (parent.as_slice(), element.as_slice()) {
+        (b"Group", b"Name") => {
+            if let Some(group) = groups.last_mut() {
+                group.push_str(text);
+            }
+        }
+        (b"String", b"Key") => string.key.push_str(text),
+        (b"String", b"Value") => string.value.push_str(text),
+        _ => {}
+    }
+}
+
+fn import_entry(import: &mut ParsedImport, row: usize, groups: &[String], entry: Entry) {
+    let mut values = entry.values;
+
+    // The root group is the database itself
+    let folder = groups
+        .iter()
+        .skip(1)
+        .filter(|g| !g.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let login = Login {
+        username: values.take("UserName"),
+        password: values.take("Password"),
+        login_uris: values
+            .take("URL")
+            .into_iter()
+            .map(|uri| LoginUri {
+                uri: Some(uri),
+                r#match: None,
+            })
+            .collect(),
+        // KeePassXC stores the `otpauth://` URL, KeePass the secret when using TOTP
+        totp: values
+            .take("otp")
+            .or_else(|| values.take("TimeOtp-Secret-Base32")),
+        fido2_credentials: vec![],
+    };
+
+    let name = values.take("Title");
+    let mut cipher = new_cipher(import.name(row, name), CipherType::Login(Box::new(login)));
+    cipher.notes = values.take("Notes");
+    cipher.folder_id = import.folder(&folder);
+    cipher.fields = values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="utf-8" standalone="yes"?>
+<KeePassFile>
+    <Meta>
+        <Generator>KeePass</Generator>
+        <DatabaseName>Passwords</DatabaseName>
+    </Meta>
+    <Root>
+        <Group>
+            <UUID>hD2Yg/s2SVSZ4F0PRR4X4g==</UUID>
+            <Name>Passwords</Name>
+            <Entry>
+                <UUID>A0zDbTfGR0iAv/Y6NCWLDw==</UUID>
+                <String>
+                    <Key>Notes</Key>
+                    <Value>Line one
+Line &amp; two</Value>
+                </String>
+                <String>
+                    <Key>Password</Key>
+                    <Value ProtectInMemory="True">s3cr&lt;t</Value>
+                </String>
+                <String>
+                    <Key>Title</Key>
+                    <Value>Example</Value>
+                </String>
+                <String>
+                    <Key>URL</Key>
+                    <Value>https://example.com</Value>
+                </String>
+                <String>
+                    <Key>UserName</Key>
+                    <Value>test</Value>
+                </String>
+                <String>
+                    <Key>PIN</Key>
+                    <Value ProtectInMemory="True">1234</Value>
+                </String>
+                <String>
+                    <Key>Empty</Key>
+                    <Value />
+                </String>
+                <History>
+                    <Entry>
+                        <String>
+                            <Key>Title</Key>
+                            <Value>Old title</Value>
+                        </String>
+                    </Entry>
+                </History>
+            </Entry>
+            <Group>
+                <Name>Internet</Name>
+                <Group>
+                    <Name>Email</Name>
+                    <Entry>
+                        <String>
+                            <Key>Password</Key>
+                            <Value ProtectInMemory="True"><![CDATA[cdata]]></Value>
+                        </String>
+                        <String>
+                            <Key>otp</Key>
+                            <Value>otpauth://totp/test?secret=JBSWY3DPEHPK3PXP</Value>
+                        </String>
+                    </Entry>
+                </Group>
+            </Group>
+        </Group>
+    </Root>
+</KeePassFile>
+"#;
+
+    #[test]
+    fn test_import_keepass_xml() {
+        let import = import_keepass_xml(XML.as_bytes()).unwrap();
+
+        assert_eq!(import.folders.len(), 1);
+        assert_eq!(import.folders[0].name, "Internet/Email");
+        assert_eq!(import.ciphers.len(), 2);
+
+        let cipher = &import.ciphers[0];
+        assert_eq!(cipher.name, "Example");
+        assert_eq!(cipher.folder_id, None);
+        assert_eq!(cipher.notes.as_deref(), Some("Line one\nLine & two"));
+        let CipherType::Login(login) = &cipher.r#type else {
+            panic!("Expected login")
+        };
+        assert_eq!(login.username.as_deref(), Some("test"));
+        assert_eq!(login.password.as_deref(), Some("s3cr<t"));
+        assert_eq!(
+            login.login_uris[0].uri.as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(cipher.fields.len(), 1);
+        assert_eq!(cipher.fields[0].name.as_deref(), Some("PIN"));
+        assert_eq!(cipher.fields[0].r#type, 1);
+
+        let cipher = &import.ciphers[1];
+        assert_eq!(cipher.name, "--");
+        assert_eq!(cipher.folder_id, Some(import.folders[0].id));
+        let CipherType::Login(login) = &cipher.r#type else {
+            panic!("Expected login")
+        };
+        assert_eq!(login.password.as_deref(), Some("cdata"));
+        assert_eq!(
+            login.totp.as_deref(),
+            Some("otpauth://totp/test?secret=JBSWY3DPEHPK3PXP")
+        );
+
+        assert_eq!(import.warnings.len(), 1);
+        assert_eq!(import.warnings[0].row, 2);
+    }
+
+    #[test]
+    fn test_kdbx() {
+        let data = [0x03, 0xd9, 0xa2, 0x9a, 0x67, 0xfb, 0x4b, 0xb5];
+        assert!(matches!(
+            import_keepass_xml(&data),
+            Err(ImporterError::UnsupportedFile(_))
+        ));
+    }
+}
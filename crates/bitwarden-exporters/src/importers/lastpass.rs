@@ -0,0 +1,309 @@
+use super::{new_cipher, non_empty, read_csv, split_name, ImporterError, ParsedImport, Values};
+use crate::{Card, Cipher, CipherType, Identity, Login, LoginUri, SecureNote, SecureNoteType};
+
+/// Secure notes are exported as rows with this URL
+const NOTE_URL: &str = "http://sn";
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// LastPass exports `url,username,password,totp,extra,name,grouping,fav`, where `extra` holds
+/// the notes of logins and the contents of secure notes
+pub(crate) fn import_lastpass_csv(data: &str) -> Result<ParsedImport, ImporterError> {
+    let mut import = ParsedImport::default();
+
+    for mut record in read_csv(data, &mut import.warnings)? {
+        let values = &mut record.values;
+
+        let url = values.take("url");
+        let name = values.take("name");
+        let extra = values.take("extra");
+        // Nested folders are separated by backslashes
+        let folder_id = values
+            .take("grouping")
+            .filter(|g| g != "(none)")
+            .and_then(|g| import.folder(&g.replace('\\', "/")));
+        let favorite = values.take("fav").as_deref() == Some("1");
+
+        let mut cipher = if url.as_deref() == Some(NOTE_URL) {
+            let name = import.name(record.line, name);
+            note(&mut import, record.line, name, extra)
+        } else {
+            let login = Login {
+                username: values.take("username"),
+                password: values.take("password"),
+                login_uris: url
+                    .into_iter()
+                    .map(|uri| LoginUri {
+                        uri: Some(uri),
+                        r#match: None,
+                    })
+                    .collect(),
+                totp: values.take("totp"),
+                fido2_credentials: vec![],
+            };
+            let mut cipher = new_cipher(
+                import.name(record.line, name),
+                CipherType::Login(Box::new(login)),
+            );
+            cipher.notes = extra;
+            cipher
+        };
+
+        cipher.folder_id = folder_id;
+        cipher.favorite = favorite;
+        // Secure notes have no username, password or TOTP, but keep any that were set
+        cipher.fields.extend(record.values.into_fields());
+        import.ciphers.push(cipher);
+    }
+
+    Ok(import)
+}
+
+/// Typed notes are exported as `NoteType:<type>` followed by `Key:Value` lines, with the free
+/// form `Notes` last since they can span multiple lines
+fn note(import: &mut ParsedImport, row: usize, name: String, extra: Option<String>) -> Cipher {
+    let Some(typed) = extra.as_deref().and_then(|e| e.strip_prefix("NoteType:")) else {
+        let mut cipher = new_cipher(name, secure_note());
+        cipher.notes = extra;
+        return cipher;
+    };
+
+    let mut lines = typed.lines();
+    let note_type = lines.next().unwrap_or_default().trim().to_owned();
+    let mut values = Values::default();
+    let mut notes = None;
+    while let Some(line) = lines.next() {
+        let (key, value) = line.split_once(':').unwrap_or((line, ""));
+        if key == "Notes" {
+            let rest: Vec<&str> = std::iter::once(value).chain(lines).collect();
+            notes = non_empty(&rest.join("\n"));
+            break;
+        }
+        let hidden = key.contains("Password") || key.contains("PIN") || key == "Security Code";
+        values.insert_field(key, key, value.to_owned(), hidden);
+    }
+
+    let r#type = match note_type.as_str() {
+        "Credit Card" => {
+            let (exp_month, exp_year) = match values.take("Expiration Date") {
+                Some(date) => expiration(&date).unwrap_or_else(|| {
+                    import.warn(
+                        row,
+                        format!("The expiration date \"{date}\" couldn't be read"),
+                    );
+                    values.insert("Expiration Date", date);
+                    (None, None)
+                }),
+                None => (None, None),
+            };
+            CipherType::Card(Box::new(Card {
+                cardholder_name: values.take("Name on Card"),
+                exp_month,
+                exp_year,
+                code: values.take("Security Code"),
+                brand: values.take("Type"),
+                number: values.take("Number"),
+            }))
+        }
+        "Address" => CipherType::Identity(Box::new(Identity {
+            title: values.take("Title"),
+            first_name: values.take("First Name"),
+            middle_name: values.take("Middle Name"),
+            last_name: values.take("Last Name"),
+            address1: values.take("Address 1"),
+            address2: values.take("Address 2"),
+            address3: values.take("Address 3"),
+            city: values.take("City / Town"),
+            state: values.take("State"),
+            postal_code: values.take("Zip / Postal Code"),
+            country: values.take("Country"),
+            company: values.take("Company"),
+            email: values.take("Email Address"),
+            phone: values.take("Phone").map(|p| phone(&p)),
+            ssn: None,
+            username: values.take("Username"),
+            passport_number: None,
+            license_number: None,
+        })),
+        "Driver's License" | "Passport" | "Social Security" => {
+            let (first_name, middle_name, last_name) =
+                split_name(&values.take("Name").unwrap_or_default());
+            let number = values.take("Number");
+            let mut identity = Identity {
+                title: None,
+                first_name,
+                middle_name,
+                last_name,
+                address1: values.take("Address"),
+                address2: None,
+                address3: None,
+                city: values.take("City / Town"),
+                state: values.take("State"),
+                postal_code: values.take("ZIP / Postal Code"),
+                country: values.take("Country"),
+                company: None,
+                email: None,
+                phone: None,
+                ssn: None,
+                username: None,
+                passport_number: None,
+                license_number: None,
+            };
+            match note_type.as_str() {
+                "Passport" => identity.passport_number = number,
+                "Social Security" => identity.ssn = number,
+                _ => identity.license_number = number,
+            }
+            CipherType::Identity(Box::new(identity))
+        }
+        // Bank accounts, Wi-Fi passwords, servers, databases and other types only have
+        // equivalents as custom fields
+        _ => secure_note(),
+    };
+
+    let mut cipher = new_cipher(name, r#type);
+    cipher.notes = notes;
+    cipher.fields = values.into_fields();
+    cipher
+}
+
+fn secure_note() -> CipherType {
+    CipherType::SecureNote(Box::new(SecureNote {
+        r#type: SecureNoteType::Generic,
+    }))
+}
+
+/// Expiration dates are exported as `January,2025`
+fn expiration(date: &str) -> Option<(Option<String>, Option<String>)> {
+    let (month, year) = date.split_once(',')?;
+    let month = MONTHS.iter().position(|m| *m == month.trim())? + 1;
+    let year = year.trim();
+    if year.len() != 4 || !year.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((Some(month.to_string()), Some(year.to_owned())))
+}
+
+/// Newer exports store phone numbers as `{"num":"...","ext":"","cc3l":"USA"}`
+fn phone(value: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(value)
+        .ok()
+        .and_then(|v| v["num"].as_str().map(str::to_owned))
+        .unwrap_or_else(|| value.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_lastpass_csv() {
+        let data = r#"url,username,password,totp,extra,name,grouping,fav
+https://example.com/login,test@example.com,secret,JBSWY3DPEHPK3PXP,Login notes,Example,Work\Email,1
+http://sn,,,,A plain note,Plain note,(none),0
+http://sn,,,,"NoteType:Credit Card
+Language:en-US
+Name on Card:John Doe
+Type:Visa
+Number:4111111111111111
+Security Code:123
+Start Date:,
+Expiration Date:March,2030
+Notes:First line
+Second line",Visa,Cards,0
+http://sn,,,,"NoteType:Address
+Title:mr
+First Name:John
+Last Name:Doe
+Phone:{""num"":""5555555555"",""ext"":"""",""cc3l"":""USA""}
+Country:US",Home,,0
+http://sn,,,,"NoteType:Bank Account
+Bank Name:Test Bank
+Account Number:123456
+PIN:0000
+Notes:",Bank,,0
+"#;
+        let import = import_lastpass_csv(data).unwrap();
+        assert!(import.warnings.is_empty());
+
+        let folders: Vec<_> = import.folders.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(folders, vec!["Work/Email", "Cards"]);
+        assert_eq!(import.ciphers.len(), 5);
+
+        let login = &import.ciphers[0];
+        assert_eq!(login.name, "Example");
+        assert!(login.favorite);
+        assert_eq!(login.folder_id, Some(import.folders[0].id));
+        assert_eq!(login.notes.as_deref(), Some("Login notes"));
+        let CipherType::Login(l) = &login.r#type else {
+            panic!("Expected login")
+        };
+        assert_eq!(l.totp.as_deref(), Some("JBSWY3DPEHPK3PXP"));
+
+        let note = &import.ciphers[1];
+        assert!(matches!(note.r#type, CipherType::SecureNote(_)));
+        assert_eq!(note.folder_id, None);
+        assert_eq!(note.notes.as_deref(), Some("A plain note"));
+
+        let card = &import.ciphers[2];
+        let CipherType::Card(c) = &card.r#type else {
+            panic!("Expected card")
+        };
+        assert_eq!(c.cardholder_name.as_deref(), Some("John Doe"));
+        assert_eq!(c.brand.as_deref(), Some("Visa"));
+        assert_eq!(c.number.as_deref(), Some("4111111111111111"));
+        assert_eq!(c.code.as_deref(), Some("123"));
+        assert_eq!(c.exp_month.as_deref(), Some("3"));
+        assert_eq!(c.exp_year.as_deref(), Some("2030"));
+        assert_eq!(card.notes.as_deref(), Some("First line\nSecond line"));
+        let fields: Vec<_> = card.fields.iter().map(|f| f.name.as_deref()).collect();
+        assert_eq!(fields, vec![Some("Language"), Some("Start Date")]);
+
+        let CipherType::Identity(i) = &import.ciphers[3].r#type else {
+            panic!("Expected identity")
+        };
+        assert_eq!(i.first_name.as_deref(), Some("John"));
+        assert_eq!(i.phone.as_deref(), Some("5555555555"));
+        assert_eq!(i.country.as_deref(), Some("US"));
+
+        let bank = &import.ciphers[4];
+        assert!(matches!(bank.r#type, CipherType::SecureNote(_)));
+        assert_eq!(bank.notes, None);
+        let fields: Vec<_> = bank
+            .fields
+            .iter()
+            .map(|f| (f.name.as_deref().unwrap(), f.r#type))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("Bank Name", 0), ("Account Number", 0), ("PIN", 1)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_expiration() {
+        let data = r#"url,username,password,totp,extra,name,grouping,fav
+http://sn,,,,"NoteType:Credit Card
+Expiration Date:Soon",Card,,0
+"#;
+        let import = import_lastpass_csv(data).unwrap();
+
+        assert_eq!(import.warnings.len(), 1);
+        assert_eq!(import.warnings[0].row, 2);
+        let fields = &import.ciphers[0].fields;
+        assert_eq!(fields[0].value.as_deref(), Some("Soon"));
+    }
+}
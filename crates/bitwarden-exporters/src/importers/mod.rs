@@ -0,0 +1,340 @@
+//! Importers for the exports of other password managers.
+//!
+//! Every importer maps the items it understands to logins, cards, identities and secure notes,
+//! keeps the values it has no place for as custom fields, and reports the items it could only
+//! partially import as [ImportWarning]s instead of failing the whole import.
+
+use std::io::{Cursor, Read};
+
+use chrono::Utc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{Cipher, CipherType, Field, Folder, ImportWarning};
+
+mod browser;
+mod dashlane;
+mod keepass;
+mod lastpass;
+mod onepassword;
+
+pub(crate) use browser::{import_chrome_csv, import_firefox_csv};
+pub(crate) use dashlane::import_dashlane;
+pub(crate) use keepass::import_keepass_xml;
+pub(crate) use lastpass::import_lastpass_csv;
+pub(crate) use onepassword::import_1pux;
+
+#[derive(Error, Debug)]
+pub enum ImporterError {
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("XML error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("The file isn't valid UTF-8")]
+    InvalidUtf8,
+    #[error("Unsupported file: {0}")]
+    UnsupportedFile(&'static str),
+}
+
+/// The folders and ciphers read from an export, along with the warnings for the items that
+/// couldn't be fully imported
+#[derive(Default)]
+pub(crate) struct ParsedImport {
+    pub folders: Vec<Folder>,
+    pub ciphers: Vec<Cipher>,
+    pub warnings: Vec<ImportWarning>,
+}
+
+impl ParsedImport {
+    /// The id of the folder with the given name, creating it on first use
+    pub fn folder(&mut self, name: &str) -> Option<Uuid> {
+        let name = name.trim().trim_matches('/');
+        if name.is_empty() {
+            return None;
+        }
+
+        if let Some(folder) = self.folders.iter().find(|f| f.name == name) {
+            return Some(folder.id);
+        }
+
+        let id = Uuid::new_v4();
+        self.folders.push(Folder {
+            id,
+            name: name.to_owned(),
+        });
+        Some(id)
+    }
+
+    pub fn warn(&mut self, row: usize, message: impl Into<String>) {
+        self.warnings.push(ImportWarning {
+            row: row as u32,
+            message: message.into(),
+        });
+    }
+
+    /// The name of an item, exports allow items without one but ciphers require it
+    pub fn name(&mut self, row: usize, name: Option<String>) -> String {
+        name.unwrap_or_else(|| {
+            self.warn(row, "The item has no name, it was imported as \"--\"");
+            "--".to_owned()
+        })
+    }
+}
+
+pub(crate) fn new_cipher(name: String, r#type: CipherType) -> Cipher {
+    let now = Utc::now();
+    Cipher {
+        id: Uuid::new_v4(),
+        folder_id: None,
+        organization_id: None,
+        collection_ids: vec![],
+        name,
+        notes: None,
+        r#type,
+        favorite: false,
+        reprompt: 0,
+        fields: vec![],
+        password_history: vec![],
+        revision_date: now,
+        creation_date: now,
+        deleted_date: None,
+    }
+}
+
+pub(crate) fn non_empty(value: &str) -> Option<String> {
+    (!value.trim().is_empty()).then(|| value.to_owned())
+}
+
+/// The host of a URL without `www.`, used to name logins that only have a URL
+pub(crate) fn name_from_url(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(host.strip_prefix("www.").unwrap_or(host).to_owned())
+}
+
+/// Split a full name into first, middle and last names
+pub(crate) fn split_name(name: &str) -> (Option<String>, Option<String>, Option<String>) {
+    let parts: Vec<&str> = name.split_whitespace().collect();
+    match parts.as_slice() {
+        [] => (None, None, None),
+        [first] => (Some(first.to_string()), None, None),
+        [first, last] => (Some(first.to_string()), None, Some(last.to_string())),
+        [first, middle @ .., last] => (
+            Some(first.to_string()),
+            Some(middle.join(" ")),
+            Some(last.to_string()),
+        ),
+    }
+}
+
+struct Value {
+    key: String,
+    name: String,
+    value: String,
+    hidden: bool,
+}
+
+/// The values of an item in an export. Importers take the ones they map to cipher properties,
+/// and the rest become custom fields.
+#[derive(Default)]
+pub(crate) struct Values(Vec<Value>);
+
+impl Values {
+    pub fn insert(&mut self, key: &str, value: String) {
+        self.insert_field(key, key, value, false);
+    }
+
+    /// Insert a value looked up by `key` that becomes a custom field named `name`
+    pub fn insert_field(&mut self, key: &str, name: &str, value: String, hidden: bool) {
+        self.0.push(Value {
+            key: key.to_owned(),
+            name: name.to_owned(),
+            value,
+            hidden,
+        });
+    }
+
+    /// Remove the value with the given key, keys are case insensitive
+    pub fn take(&mut self, key: &str) -> Option<String> {
+        let index = self
+            .0
+            .iter()
+            .position(|v| v.key.eq_ignore_ascii_case(key))?;
+        non_empty(&self.0.remove(index).value)
+    }
+
+    /// Drop values that are known but have no equivalent in Bitwarden
+    pub fn discard(&mut self, keys: &[&str]) {
+        self.0
+            .retain(|v| !keys.iter().any(|k| v.key.eq_ignore_ascii_case(k)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|v| v.value.trim().is_empty())
+    }
+
+    pub fn into_fields(self) -> Vec<Field> {
+        self.0
+            .into_iter()
+            .filter(|v| !v.value.trim().is_empty())
+            .map(|v| Field {
+                name: Some(v.name),
+                value: Some(v.value),
+                r#type: if v.hidden { 1 } else { 0 },
+                linked_id: None,
+            })
+            .collect()
+    }
+}
+
+pub(crate) struct CsvRecord {
+    /// Line of the record in the file, for warnings
+    pub line: usize,
+    pub values: Values,
+}
+
+/// Read the records of a CSV file with a header row. Rows that can't be read are skipped with a
+/// warning, as are empty rows.
+pub(crate) fn read_csv(
+    data: &str,
+    warnings: &mut Vec<ImportWarning>,
+) -> Result<Vec<CsvRecord>, ImporterError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let headers = reader.headers()?.clone();
+
+    let mut records = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|p| p.line() as usize).unwrap_or(index + 2);
+                warnings.push(ImportWarning {
+                    row: line as u32,
+                    message: format!("The row couldn't be read: {e}"),
+                });
+                continue;
+            }
+        };
+
+        let mut values = Values::default();
+        for (header, value) in headers.iter().zip(record.iter()) {
+            values.insert(header.trim(), value.to_owned());
+        }
+        if values.is_empty() {
+            continue;
+        }
+
+        records.push(CsvRecord {
+            line: record
+                .position()
+                .map(|p| p.line() as usize)
+                .unwrap_or(index + 2),
+            values,
+        });
+    }
+
+    Ok(records)
+}
+
+/// Text exports, excluding the byte order mark some applications add
+pub(crate) fn utf8(data: &[u8]) -> Result<&str, ImporterError> {
+    let data = std::str::from_utf8(data).map_err(|_| ImporterError::InvalidUtf8)?;
+    Ok(data.strip_prefix('\u{feff}').unwrap_or(data))
+}
+
+pub(crate) fn is_zip(data: &[u8]) -> bool {
+    data.starts_with(b"PK\x03\x04")
+}
+
+/// Read the files of a zip archive whose name match the filter, sorted by name
+pub(crate) fn read_zip(
+    data: &[u8],
+    filter: impl Fn(&str) -> bool,
+) -> Result<Vec<(String, Vec<u8>)>, ImporterError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))?;
+
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !file.is_file() || !filter(file.name()) {
+            continue;
+        }
+
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        files.push((file.name().to_owned(), contents));
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_name() {
+        assert_eq!(split_name(""), (None, None, None));
+        assert_eq!(split_name("John"), (Some("John".to_owned()), None, None));
+        assert_eq!(
+            split_name("John Doe"),
+            (Some("John".to_owned()), None, Some("Doe".to_owned()))
+        );
+        assert_eq!(
+            split_name("John Ronald Reuel Tolkien"),
+            (
+                Some("John".to_owned()),
+                Some("Ronald Reuel".to_owned()),
+                Some("Tolkien".to_owned())
+            )
+        );
+    }
+
+    #[test]
+    fn test_values() {
+        let mut values = Values::default();
+        values.insert("Username", "test".to_owned());
+        values.insert("Empty", "".to_owned());
+        values.insert("guid", "1234".to_owned());
+        values.insert_field("pin", "PIN", "0000".to_owned(), true);
+        values.insert("Other", "value".to_owned());
+
+        assert_eq!(values.take("username"), Some("test".to_owned()));
+        assert_eq!(values.take("username"), None);
+        assert_eq!(values.take("empty"), None);
+        values.discard(&["GUID"]);
+
+        let fields = values.into_fields();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].name.as_deref(), Some("PIN"));
+        assert_eq!(fields[0].r#type, 1);
+        assert_eq!(fields[1].name.as_deref(), Some("Other"));
+        assert_eq!(fields[1].r#type, 0);
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let data = "\u{feff}name,url\nTest,https://example.com\n,\nShort\n";
+        let mut warnings = vec![];
+        let records = read_csv(utf8(data.as_bytes()).unwrap(), &mut warnings).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].line, 2);
+        assert_eq!(records[1].line, 4);
+        assert!(warnings.is_empty());
+
+        let mut values = records.into_iter().next().unwrap().values;
+        assert_eq!(values.take("name"), Some("Test".to_owned()));
+        assert_eq!(values.take("url"), Some("https://example.com".to_owned()));
+    }
+}
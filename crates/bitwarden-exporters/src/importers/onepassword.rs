@@ -0,0 +1,513 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{new_cipher, read_zip, split_name, ImporterError, ParsedImport, Values};
+use crate::{Card, CipherType, Identity, Login, LoginUri, SecureNote, SecureNoteType};
+
+#[derive(Deserialize)]
+struct Export {
+    #[serde(default)]
+    accounts: Vec<Account>,
+}
+
+#[derive(Deserialize)]
+struct Account {
+    #[serde(default)]
+    vaults: Vec<Vault>,
+}
+
+#[derive(Deserialize)]
+struct Vault {
+    attrs: VaultAttrs,
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct VaultAttrs {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Item {
+    #[serde(default)]
+    fav_index: u32,
+    #[serde(default)]
+    trashed: bool,
+    #[serde(default)]
+    category_uuid: String,
+    #[serde(default)]
+    details: Details,
+    #[serde(default)]
+    overview: Overview,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct Details {
+    #[serde(default)]
+    login_fields: Vec<LoginField>,
+    notes_plain: Option<String>,
+    #[serde(default)]
+    sections: Vec<Section>,
+    /// Only set for the password category
+    password: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoginField {
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    field_type: String,
+    designation: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Section {
+    #[serde(default)]
+    fields: Vec<SectionField>,
+}
+
+#[derive(Deserialize)]
+struct SectionField {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    id: String,
+    /// An object with a single property named after the kind of value, like
+    /// `{"concealed": "..."}` or `{"monthYear": 203012}`
+    #[serde(default)]
+    value: HashMap<String, Value>,
+}
+
+#[derive(Deserialize, Default)]
+struct Overview {
+    #[serde(default)]
+    title: String,
+    url: Option<String>,
+    #[serde(default)]
+    urls: Vec<OverviewUrl>,
+}
+
+#[derive(Deserialize)]
+struct OverviewUrl {
+    #[serde(default)]
+    url: String,
+}
+
+/// Item categories, identified by their `categoryUuid`
+mod category {
+    pub const LOGIN: &str = "001";
+    pub const CREDIT_CARD: &str = "002";
+    pub const IDENTITY: &str = "004";
+    pub const PASSWORD: &str = "005";
+    pub const DOCUMENT: &str = "006";
+    pub const DRIVERS_LICENSE: &str = "103";
+    pub const PASSPORT: &str = "106";
+    pub const SOCIAL_SECURITY_NUMBER: &str = "108";
+}
+
+/// Import a 1Password 1PUX export, a zip archive with the items of every vault in
+/// `export.data`. Each vault becomes a folder.
+pub(crate) fn import_1pux(data: &[u8]) -> Result<ParsedImport, ImporterError> {
+    let Some((_, contents)) = read_zip(data, |name| name == "export.data")?.pop() else {
+        return Err(ImporterError::UnsupportedFile(
+            "the archive has no export.data, it isn't a 1PUX export",
+        ));
+    };
+    let export: Export = serde_json::from_slice(&contents)?;
+
+    let mut import = ParsedImport::default();
+    let mut row = 0;
+    for vault in export.accounts.into_iter().flat_map(|a| a.vaults) {
+        for item in vault.items {
+            row += 1;
+            import_item(&mut import, row, &vault.attrs.name, item);
+        }
+    }
+
+    Ok(import)
+}
+
+fn import_item(import: &mut ParsedImport, row: usize, vault: &str, item: Item) {
+    let name = (!item.overview.title.is_empty()).then(|| item.overview.title.clone());
+    if item.trashed {
+        return;
+    }
+    if item.category_uuid == category::DOCUMENT {
+        import.warn(
+            row,
+            format!(
+                "\"{}\" is a document, documents can't be imported",
+                name.as_deref().unwrap_or("--")
+            ),
+        );
+        return;
+    }
+
+    let mut values = Values::default();
+    let mut totp = None;
+    for field in item.details.sections.into_iter().flat_map(|s| s.fields) {
+        let Some((kind, value)) = field_value(&field.value) else {
+            continue;
+        };
+        if kind == "totp" && totp.is_none() {
+            totp = Some(value);
+            continue;
+        }
+        let title = if field.title.is_empty() {
+            &field.id
+        } else {
+            &field.title
+        };
+        values.insert_field(&field.id, title, value, kind == "concealed");
+    }
+
+    let r#type = match item.category_uuid.as_str() {
+        category::LOGIN | category::PASSWORD => {
+            let mut username = None;
+            let mut password = item.details.password;
+            for field in item.details.login_fields {
+                match field.designation.as_deref() {
+                    Some("username") if username.is_none() => username = Some(field.value),
+                    Some("password") if password.is_none() => password = Some(field.value),
+                    // Other fields of the login form
+                    _ => {
+                        let name = if field.name.is_empty() {
+                            "field"
+                        } else {
+                            &field.name
+                        };
+                        values.insert_field(name, name, field.value, field.field_type == "P");
+                    }
+                }
+            }
+
+            let mut uris: Vec<String> = item.overview.url.into_iter().collect();
+            for url in item.overview.urls {
+                if !url.url.is_empty() && !uris.contains(&url.url) {
+                    uris.push(url.url);
+                }
+            }
+
+            CipherType::Login(Box::new(Login {
+                username: username.filter(|u| !u.is_empty()),
+                password: password.filter(|p| !p.is_empty()),
+                login_uris: uris
+                    .into_iter()
+                    .filter(|u| !u.is_empty())
+                    .map(|uri| LoginUri {
+                        uri: Some(uri),
+                        r#match: None,
+                    })
+                    .collect(),
+                totp: totp.take(),
+                fido2_credentials: vec![],
+            }))
+        }
+        category::CREDIT_CARD => {
+            let (exp_month, exp_year) = values
+                .take("expiry")
+                .and_then(|e| {
+                    let (month, year) = e.split_once('/')?;
+                    Some((
+                        Some(month.trim_start_matches('0').to_owned()),
+                        Some(year.to_owned()),
+                    ))
+                })
+                .unwrap_or_default();
+            CipherType::Card(Box::new(Card {
+                cardholder_name: values.take("cardholder"),
+                exp_month,
+                exp_year,
+                code: values.take("cvv"),
+                brand: values.take("type"),
+                number: values.take("ccnum"),
+            }))
+        }
+        category::IDENTITY => CipherType::Identity(Box::new(Identity {
+            title: None,
+            first_name: values.take("firstname"),
+            middle_name: values.take("initial"),
+            last_name: values.take("lastname"),
+            address1: values.take("address"),
+            address2: None,
+            address3: None,
+            city: None,
+            state: None,
+            postal_code: None,
+            country: None,
+            company: values.take("company"),
+            email: values.take("email"),
+            phone: values.take("defphone"),
+            ssn: None,
+            username: values.take("username"),
+            passport_number: None,
+            license_number: None,
+        })),
+        category::DRIVERS_LICENSE | category::PASSPORT | category::SOCIAL_SECURITY_NUMBER => {
+            let full_name = values.take("fullname").or_else(|| values.take("name"));
+            let (first_name, middle_name, last_name) =
+                split_name(full_name.as_deref().unwrap_or(""));
+            let number = values.take("number");
+            let mut identity = Identity {
+                title: None,
+                first_name,
+                middle_name,
+                last_name,
+                address1: values.take("address"),
+                address2: None,
+                address3: None,
+                city: None,
+                state: values.take("state"),
+                postal_code: None,
+                country: values.take("country"),
+                company: None,
+                email: None,
+                phone: None,
+                ssn: None,
+                username: None,
+                passport_number: None,
+                license_number: None,
+            };
+            match item.category_uuid.as_str() {
+                category::PASSPORT => identity.passport_number = number,
+                category::SOCIAL_SECURITY_NUMBER => identity.ssn = number,
+                _ => identity.license_number = number,
+            }
+            CipherType::Identity(Box::new(identity))
+        }
+        // Secure notes, and the categories that only have an equivalent as custom fields like
+        // bank accounts, software licenses, servers or wireless routers
+        _ => CipherType::SecureNote(Box::new(SecureNote {
+            r#type: SecureNoteType::Generic,
+        })),
+    };
+
+    if let Some(totp) = totp {
+        values.insert_field("totp", "TOTP", totp, true);
+    }
+
+    let mut cipher = new_cipher(import.name(row, name), r#type);
+    cipher.notes = item.details.notes_plain.filter(|n| !n.is_empty());
+    cipher.favorite = item.fav_index > 0;
+    cipher.folder_id = import.folder(vault);
+    cipher.fields = values.into_fields();
+    import.ciphers.push(cipher);
+}
+
+/// The kind and text of a field value, dates and addresses are formatted as text
+fn field_value(value: &HashMap<String, Value>) -> Option<(&str, String)> {
+    let (kind, value) = value.iter().next()?;
+    let text = match (kind.as_str(), value) {
+        ("date", Value::Number(n)) => DateTime::from_timestamp(n.as_i64()?, 0)?
+            .format("%Y-%m-%d")
+            .to_string(),
+        ("monthYear", Value::Number(n)) => {
+            let n = n.as_u64()?;
+            format!("{:02}/{}", n % 100, n / 100)
+        }
+        ("address", Value::Object(address)) => ["street", "city", "state", "zip", "country"]
+            .iter()
+            .filter_map(|k| address.get(*k)?.as_str())
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        (_, Value::String(s)) => s.clone(),
+        (_, Value::Number(n)) => n.to_string(),
+        (_, Value::Bool(b)) => b.to_string(),
+        _ => return None,
+    };
+
+    (!text.is_empty()).then_some((kind.as_str(), text))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn pux(data: &str) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        zip.start_file(
+            "export.attributes",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.start_file("export.data", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(data.as_bytes()).unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    const EXPORT: &str = r#"{
+  "accounts": [{
+    "attrs": { "accountName": "Test", "email": "test@example.com" },
+    "vaults": [{
+      "attrs": { "uuid": "abc", "name": "Personal" },
+      "items": [
+        {
+          "uuid": "1", "favIndex": 1, "createdAt": 1700000000, "updatedAt": 1700000000,
+          "trashed": false, "categoryUuid": "001",
+          "details": {
+            "loginFields": [
+              { "value": "test@example.com", "name": "email", "fieldType": "E", "designation": "username" },
+              { "value": "secret", "name": "password", "fieldType": "P", "designation": "password" },
+              { "value": "1234", "name": "pin", "fieldType": "P" }
+            ],
+            "notesPlain": "Login notes",
+            "sections": [{
+              "title": "", "name": "",
+              "fields": [
+                { "title": "one-time password", "id": "TOTP_1", "value": { "totp": "JBSWY3DPEHPK3PXP" } },
+                { "title": "recovery code", "id": "abc", "value": { "concealed": "AAAA-BBBB" } }
+              ]
+            }]
+          },
+          "overview": {
+            "title": "Example", "url": "https://example.com",
+            "urls": [{ "label": "", "url": "https://example.com" }, { "label": "", "url": "https://login.example.com" }]
+          }
+        },
+        {
+          "uuid": "2", "favIndex": 0, "trashed": false, "categoryUuid": "002",
+          "details": {
+            "sections": [{
+              "title": "", "fields": [
+                { "title": "cardholder name", "id": "cardholder", "value": { "string": "John Doe" } },
+                { "title": "type", "id": "type", "value": { "creditCardType": "visa" } },
+                { "title": "number", "id": "ccnum", "value": { "creditCardNumber": "4111111111111111" } },
+                { "title": "verification number", "id": "cvv", "value": { "concealed": "123" } },
+                { "title": "expiry date", "id": "expiry", "value": { "monthYear": 203003 } },
+                { "title": "valid from", "id": "validFrom", "value": { "monthYear": 202003 } }
+              ]
+            }]
+          },
+          "overview": { "title": "Visa" }
+        },
+        {
+          "uuid": "3", "trashed": false, "categoryUuid": "004",
+          "details": {
+            "sections": [{
+              "title": "Identification", "fields": [
+                { "title": "first name", "id": "firstname", "value": { "string": "John" } },
+                { "title": "last name", "id": "lastname", "value": { "string": "Doe" } },
+                { "title": "birth date", "id": "birthdate", "value": { "date": 631152000 } },
+                { "title": "address", "id": "address", "value": { "address": { "street": "1 Main St", "city": "Springfield", "state": "", "zip": "12345", "country": "us" } } }
+              ]
+            }]
+          },
+          "overview": { "title": "Identity" }
+        },
+        { "uuid": "4", "trashed": true, "categoryUuid": "003", "details": {}, "overview": { "title": "Deleted" } },
+        { "uuid": "5", "trashed": false, "categoryUuid": "006", "details": {}, "overview": { "title": "Scan" } },
+        {
+          "uuid": "6", "trashed": false, "categoryUuid": "101",
+          "details": {
+            "notesPlain": "Bank notes",
+            "sections": [{ "title": "", "fields": [{ "title": "account number", "id": "accountNo", "value": { "string": "123456" } }] }]
+          },
+          "overview": { "title": "Bank" }
+        }
+      ]
+    }]
+  }]
+}"#;
+
+    #[test]
+    fn test_import_1pux() {
+        let import = import_1pux(&pux(EXPORT)).unwrap();
+
+        assert_eq!(import.folders.len(), 1);
+        assert_eq!(import.folders[0].name, "Personal");
+        assert_eq!(import.ciphers.len(), 4);
+        assert!(import
+            .ciphers
+            .iter()
+            .all(|c| c.folder_id == Some(import.folders[0].id)));
+
+        let cipher = &import.ciphers[0];
+        assert_eq!(cipher.name, "Example");
+        assert!(cipher.favorite);
+        assert_eq!(cipher.notes.as_deref(), Some("Login notes"));
+        let CipherType::Login(login) = &cipher.r#type else {
+            panic!("Expected login")
+        };
+        assert_eq!(login.username.as_deref(), Some("test@example.com"));
+        assert_eq!(login.password.as_deref(), Some("secret"));
+        assert_eq!(login.totp.as_deref(), Some("JBSWY3DPEHPK3PXP"));
+        assert_eq!(login.login_uris.len(), 2);
+        let fields: Vec<_> = cipher
+            .fields
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_deref().unwrap(),
+                    f.value.as_deref().unwrap(),
+                    f.r#type,
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("recovery code", "AAAA-BBBB", 1), ("pin", "1234", 1)]
+        );
+
+        let CipherType::Card(card) = &import.ciphers[1].r#type else {
+            panic!("Expected card")
+        };
+        assert_eq!(card.cardholder_name.as_deref(), Some("John Doe"));
+        assert_eq!(card.brand.as_deref(), Some("visa"));
+        assert_eq!(card.code.as_deref(), Some("123"));
+        assert_eq!(card.exp_month.as_deref(), Some("3"));
+        assert_eq!(card.exp_year.as_deref(), Some("2030"));
+        assert_eq!(
+            import.ciphers[1].fields[0].value.as_deref(),
+            Some("03/2020")
+        );
+
+        let identity = &import.ciphers[2];
+        let CipherType::Identity(i) = &identity.r#type else {
+            panic!("Expected identity")
+        };
+        assert_eq!(i.first_name.as_deref(), Some("John"));
+        assert_eq!(
+            i.address1.as_deref(),
+            Some("1 Main St, Springfield, 12345, us")
+        );
+        assert_eq!(identity.fields[0].value.as_deref(), Some("1990-01-01"));
+
+        let bank = &import.ciphers[3];
+        assert!(matches!(bank.r#type, CipherType::SecureNote(_)));
+        assert_eq!(bank.notes.as_deref(), Some("Bank notes"));
+        assert_eq!(bank.fields[0].name.as_deref(), Some("account number"));
+
+        // The trashed item is skipped and the document reported
+        assert_eq!(import.warnings.len(), 1);
+        assert_eq!(import.warnings[0].row, 5);
+    }
+
+    #[test]
+    fn test_not_1pux() {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        zip.start_file("other.txt", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        assert!(matches!(
+            import_1pux(&data),
+            Err(ImporterError::UnsupportedFile(_))
+        ));
+    }
+}
@@ -1,39 +1,170 @@
+use bitwarden_core::require;
+use bitwarden_crypto::{KeyEncryptable, SymmetricCryptoKey};
 use chrono::{DateTime, Utc};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::{Card, Cipher, CipherType, Field, Folder, Identity, Login, LoginUri, SecureNote};
+use crate::{
+    Card, Cipher, CipherType, Collection, Fido2Credential, Field, Folder, Identity, Login,
+    LoginUri, PasswordHistory, SecureNote, SecureNoteType,
+};
 
 #[derive(Error, Debug)]
 pub enum JsonError {
     #[error("JSON error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    MissingField(#[from] bitwarden_core::MissingFieldError),
+
+    #[error("The export is encrypted, it needs to be imported as encrypted JSON")]
+    Encrypted,
+    #[error("Unsupported item type {0}")]
+    UnsupportedType(u8),
+
+    #[error(transparent)]
+    Crypto(#[from] bitwarden_crypto::CryptoError),
 }
 
 pub(crate) fn export_json(folders: Vec<Folder>, ciphers: Vec<Cipher>) -> Result<String, JsonError> {
     let export = JsonExport {
         encrypted: false,
-        folders: folders.into_iter().map(|f| f.into()).collect(),
+        folders: Some(folders.into_iter().map(|f| f.into()).collect()),
+        collections: None,
+        items: ciphers.into_iter().map(|c| c.into()).collect(),
+    };
+
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+/// Organization exports list the collections instead of the folders, and the collections of each
+/// cipher in `collectionIds`
+pub(crate) fn export_organization_json(
+    collections: Vec<Collection>,
+    ciphers: Vec<Cipher>,
+) -> Result<String, JsonError> {
+    let export = JsonExport {
+        encrypted: false,
+        folders: None,
+        collections: Some(collections.into_iter().map(|c| c.into()).collect()),
         items: ciphers.into_iter().map(|c| c.into()).collect(),
     };
 
     Ok(serde_json::to_string_pretty(&export)?)
 }
 
+/// Properties of the vault models that only matter to clients, and aren't part of exports
+const CLIENT_PROPERTIES: [&str; 5] = [
+    "organizationUseTotp",
+    "edit",
+    "viewPassword",
+    "localData",
+    "attachments",
+];
+
+/// Export the still encrypted ciphers, along with a random value encrypted with the same key to
+/// validate it when importing. Folder names are encrypted again with the key, since personal
+/// exports use the user key.
+pub(crate) fn export_account_encrypted_json(
+    key: &SymmetricCryptoKey,
+    folders: Option<Vec<Folder>>,
+    collections: Option<Vec<bitwarden_vault::Collection>>,
+    ciphers: Vec<bitwarden_vault::Cipher>,
+) -> Result<String, JsonError> {
+    let folders = folders
+        .map(|folders| {
+            folders
+                .into_iter()
+                .map(|f| {
+                    Ok(JsonFolder {
+                        id: f.id,
+                        name: f.name.encrypt_with_key(key)?.to_string(),
+                    })
+                })
+                .collect::<Result<_, JsonError>>()
+        })
+        .transpose()?;
+
+    let items = ciphers
+        .into_iter()
+        .map(|c| {
+            let mut item = serde_json::to_value(c)?;
+            if let Some(item) = item.as_object_mut() {
+                for property in CLIENT_PROPERTIES {
+                    item.remove(property);
+                }
+            }
+            Ok(item)
+        })
+        .collect::<Result<_, JsonError>>()?;
+
+    let export = AccountEncryptedJsonExport {
+        encrypted: true,
+        enc_key_validation: Uuid::new_v4()
+            .to_string()
+            .encrypt_with_key(key)?
+            .to_string(),
+        folders,
+        collections: collections.map(|c| c.into_iter().map(|c| c.into()).collect()),
+        items,
+    };
+
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+/// Parse an unencrypted JSON export, the ids of the folders and ciphers are preserved.
+pub(crate) fn import_json(data: &str) -> Result<(Vec<Folder>, Vec<Cipher>), JsonError> {
+    let export: JsonExport = serde_json::from_str(data)?;
+    if export.encrypted {
+        return Err(JsonError::Encrypted);
+    }
+
+    let folders = export
+        .folders
+        .unwrap_or_default()
+        .into_iter()
+        .map(|f| f.into())
+        .collect();
+    let ciphers = export
+        .items
+        .into_iter()
+        .map(|c| c.try_into())
+        .collect::<Result<_, _>>()?;
+
+    Ok((folders, ciphers))
+}
+
 /// JSON export format. These are intentionally decoupled from the internal data structures to
 /// ensure internal changes are not reflected in the public exports.
 ///
 /// Be careful about changing these structs to maintain compatibility with old exporters/importers.
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonExport {
     encrypted: bool,
-    folders: Vec<JsonFolder>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    folders: Option<Vec<JsonFolder>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    collections: Option<Vec<JsonCollection>>,
+    #[serde(default)]
     items: Vec<JsonCipher>,
 }
 
+/// Export encrypted with the account key, where the items are the encrypted ciphers
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
+struct AccountEncryptedJsonExport {
+    encrypted: bool,
+    #[serde(rename = "encKeyValidation_DO_NOT_EDIT")]
+    enc_key_validation: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folders: Option<Vec<JsonFolder>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collections: Option<Vec<JsonCollection>>,
+    items: Vec<serde_json::Value>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct JsonFolder {
     id: Uuid,
     name: String,
@@ -48,9 +179,41 @@ impl From<Folder> for JsonFolder {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonCollection {
+    id: Uuid,
+    organization_id: Uuid,
+    name: String,
+    external_id: Option<String>,
+}
+
+impl From<Collection> for JsonCollection {
+    fn from(collection: Collection) -> Self {
+        JsonCollection {
+            id: collection.id,
+            organization_id: collection.organization_id,
+            name: collection.name,
+            external_id: collection.external_id,
+        }
+    }
+}
+
+impl From<bitwarden_vault::Collection> for JsonCollection {
+    fn from(collection: bitwarden_vault::Collection) -> Self {
+        JsonCollection {
+            id: collection.id.unwrap_or_default(),
+            organization_id: collection.organization_id,
+            name: collection.name.to_string(),
+            external_id: collection.external_id,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonCipher {
+    #[serde(default = "Uuid::new_v4")]
     id: Uuid,
     folder_id: Option<Uuid>,
     // Organizational IDs which are always empty in personal exports
@@ -70,26 +233,32 @@ struct JsonCipher {
     #[serde(skip_serializing_if = "Option::is_none")]
     secure_note: Option<JsonSecureNote>,
 
+    #[serde(default)]
     favorite: bool,
+    #[serde(default)]
     reprompt: u8,
 
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     fields: Vec<JsonField>,
-    password_history: Option<Vec<String>>,
+    password_history: Option<Vec<JsonPasswordHistory>>,
 
+    #[serde(default = "Utc::now")]
     revision_date: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
     creation_date: DateTime<Utc>,
     deleted_date: Option<DateTime<Utc>>,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonLogin {
     username: Option<String>,
     password: Option<String>,
+    #[serde(default)]
     uris: Vec<JsonLoginUri>,
     totp: Option<String>,
-    fido2_credentials: Vec<String>,
+    #[serde(default)]
+    fido2_credentials: Vec<JsonFido2Credential>,
 }
 
 impl From<Login> for JsonLogin {
@@ -99,12 +268,54 @@ impl From<Login> for JsonLogin {
             password: login.password,
             uris: login.login_uris.into_iter().map(|u| u.into()).collect(),
             totp: login.totp,
-            fido2_credentials: vec![],
+            fido2_credentials: login
+                .fido2_credentials
+                .into_iter()
+                .map(|f| f.into())
+                .collect(),
         }
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFido2Credential {
+    credential_id: String,
+    key_type: String,
+    key_algorithm: String,
+    key_curve: String,
+    key_value: String,
+    rp_id: String,
+    user_handle: Option<String>,
+    user_name: Option<String>,
+    counter: String,
+    rp_name: Option<String>,
+    user_display_name: Option<String>,
+    discoverable: String,
+    creation_date: DateTime<Utc>,
+}
+
+impl From<Fido2Credential> for JsonFido2Credential {
+    fn from(credential: Fido2Credential) -> Self {
+        JsonFido2Credential {
+            credential_id: credential.credential_id,
+            key_type: credential.key_type,
+            key_algorithm: credential.key_algorithm,
+            key_curve: credential.key_curve,
+            key_value: credential.key_value,
+            rp_id: credential.rp_id,
+            user_handle: credential.user_handle,
+            user_name: credential.user_name,
+            counter: credential.counter,
+            rp_name: credential.rp_name,
+            user_display_name: credential.user_display_name,
+            discoverable: credential.discoverable,
+            creation_date: credential.creation_date,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonLoginUri {
     uri: Option<String>,
@@ -120,9 +331,10 @@ impl From<LoginUri> for JsonLoginUri {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonSecureNote {
+    #[serde(default)]
     r#type: u8,
 }
 
@@ -134,7 +346,7 @@ impl From<SecureNote> for JsonSecureNote {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonCard {
     cardholder_name: Option<String>,
@@ -158,7 +370,7 @@ impl From<Card> for JsonCard {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonIdentity {
     title: Option<String>,
@@ -206,15 +418,32 @@ impl From<Identity> for JsonIdentity {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonField {
     name: Option<String>,
     value: Option<String>,
+    #[serde(default)]
     r#type: u8,
     linked_id: Option<u32>,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPasswordHistory {
+    last_used_date: DateTime<Utc>,
+    password: String,
+}
+
+impl From<PasswordHistory> for JsonPasswordHistory {
+    fn from(history: PasswordHistory) -> Self {
+        JsonPasswordHistory {
+            last_used_date: history.last_used_date,
+            password: history.password,
+        }
+    }
+}
+
 impl From<Field> for JsonField {
     fn from(field: Field) -> Self {
         JsonField {
@@ -245,8 +474,8 @@ impl From<Cipher> for JsonCipher {
         JsonCipher {
             id: cipher.id,
             folder_id: cipher.folder_id,
-            organization_id: None,
-            collection_ids: None,
+            organization_id: cipher.organization_id,
+            collection_ids: cipher.organization_id.map(|_| cipher.collection_ids),
             name: cipher.name,
             notes: cipher.notes,
             r#type,
@@ -257,7 +486,13 @@ impl From<Cipher> for JsonCipher {
             favorite: cipher.favorite,
             reprompt: cipher.reprompt,
             fields: cipher.fields.into_iter().map(|f| f.into()).collect(),
-            password_history: None,
+            password_history: (!cipher.password_history.is_empty()).then(|| {
+                cipher
+                    .password_history
+                    .into_iter()
+                    .map(|p| p.into())
+                    .collect()
+            }),
             revision_date: cipher.revision_date,
             creation_date: cipher.creation_date,
             deleted_date: cipher.deleted_date,
@@ -265,6 +500,157 @@ impl From<Cipher> for JsonCipher {
     }
 }
 
+impl From<JsonFolder> for Folder {
+    fn from(folder: JsonFolder) -> Self {
+        Folder {
+            id: folder.id,
+            name: folder.name,
+        }
+    }
+}
+
+impl From<JsonLogin> for Login {
+    fn from(login: JsonLogin) -> Self {
+        Login {
+            username: login.username,
+            password: login.password,
+            login_uris: login.uris.into_iter().map(|u| u.into()).collect(),
+            totp: login.totp,
+            fido2_credentials: login
+                .fido2_credentials
+                .into_iter()
+                .map(|f| f.into())
+                .collect(),
+        }
+    }
+}
+
+impl From<JsonFido2Credential> for Fido2Credential {
+    fn from(credential: JsonFido2Credential) -> Self {
+        Fido2Credential {
+            credential_id: credential.credential_id,
+            key_type: credential.key_type,
+            key_algorithm: credential.key_algorithm,
+            key_curve: credential.key_curve,
+            key_value: credential.key_value,
+            rp_id: credential.rp_id,
+            user_handle: credential.user_handle,
+            user_name: credential.user_name,
+            counter: credential.counter,
+            rp_name: credential.rp_name,
+            user_display_name: credential.user_display_name,
+            discoverable: credential.discoverable,
+            creation_date: credential.creation_date,
+        }
+    }
+}
+
+impl From<JsonLoginUri> for LoginUri {
+    fn from(login_uri: JsonLoginUri) -> Self {
+        LoginUri {
+            uri: login_uri.uri,
+            r#match: login_uri.r#match,
+        }
+    }
+}
+
+impl From<JsonCard> for Card {
+    fn from(card: JsonCard) -> Self {
+        Card {
+            cardholder_name: card.cardholder_name,
+            exp_month: card.exp_month,
+            exp_year: card.exp_year,
+            code: card.code,
+            brand: card.brand,
+            number: card.number,
+        }
+    }
+}
+
+impl From<JsonIdentity> for Identity {
+    fn from(identity: JsonIdentity) -> Self {
+        Identity {
+            title: identity.title,
+            first_name: identity.first_name,
+            middle_name: identity.middle_name,
+            last_name: identity.last_name,
+            address1: identity.address1,
+            address2: identity.address2,
+            address3: identity.address3,
+            city: identity.city,
+            state: identity.state,
+            postal_code: identity.postal_code,
+            country: identity.country,
+            company: identity.company,
+            email: identity.email,
+            phone: identity.phone,
+            ssn: identity.ssn,
+            username: identity.username,
+            passport_number: identity.passport_number,
+            license_number: identity.license_number,
+        }
+    }
+}
+
+impl From<JsonField> for Field {
+    fn from(field: JsonField) -> Self {
+        Field {
+            name: field.name,
+            value: field.value,
+            r#type: field.r#type,
+            linked_id: field.linked_id,
+        }
+    }
+}
+
+impl From<JsonPasswordHistory> for PasswordHistory {
+    fn from(history: JsonPasswordHistory) -> Self {
+        PasswordHistory {
+            password: history.password,
+            last_used_date: history.last_used_date,
+        }
+    }
+}
+
+impl TryFrom<JsonCipher> for Cipher {
+    type Error = JsonError;
+
+    fn try_from(cipher: JsonCipher) -> Result<Self, Self::Error> {
+        let r#type = match cipher.r#type {
+            1 => CipherType::Login(Box::new(require!(cipher.login).into())),
+            // Generic is the only secure note type
+            2 => CipherType::SecureNote(Box::new(SecureNote {
+                r#type: SecureNoteType::Generic,
+            })),
+            3 => CipherType::Card(Box::new(require!(cipher.card).into())),
+            4 => CipherType::Identity(Box::new(require!(cipher.identity).into())),
+            t => return Err(JsonError::UnsupportedType(t)),
+        };
+
+        Ok(Cipher {
+            id: cipher.id,
+            folder_id: cipher.folder_id,
+            organization_id: cipher.organization_id,
+            collection_ids: cipher.collection_ids.unwrap_or_default(),
+            name: cipher.name,
+            notes: cipher.notes,
+            r#type,
+            favorite: cipher.favorite,
+            reprompt: cipher.reprompt,
+            fields: cipher.fields.into_iter().map(|f| f.into()).collect(),
+            password_history: cipher
+                .password_history
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
+            revision_date: cipher.revision_date,
+            creation_date: cipher.creation_date,
+            deleted_date: cipher.deleted_date,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs, io::Read, path::PathBuf};
@@ -277,6 +663,8 @@ mod tests {
         let cipher = Cipher {
             id: "25c8c414-b446-48e9-a1bd-b10700bbd740".parse().unwrap(),
             folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+            organization_id: None,
+            collection_ids: vec![],
 
             name: "Bitwarden".to_string(),
             notes: Some("My note".to_string()),
@@ -289,6 +677,7 @@ mod tests {
                     r#match: None,
                 }],
                 totp: Some("ABC".to_string()),
+                fido2_credentials: vec![],
             })),
 
             favorite: true,
@@ -326,6 +715,7 @@ mod tests {
                     linked_id: Some(101),
                 },
             ],
+            password_history: vec![],
 
             revision_date: "2024-01-30T14:09:33.753Z".parse().unwrap(),
             creation_date: "2024-01-30T11:23:54.416Z".parse().unwrap(),
@@ -405,6 +795,8 @@ mod tests {
         let cipher = Cipher {
             id: "23f0f877-42b1-4820-a850-b10700bc41eb".parse().unwrap(),
             folder_id: None,
+            organization_id: None,
+            collection_ids: vec![],
 
             name: "My secure note".to_string(),
             notes: Some("Very secure!".to_string()),
@@ -417,6 +809,7 @@ mod tests {
             reprompt: 0,
 
             fields: vec![],
+            password_history: vec![],
 
             revision_date: "2024-01-30T11:25:25.466Z".parse().unwrap(),
             creation_date: "2024-01-30T11:25:25.466Z".parse().unwrap(),
@@ -455,6 +848,8 @@ mod tests {
         let cipher = Cipher {
             id: "3ed8de45-48ee-4e26-a2dc-b10701276c53".parse().unwrap(),
             folder_id: None,
+            organization_id: None,
+            collection_ids: vec![],
 
             name: "My card".to_string(),
             notes: None,
@@ -472,6 +867,7 @@ mod tests {
             reprompt: 0,
 
             fields: vec![],
+            password_history: vec![],
 
             revision_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
             creation_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
@@ -515,6 +911,8 @@ mod tests {
         let cipher = Cipher {
             id: "41cc3bc1-c3d9-4637-876c-b10701273712".parse().unwrap(),
             folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+            organization_id: None,
+            collection_ids: vec![],
 
             name: "My identity".to_string(),
             notes: None,
@@ -544,6 +942,7 @@ mod tests {
             reprompt: 0,
 
             fields: vec![],
+            password_history: vec![],
 
             revision_date: "2024-01-30T17:54:50.706Z".parse().unwrap(),
             creation_date: "2024-01-30T17:54:50.706Z".parse().unwrap(),
@@ -614,6 +1013,8 @@ mod tests {
                 Cipher {
                     id: "25c8c414-b446-48e9-a1bd-b10700bbd740".parse().unwrap(),
                     folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "Bitwarden".to_string(),
                     notes: Some("My note".to_string()),
@@ -626,6 +1027,7 @@ mod tests {
                             r#match: None,
                         }],
                         totp: Some("ABC".to_string()),
+                        fido2_credentials: vec![],
                     })),
 
                     favorite: true,
@@ -663,6 +1065,7 @@ mod tests {
                             linked_id: Some(101),
                         },
                     ],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T14:09:33.753Z".parse().unwrap(),
                     creation_date: "2024-01-30T11:23:54.416Z".parse().unwrap(),
@@ -671,6 +1074,8 @@ mod tests {
                 Cipher {
                     id: "23f0f877-42b1-4820-a850-b10700bc41eb".parse().unwrap(),
                     folder_id: None,
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "My secure note".to_string(),
                     notes: Some("Very secure!".to_string()),
@@ -683,6 +1088,7 @@ mod tests {
                     reprompt: 0,
 
                     fields: vec![],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T11:25:25.466Z".parse().unwrap(),
                     creation_date: "2024-01-30T11:25:25.466Z".parse().unwrap(),
@@ -691,6 +1097,8 @@ mod tests {
                 Cipher {
                     id: "3ed8de45-48ee-4e26-a2dc-b10701276c53".parse().unwrap(),
                     folder_id: None,
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "My card".to_string(),
                     notes: None,
@@ -708,6 +1116,7 @@ mod tests {
                     reprompt: 0,
 
                     fields: vec![],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
                     creation_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
@@ -716,6 +1125,8 @@ mod tests {
                 Cipher {
                     id: "41cc3bc1-c3d9-4637-876c-b10701273712".parse().unwrap(),
                     folder_id: Some("942e2984-1b9a-453b-b039-b107012713b9".parse().unwrap()),
+                    organization_id: None,
+                    collection_ids: vec![],
 
                     name: "My identity".to_string(),
                     notes: None,
@@ -745,6 +1156,7 @@ mod tests {
                     reprompt: 0,
 
                     fields: vec![],
+                    password_history: vec![],
 
                     revision_date: "2024-01-30T17:54:50.706Z".parse().unwrap(),
                     creation_date: "2024-01-30T17:54:50.706Z".parse().unwrap(),
@@ -759,4 +1171,116 @@ mod tests {
             expected.parse::<serde_json::Value>().unwrap()
         )
     }
+
+    #[test]
+    fn test_import_json_round_trip() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("resources");
+        d.push("json_export.json");
+
+        let expected = fs::read_to_string(d).unwrap();
+
+        let (folders, ciphers) = import_json(&expected).unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(ciphers.len(), 4);
+
+        let export = export_json(folders, ciphers).unwrap();
+
+        assert_eq!(
+            export.parse::<serde_json::Value>().unwrap(),
+            expected.parse::<serde_json::Value>().unwrap()
+        )
+    }
+
+    #[test]
+    fn test_import_json_minimal() {
+        // Hand written imports only need the essentials
+        let (folders, ciphers) = import_json(
+            r#"{
+                "encrypted": false,
+                "items": [
+                    {
+                        "type": 1,
+                        "name": "Bitwarden",
+                        "notes": null,
+                        "login": { "username": "test@bitwarden.com", "password": "asdf" }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert!(folders.is_empty());
+        assert_eq!(ciphers.len(), 1);
+        assert_eq!(ciphers[0].name, "Bitwarden");
+        assert!(
+            matches!(&ciphers[0].r#type, CipherType::Login(l) if l.password.as_deref() == Some("asdf"))
+        );
+    }
+
+    #[test]
+    fn test_import_json_passkeys() {
+        let (_, ciphers) = import_json(
+            r#"{
+                "encrypted": false,
+                "items": [
+                    {
+                        "type": 1,
+                        "name": "Bitwarden",
+                        "login": {
+                            "username": "test@bitwarden.com",
+                            "fido2Credentials": [
+                                {
+                                    "credentialId": "e8d88789-e916-e196-3cbd-81dafae71bbc",
+                                    "keyType": "public-key",
+                                    "keyAlgorithm": "ECDSA",
+                                    "keyCurve": "P-256",
+                                    "keyValue": "AAECAwQF",
+                                    "rpId": "bitwarden.com",
+                                    "userHandle": "YWxpY2U",
+                                    "userName": "test@bitwarden.com",
+                                    "counter": "0",
+                                    "rpName": "Bitwarden",
+                                    "userDisplayName": "Test",
+                                    "discoverable": "true",
+                                    "creationDate": "2024-01-30T17:55:36.150Z"
+                                }
+                            ]
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let CipherType::Login(login) = &ciphers[0].r#type else {
+            panic!("Expected a login");
+        };
+        assert_eq!(login.fido2_credentials.len(), 1);
+        let passkey = &login.fido2_credentials[0];
+        assert_eq!(
+            passkey.credential_id,
+            "e8d88789-e916-e196-3cbd-81dafae71bbc"
+        );
+        assert_eq!(passkey.key_value, "AAECAwQF");
+        assert_eq!(passkey.rp_id, "bitwarden.com");
+        assert_eq!(passkey.user_handle.as_deref(), Some("YWxpY2U"));
+        assert_eq!(passkey.discoverable, "true");
+    }
+
+    #[test]
+    fn test_import_json_errors() {
+        assert!(matches!(
+            import_json(r#"{ "encrypted": true, "passwordProtected": true, "data": "" }"#),
+            Err(JsonError::Encrypted)
+        ));
+        assert!(matches!(
+            import_json(r#"{ "encrypted": false, "items": [{ "type": 1, "name": "No login" }] }"#),
+            Err(JsonError::MissingField(_))
+        ));
+        assert!(matches!(
+            import_json(r#"{ "encrypted": false, "items": [{ "type": 9, "name": "Unknown" }] }"#),
+            Err(JsonError::UnsupportedType(9))
+        ));
+    }
 }
@@ -6,23 +6,93 @@ use uuid::Uuid;
 
 #[cfg(feature = "uniffi")]
 uniffi::setup_scaffolding!();
+#[cfg(feature = "uniffi")]
+mod uniffi_support;
 
 mod client_exporter;
 mod csv;
+mod cxf;
 mod encrypted_json;
 mod json;
 mod models;
 pub use client_exporter::{ClientExporters, ClientExportersExt};
 mod error;
 mod export;
-pub use error::ExportError;
+mod import;
+mod importers;
+pub use cxf::CxfError;
+pub use error::{ExportError, ImportError};
+pub use importers::ImporterError;
 
 #[derive(JsonSchema)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum ExportFormat {
+    Csv,
+    /// CSV including cards and identities, which have their own columns. Older importers ignore
+    /// the extra columns and import every row as a login. Only supported for personal vaults.
+    CsvAllTypes,
+    Json,
+    EncryptedJson {
+        password: String,
+    },
+    /// JSON encrypted with the user key, or the organization key for organization exports. It
+    /// can only be imported back into the same account or organization.
+    AccountEncryptedJson,
+    /// The FIDO Alliance Credential Exchange Format, which includes the passkeys. Only supported
+    /// for personal vaults.
+    Cxf {
+        account: Account,
+    },
+}
+
+#[derive(JsonSchema)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ImportFormat {
     Csv,
     Json,
-    EncryptedJson { password: String },
+    EncryptedJson {
+        password: String,
+    },
+    /// 1Password `.1pux` export
+    OnePassword1Pux,
+    /// KeePass and KeePassXC `KeePass XML (2.x)` export
+    KeePassXml,
+    LastPassCsv,
+    /// Chrome, Edge and other Chromium based browsers
+    ChromeCsv,
+    FirefoxCsv,
+    /// Dashlane CSV export, either the zip archive or one of the CSV files it contains
+    DashlaneCsv,
+    /// The FIDO Alliance Credential Exchange Format, exported by any provider supporting it
+    Cxf,
+}
+
+/// The account being exported, exports in the Credential Exchange Format identify the account
+/// the credentials belong to
+#[derive(JsonSchema)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Account {
+    pub id: Uuid,
+    pub email: String,
+    pub name: Option<String>,
+}
+
+/// The outcome of an import
+#[derive(Debug, JsonSchema)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ImportResult {
+    pub folders: u32,
+    pub ciphers: u32,
+    pub warnings: Vec<ImportWarning>,
+}
+
+/// An item of the export that was only partially imported or skipped
+#[derive(Debug, JsonSchema, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ImportWarning {
+    /// Position of the item in the export, the line number for CSV files
+    pub row: u32,
+    pub message: String,
 }
 
 /// Export representation of a Bitwarden folder.
@@ -34,6 +104,17 @@ pub struct Folder {
     pub name: String,
 }
 
+/// Export representation of a Bitwarden collection.
+///
+/// These are mostly duplicated from the `bitwarden` vault models to facilitate a stable export API
+/// that is not tied to the internal vault models. We may revisit this in the future.
+pub struct Collection {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub external_id: Option<String>,
+}
+
 /// Export representation of a Bitwarden cipher.
 ///
 /// These are mostly duplicated from the `bitwarden` vault models to facilitate a stable export API
@@ -41,6 +122,8 @@ pub struct Folder {
 pub struct Cipher {
     pub id: Uuid,
     pub folder_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub collection_ids: Vec<Uuid>,
 
     pub name: String,
     pub notes: Option<String>,
@@ -51,12 +134,19 @@ pub struct Cipher {
     pub reprompt: u8,
 
     pub fields: Vec<Field>,
+    pub password_history: Vec<PasswordHistory>,
 
     pub revision_date: DateTime<Utc>,
     pub creation_date: DateTime<Utc>,
     pub deleted_date: Option<DateTime<Utc>>,
 }
 
+#[derive(Clone)]
+pub struct PasswordHistory {
+    pub password: String,
+    pub last_used_date: DateTime<Utc>,
+}
+
 #[derive(Clone)]
 pub struct Field {
     pub name: Option<String>,
@@ -88,6 +178,25 @@ pub struct Login {
     pub password: Option<String>,
     pub login_uris: Vec<LoginUri>,
     pub totp: Option<String>,
+    /// Only included in the formats that export passkeys
+    pub fido2_credentials: Vec<Fido2Credential>,
+}
+
+/// A passkey, `key_value` is the private key as a base64url encoded PKCS#8 document
+pub struct Fido2Credential {
+    pub credential_id: String,
+    pub key_type: String,
+    pub key_algorithm: String,
+    pub key_curve: String,
+    pub key_value: String,
+    pub rp_id: String,
+    pub user_handle: Option<String>,
+    pub user_name: Option<String>,
+    pub counter: String,
+    pub rp_name: Option<String>,
+    pub user_display_name: Option<String>,
+    pub discoverable: String,
+    pub creation_date: DateTime<Utc>,
 }
 
 pub struct LoginUri {
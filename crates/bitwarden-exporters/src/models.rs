@@ -1,6 +1,8 @@
 use bitwarden_core::{require, MissingFieldError};
 use bitwarden_vault::{
-    CipherType, CipherView, FieldView, FolderView, LoginUriView, SecureNoteType,
+    CardView, CipherRepromptType, CipherType, CipherView, CollectionView, Fido2CredentialFullView,
+    FieldType, FieldView, FolderView, IdentityView, LoginUriView, LoginView, PasswordHistoryView,
+    SecureNoteType, SecureNoteView, UriMatchType,
 };
 
 impl TryFrom<FolderView> for crate::Folder {
@@ -14,6 +16,19 @@ impl TryFrom<FolderView> for crate::Folder {
     }
 }
 
+impl TryFrom<CollectionView> for crate::Collection {
+    type Error = MissingFieldError;
+
+    fn try_from(value: CollectionView) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: require!(value.id),
+            organization_id: value.organization_id,
+            name: value.name,
+            external_id: value.external_id,
+        })
+    }
+}
+
 impl TryFrom<CipherView> for crate::Cipher {
     type Error = MissingFieldError;
 
@@ -31,6 +46,7 @@ impl TryFrom<CipherView> for crate::Cipher {
                         .map(|u| u.into())
                         .collect(),
                     totp: l.totp,
+                    fido2_credentials: vec![],
                 }))
             }
             CipherType::SecureNote => crate::CipherType::SecureNote(Box::new(crate::SecureNote {
@@ -79,6 +95,8 @@ impl TryFrom<CipherView> for crate::Cipher {
         Ok(Self {
             id: require!(value.id),
             folder_id: value.folder_id,
+            organization_id: value.organization_id,
+            collection_ids: value.collection_ids,
             name: value.name,
             notes: value.notes,
             r#type: r,
@@ -90,6 +108,12 @@ impl TryFrom<CipherView> for crate::Cipher {
                 .into_iter()
                 .map(|f| f.into())
                 .collect(),
+            password_history: value
+                .password_history
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
             revision_date: value.revision_date,
             creation_date: value.creation_date,
             deleted_date: value.deleted_date,
@@ -108,6 +132,35 @@ impl From<FieldView> for crate::Field {
     }
 }
 
+impl From<PasswordHistoryView> for crate::PasswordHistory {
+    fn from(value: PasswordHistoryView) -> Self {
+        Self {
+            password: value.password,
+            last_used_date: value.last_used_date,
+        }
+    }
+}
+
+impl From<Fido2CredentialFullView> for crate::Fido2Credential {
+    fn from(value: Fido2CredentialFullView) -> Self {
+        Self {
+            credential_id: value.credential_id,
+            key_type: value.key_type,
+            key_algorithm: value.key_algorithm,
+            key_curve: value.key_curve,
+            key_value: value.key_value,
+            rp_id: value.rp_id,
+            user_handle: value.user_handle,
+            user_name: value.user_name,
+            counter: value.counter,
+            rp_name: value.rp_name,
+            user_display_name: value.user_display_name,
+            discoverable: value.discoverable,
+            creation_date: value.creation_date,
+        }
+    }
+}
+
 impl From<LoginUriView> for crate::LoginUri {
     fn from(value: LoginUriView) -> Self {
         Self {
@@ -125,6 +178,188 @@ impl From<SecureNoteType> for crate::SecureNoteType {
     }
 }
 
+impl From<crate::Folder> for FolderView {
+    fn from(value: crate::Folder) -> Self {
+        Self {
+            id: Some(value.id),
+            name: value.name,
+            revision_date: chrono::Utc::now(),
+        }
+    }
+}
+
+impl From<crate::Cipher> for CipherView {
+    fn from(value: crate::Cipher) -> Self {
+        let (r#type, login, secure_note, card, identity) = match value.r#type {
+            crate::CipherType::Login(l) => (
+                CipherType::Login,
+                Some(LoginView {
+                    username: l.username,
+                    password: l.password,
+                    password_revision_date: None,
+                    uris: Some(l.login_uris.into_iter().map(|u| u.into()).collect()),
+                    totp: l.totp,
+                    autofill_on_page_load: None,
+                    fido2_credentials: None,
+                }),
+                None,
+                None,
+                None,
+            ),
+            crate::CipherType::SecureNote(n) => (
+                CipherType::SecureNote,
+                None,
+                Some(SecureNoteView {
+                    r#type: n.r#type.into(),
+                }),
+                None,
+                None,
+            ),
+            crate::CipherType::Card(c) => (
+                CipherType::Card,
+                None,
+                None,
+                Some(CardView {
+                    cardholder_name: c.cardholder_name,
+                    exp_month: c.exp_month,
+                    exp_year: c.exp_year,
+                    code: c.code,
+                    brand: c.brand,
+                    number: c.number,
+                }),
+                None,
+            ),
+            crate::CipherType::Identity(i) => (
+                CipherType::Identity,
+                None,
+                None,
+                None,
+                Some(IdentityView {
+                    title: i.title,
+                    first_name: i.first_name,
+                    middle_name: i.middle_name,
+                    last_name: i.last_name,
+                    address1: i.address1,
+                    address2: i.address2,
+                    address3: i.address3,
+                    city: i.city,
+                    state: i.state,
+                    postal_code: i.postal_code,
+                    country: i.country,
+                    company: i.company,
+                    email: i.email,
+                    phone: i.phone,
+                    ssn: i.ssn,
+                    username: i.username,
+                    passport_number: i.passport_number,
+                    license_number: i.license_number,
+                }),
+            ),
+        };
+
+        Self {
+            id: Some(value.id),
+            organization_id: None,
+            folder_id: value.folder_id,
+            collection_ids: vec![],
+            key: None,
+            name: value.name,
+            notes: value.notes,
+            r#type,
+            login,
+            identity,
+            card,
+            secure_note,
+            favorite: value.favorite,
+            reprompt: match value.reprompt {
+                1 => CipherRepromptType::Password,
+                _ => CipherRepromptType::None,
+            },
+            organization_use_totp: true,
+            edit: true,
+            view_password: true,
+            local_data: None,
+            attachments: None,
+            fields: Some(value.fields.into_iter().map(|f| f.into()).collect()),
+            password_history: (!value.password_history.is_empty()).then(|| {
+                value
+                    .password_history
+                    .into_iter()
+                    .map(|p| PasswordHistoryView {
+                        password: p.password,
+                        last_used_date: p.last_used_date,
+                    })
+                    .collect()
+            }),
+            creation_date: value.creation_date,
+            deleted_date: value.deleted_date,
+            revision_date: value.revision_date,
+        }
+    }
+}
+
+impl From<crate::Fido2Credential> for Fido2CredentialFullView {
+    fn from(value: crate::Fido2Credential) -> Self {
+        Self {
+            credential_id: value.credential_id,
+            key_type: value.key_type,
+            key_algorithm: value.key_algorithm,
+            key_curve: value.key_curve,
+            key_value: value.key_value,
+            rp_id: value.rp_id,
+            user_handle: value.user_handle,
+            user_name: value.user_name,
+            counter: value.counter,
+            rp_name: value.rp_name,
+            user_display_name: value.user_display_name,
+            discoverable: value.discoverable,
+            creation_date: value.creation_date,
+        }
+    }
+}
+
+impl From<crate::Field> for FieldView {
+    fn from(value: crate::Field) -> Self {
+        Self {
+            name: value.name,
+            value: value.value,
+            r#type: match value.r#type {
+                1 => FieldType::Hidden,
+                2 => FieldType::Boolean,
+                3 => FieldType::Linked,
+                _ => FieldType::Text,
+            },
+            linked_id: value.linked_id.and_then(|id| id.try_into().ok()),
+        }
+    }
+}
+
+impl From<crate::LoginUri> for LoginUriView {
+    fn from(value: crate::LoginUri) -> Self {
+        Self {
+            uri: value.uri,
+            r#match: value.r#match.and_then(|m| match m {
+                0 => Some(UriMatchType::Domain),
+                1 => Some(UriMatchType::Host),
+                2 => Some(UriMatchType::StartsWith),
+                3 => Some(UriMatchType::Exact),
+                4 => Some(UriMatchType::RegularExpression),
+                5 => Some(UriMatchType::Never),
+                _ => None,
+            }),
+            uri_checksum: None,
+        }
+    }
+}
+
+impl From<crate::SecureNoteType> for SecureNoteType {
+    fn from(value: crate::SecureNoteType) -> Self {
+        match value {
+            crate::SecureNoteType::Generic => SecureNoteType::Generic,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bitwarden_vault::{CipherRepromptType, LoginView};
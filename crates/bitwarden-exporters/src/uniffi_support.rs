@@ -0,0 +1,3 @@
+use uuid::Uuid;
+
+uniffi::ffi_converter_forward!(Uuid, bitwarden_core::UniFfiTag, crate::UniFfiTag);
@@ -7,6 +7,8 @@ use bitwarden::{
     secrets_manager::{ClientProjectsExt, ClientSecretsExt},
 };
 
+#[cfg(feature = "internal")]
+use crate::command::{CiphersCommand, CollectionsCommand, FoldersCommand};
 #[cfg(feature = "secrets")]
 use crate::command::{GeneratorsCommand, ProjectsCommand, SecretsCommand};
 use crate::{
@@ -23,7 +25,7 @@ impl Client {
     }
 
     pub async fn run_command(&self, input_str: &str) -> String {
-        const SUBCOMMANDS_TO_CLEAN: &[&str] = &["Secrets"];
+        const SUBCOMMANDS_TO_CLEAN: &[&str] = &["Secrets", "Folders", "Collections", "Ciphers"];
         let mut cmd_value: serde_json::Value = match serde_json::from_str(input_str) {
             Ok(cmd) => cmd,
             Err(e) => {
@@ -71,6 +73,57 @@ impl Client {
             #[cfg(feature = "internal")]
             Command::Fingerprint(req) => client.platform().fingerprint(&req).into_string(),
 
+            #[cfg(feature = "internal")]
+            Command::Folders(cmd) => match cmd {
+                FoldersCommand::Create(req) => {
+                    client.vault().folders().create(&req).await.into_string()
+                }
+                FoldersCommand::Update(req) => {
+                    client.vault().folders().update(&req).await.into_string()
+                }
+                FoldersCommand::Delete(req) => {
+                    client.vault().folders().delete(&req).await.into_string()
+                }
+            },
+
+            #[cfg(feature = "internal")]
+            Command::Collections(cmd) => match cmd {
+                CollectionsCommand::Create(req) => client
+                    .vault()
+                    .collections()
+                    .create(&req)
+                    .await
+                    .into_string(),
+                CollectionsCommand::Update(req) => client
+                    .vault()
+                    .collections()
+                    .update(&req)
+                    .await
+                    .into_string(),
+                CollectionsCommand::Delete(req) => client
+                    .vault()
+                    .collections()
+                    .delete(&req)
+                    .await
+                    .into_string(),
+            },
+
+            #[cfg(feature = "internal")]
+            Command::Ciphers(cmd) => match cmd {
+                CiphersCommand::Move(req) => client
+                    .vault()
+                    .ciphers()
+                    .move_to_folder(&req)
+                    .await
+                    .into_string(),
+                CiphersCommand::UpdateCollections(req) => client
+                    .vault()
+                    .ciphers()
+                    .update_collections(&req)
+                    .await
+                    .into_string(),
+            },
+
             #[cfg(feature = "secrets")]
             Command::Secrets(cmd) => match cmd {
                 SecretsCommand::Get(req) => client.secrets().get(&req).await.into_string(),
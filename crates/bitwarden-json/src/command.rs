@@ -17,7 +17,11 @@ use bitwarden::{
 use bitwarden::{
     auth::login::{ApiKeyLoginRequest, PasswordLoginRequest},
     platform::{FingerprintRequest, SecretVerificationRequest},
-    vault::SyncRequest,
+    vault::{
+        CipherCollectionsRequest, CipherMoveRequest, CollectionCreateRequest,
+        CollectionDeleteRequest, CollectionUpdateRequest, FolderCreateRequest, FolderDeleteRequest,
+        FolderUpdateRequest, SyncRequest,
+    },
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -73,6 +77,13 @@ pub enum Command {
     /// Returns: [SyncResponse](bitwarden::vault::SyncResponse)
     Sync(SyncRequest),
 
+    #[cfg(feature = "internal")]
+    Folders(FoldersCommand),
+    #[cfg(feature = "internal")]
+    Collections(CollectionsCommand),
+    #[cfg(feature = "internal")]
+    Ciphers(CiphersCommand),
+
     #[cfg(feature = "secrets")]
     Secrets(SecretsCommand),
     #[cfg(feature = "secrets")]
@@ -188,3 +199,69 @@ pub enum GeneratorsCommand {
     /// Returns: [String]
     GeneratePassword(PasswordGeneratorRequest),
 }
+
+#[cfg(feature = "internal")]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum FoldersCommand {
+    /// > Requires Authentication
+    /// Creates a new folder with the given name
+    ///
+    /// Returns: [FolderView](bitwarden::vault::FolderView)
+    Create(FolderCreateRequest),
+
+    /// > Requires Authentication
+    /// Renames an existing folder
+    ///
+    /// Returns: [FolderView](bitwarden::vault::FolderView)
+    Update(FolderUpdateRequest),
+
+    /// > Requires Authentication
+    /// Deletes a folder, the ciphers it contains are moved out of it
+    ///
+    /// Returns: ()
+    Delete(FolderDeleteRequest),
+}
+
+#[cfg(feature = "internal")]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum CollectionsCommand {
+    /// > Requires Authentication
+    /// > Requires permission to manage the organization's collections
+    /// Creates a new collection in the provided organization
+    ///
+    /// Returns: [CollectionView](bitwarden::vault::CollectionView)
+    Create(CollectionCreateRequest),
+
+    /// > Requires Authentication
+    /// > Requires permission to manage the organization's collections
+    /// Updates the name and external id of an existing collection
+    ///
+    /// Returns: [CollectionView](bitwarden::vault::CollectionView)
+    Update(CollectionUpdateRequest),
+
+    /// > Requires Authentication
+    /// > Requires permission to manage the organization's collections
+    /// Deletes a collection, the ciphers it contains are kept in the organization
+    ///
+    /// Returns: ()
+    Delete(CollectionDeleteRequest),
+}
+
+#[cfg(feature = "internal")]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum CiphersCommand {
+    /// > Requires Authentication
+    /// Moves the ciphers to the provided folder, or out of their folder when none is provided
+    ///
+    /// Returns: ()
+    Move(CipherMoveRequest),
+
+    /// > Requires Authentication
+    /// Replaces the collections an organization cipher belongs to
+    ///
+    /// Returns: [CipherView](bitwarden::vault::CipherView)
+    UpdateCollections(CipherCollectionsRequest),
+}
@@ -0,0 +1,255 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use bitwarden_api_api::models::{SendFileModel, SendTextModel};
+use bitwarden_core::require;
+use bitwarden_crypto::{CryptoError, EncString, KeyDecryptable, SymmetricCryptoKey};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    Send, SendError, SendFile, SendFileView, SendParseError, SendText, SendTextView, SendType,
+};
+
+/// A link to a Send, as created by [crate::SendView::share_url]
+#[derive(Debug, PartialEq)]
+pub struct SendLink {
+    /// The web vault the link was created on
+    pub web_vault_url: String,
+    pub access_id: String,
+    /// URL safe base64 encoded key
+    pub key: String,
+}
+
+impl SendLink {
+    /// Parse a link of the form `https://vault.bitwarden.com/#/send/<access_id>/<key>`, or
+    /// `https://send.bitwarden.com/#<access_id>/<key>` as shared from Bitwarden cloud
+    pub fn parse(url: &str) -> Result<Self, SendError> {
+        let (base, fragment) = url.trim().split_once('#').ok_or(SendError::InvalidLink)?;
+        if !base.starts_with("https://") && !base.starts_with("http://") {
+            return Err(SendError::InvalidLink);
+        }
+
+        let mut parts = fragment.split('/').filter(|p| !p.is_empty()).peekable();
+        // Links to the web vault route the Send under `send/`
+        parts.next_if_eq(&"send");
+        let (Some(access_id), Some(key), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(SendError::InvalidLink);
+        };
+
+        Ok(Self {
+            web_vault_url: base.trim_end_matches('/').to_owned(),
+            access_id: access_id.to_owned(),
+            key: key.to_owned(),
+        })
+    }
+
+    /// Bitwarden cloud serves Sends from `vault.` or `send.` and the API from `api.`, while
+    /// self-hosted servers serve the API from `/api` under the web vault.
+    pub fn api_url(&self) -> String {
+        for prefix in ["://vault.", "://send."] {
+            if self.web_vault_url.contains(prefix) {
+                return self.web_vault_url.replacen(prefix, "://api.", 1);
+            }
+        }
+        format!("{}/api", self.web_vault_url)
+    }
+
+    fn key_bytes(&self) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+        Ok(Zeroizing::new(
+            URL_SAFE_NO_PAD
+                .decode(&self.key)
+                .map_err(|_| CryptoError::InvalidKey)?,
+        ))
+    }
+
+    pub(crate) fn get_key(&self) -> Result<SymmetricCryptoKey, CryptoError> {
+        Send::derive_shareable_key(&self.key_bytes()?)
+    }
+
+    /// The hash the server expects when accessing a password protected Send
+    pub(crate) fn password_hash(&self, password: &str) -> Result<String, CryptoError> {
+        Ok(Send::hash_password(password, &self.key_bytes()?))
+    }
+}
+
+/// The response of `POST /sends/access/{id}`, which isn't described by the generated API client
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SendAccessResponseModel {
+    pub id: Option<String>,
+    pub r#type: Option<bitwarden_api_api::models::SendType>,
+    pub name: Option<String>,
+    pub file: Option<SendFileModel>,
+    pub text: Option<SendTextModel>,
+    pub expiration_date: Option<String>,
+    pub creator_identifier: Option<String>,
+}
+
+/// The response of `POST /sends/{id}/access/file/{file_id}`
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SendFileDownloadDataResponseModel {
+    pub url: Option<String>,
+}
+
+/// A Send as seen by its recipients
+#[derive(Debug)]
+pub(crate) struct SendAccess {
+    id: String,
+    name: EncString,
+    r#type: SendType,
+    file: Option<SendFile>,
+    text: Option<SendText>,
+    expiration_date: Option<DateTime<Utc>>,
+    creator_identifier: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, PartialEq, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SendAccessView {
+    pub id: String,
+    pub name: String,
+    pub r#type: SendType,
+    pub file: Option<SendFileView>,
+    pub text: Option<SendTextView>,
+    pub expiration_date: Option<DateTime<Utc>>,
+    /// Email of the user who created the Send, unless they chose to hide it
+    pub creator_identifier: Option<String>,
+}
+
+impl TryFrom<SendAccessResponseModel> for SendAccess {
+    type Error = SendParseError;
+
+    fn try_from(send: SendAccessResponseModel) -> Result<Self, Self::Error> {
+        Ok(SendAccess {
+            id: require!(send.id),
+            name: require!(send.name).parse()?,
+            r#type: require!(send.r#type).into(),
+            file: send.file.map(|f| f.try_into()).transpose()?,
+            text: send.text.map(|t| t.try_into()).transpose()?,
+            expiration_date: send.expiration_date.map(|s| s.parse()).transpose()?,
+            creator_identifier: send.creator_identifier,
+        })
+    }
+}
+
+impl KeyDecryptable<SymmetricCryptoKey, SendAccessView> for SendAccess {
+    fn decrypt_with_key(&self, key: &SymmetricCryptoKey) -> Result<SendAccessView, CryptoError> {
+        Ok(SendAccessView {
+            id: self.id.clone(),
+            name: self.name.decrypt_with_key(key)?,
+            r#type: self.r#type,
+            file: self.file.decrypt_with_key(key)?,
+            text: self.text.decrypt_with_key(key)?,
+            expiration_date: self.expiration_date,
+            creator_identifier: self.creator_identifier.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitwarden_crypto::KeyEncryptable;
+
+    use super::*;
+
+    const LINK: &str =
+        "https://vault.bitwarden.com/#/send/ct2APRQtJk-BLLDwAYqhRA/Pgui0FK85cNhBGWHAlBHBw";
+
+    #[test]
+    fn test_parse() {
+        let link = SendLink::parse(LINK).unwrap();
+        assert_eq!(
+            link,
+            SendLink {
+                web_vault_url: "https://vault.bitwarden.com".to_owned(),
+                access_id: "ct2APRQtJk-BLLDwAYqhRA".to_owned(),
+                key: "Pgui0FK85cNhBGWHAlBHBw".to_owned(),
+            }
+        );
+        assert_eq!(link.api_url(), "https://api.bitwarden.com");
+
+        let link =
+            SendLink::parse("https://bitwarden.example.com/#/send/ct2APRQtJk-BLLDwAYqhRA/key/")
+                .unwrap();
+        assert_eq!(link.api_url(), "https://bitwarden.example.com/api");
+
+        let link = SendLink::parse(
+            "https://send.bitwarden.com/#ct2APRQtJk-BLLDwAYqhRA/Pgui0FK85cNhBGWHAlBHBw",
+        )
+        .unwrap();
+        assert_eq!(
+            link,
+            SendLink {
+                web_vault_url: "https://send.bitwarden.com".to_owned(),
+                access_id: "ct2APRQtJk-BLLDwAYqhRA".to_owned(),
+                key: "Pgui0FK85cNhBGWHAlBHBw".to_owned(),
+            }
+        );
+        assert_eq!(link.api_url(), "https://api.bitwarden.com");
+
+        assert!(
+            SendLink::parse("https://vault.bitwarden.com/#/send/ct2APRQtJk-BLLDwAYqhRA").is_err()
+        );
+        assert!(SendLink::parse("https://send.bitwarden.com/#ct2APRQtJk-BLLDwAYqhRA").is_err());
+        assert!(SendLink::parse("https://vault.bitwarden.com/#/vault").is_err());
+        assert!(SendLink::parse("vault.bitwarden.com/#/send/a/b").is_err());
+    }
+
+    #[test]
+    fn test_password_hash() {
+        // Matches the hash set when creating a Send with the same key and password
+        let link = SendLink::parse(LINK).unwrap();
+        assert_eq!(
+            link.password_hash("abc123").unwrap(),
+            "vTIDfdj3FTDbejmMf+mJWpYdMXsxfeSd1Sma3sjCtiQ="
+        );
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let link = SendLink::parse(LINK).unwrap();
+        let key = link.get_key().unwrap();
+
+        let response = SendAccessResponseModel {
+            id: Some("ct2APRQtJk-BLLDwAYqhRA".to_owned()),
+            r#type: Some(bitwarden_api_api::models::SendType::Text),
+            name: Some("Test".encrypt_with_key(&key).unwrap().to_string()),
+            file: None,
+            text: Some(SendTextModel {
+                text: Some("This is a test".encrypt_with_key(&key).unwrap().to_string()),
+                hidden: Some(true),
+            }),
+            expiration_date: None,
+            creator_identifier: Some("test@bitwarden.com".to_owned()),
+        };
+
+        let send: SendAccess = response.try_into().unwrap();
+        let view: SendAccessView = send.decrypt_with_key(&key).unwrap();
+
+        assert_eq!(
+            view,
+            SendAccessView {
+                id: "ct2APRQtJk-BLLDwAYqhRA".to_owned(),
+                name: "Test".to_owned(),
+                r#type: SendType::Text,
+                file: None,
+                text: Some(SendTextView {
+                    text: Some("This is a test".to_owned()),
+                    hidden: true,
+                }),
+                expiration_date: None,
+                creator_identifier: Some("test@bitwarden.com".to_owned()),
+            }
+        );
+
+        // A link with a different key can't open the Send
+        let other = SendLink {
+            key: "AAAAAAAAAAAAAAAAAAAAAA".to_owned(),
+            ..link
+        };
+        assert!(send.decrypt_with_key(&other.get_key().unwrap()).is_err());
+    }
+}
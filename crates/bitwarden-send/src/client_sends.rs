@@ -1,9 +1,22 @@
-use std::path::Path;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
 
-use bitwarden_core::{Client, Error};
-use bitwarden_crypto::{EncString, KeyDecryptable, KeyEncryptable};
+use bitwarden_api_api::models::{
+    FileUploadType, SendAccessRequestModel, SendRequestModel, SendResponseModel,
+};
+use bitwarden_core::{files, require, Client, Error};
+use bitwarden_crypto::{encrypt_stream, EncString, KeyDecryptable, KeyEncryptable};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
 
-use crate::{Send, SendListView, SendView};
+use crate::{
+    access::{SendAccess, SendAccessResponseModel, SendFileDownloadDataResponseModel},
+    Send, SendAccessView, SendError, SendFileView, SendLink, SendListView, SendType, SendView,
+};
 
 pub struct ClientSends<'a> {
     client: &'a Client,
@@ -38,10 +51,11 @@ impl<'a> ClientSends<'a> {
         encrypted_file_path: &Path,
         decrypted_file_path: &Path,
     ) -> Result<(), Error> {
-        let data = std::fs::read(encrypted_file_path)?;
-        let decrypted = self.decrypt_buffer(send, &data)?;
-        std::fs::write(decrypted_file_path, decrypted)?;
-        Ok(())
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = enc.get_key(&None)?;
+        let key = Send::get_key(&send.key, key)?;
+
+        files::decrypt_file(&key, encrypted_file_path, decrypted_file_path)
     }
 
     pub fn decrypt_buffer(&self, send: Send, encrypted_buffer: &[u8]) -> Result<Vec<u8>, Error> {
@@ -68,9 +82,16 @@ impl<'a> ClientSends<'a> {
         decrypted_file_path: &Path,
         encrypted_file_path: &Path,
     ) -> Result<(), Error> {
-        let data = std::fs::read(decrypted_file_path)?;
-        let encrypted = self.encrypt_buffer(send, &data)?;
-        std::fs::write(encrypted_file_path, encrypted)?;
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = enc.get_key(&None)?;
+        let key = Send::get_key(&send.key, key)?;
+
+        encrypt_stream(
+            &key,
+            BufReader::new(File::open(decrypted_file_path)?),
+            BufWriter::new(File::create(encrypted_file_path)?),
+        )?;
+
         Ok(())
     }
 
@@ -84,6 +105,253 @@ impl<'a> ClientSends<'a> {
     }
 }
 
+impl<'a> ClientSends<'a> {
+    /// List the Sends of the current user
+    pub async fn list(&self) -> Result<Vec<SendView>, SendError> {
+        let config = self.client.internal.get_api_configurations().await;
+        let response = bitwarden_api_api::apis::sends_api::sends_get(&config.api)
+            .await
+            .map_err(Error::from)?;
+
+        response
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| self.process_response(s))
+            .collect()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<SendView, SendError> {
+        let config = self.client.internal.get_api_configurations().await;
+        let response =
+            bitwarden_api_api::apis::sends_api::sends_id_get(&config.api, &id.to_string())
+                .await
+                .map_err(Error::from)?;
+
+        self.process_response(response)
+    }
+
+    /// Create a text Send on the server. Use [ClientSends::create_file] for file Sends.
+    pub async fn create(&self, send_view: SendView) -> Result<SendView, SendError> {
+        let request: SendRequestModel = self.encrypt(send_view)?.into();
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response = bitwarden_api_api::apis::sends_api::sends_post(&config.api, Some(request))
+            .await
+            .map_err(Error::from)?;
+
+        self.process_response(response)
+    }
+
+    /// Create a file Send on the server, encrypting and uploading the file at `path`
+    pub async fn create_file(
+        &self,
+        mut send_view: SendView,
+        path: &Path,
+    ) -> Result<SendView, SendError> {
+        let file_name = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .ok_or(Error::Internal("The file path has no file name".into()))?;
+        send_view.r#type = SendType::File;
+        send_view.text = None;
+        send_view.file = Some(SendFileView {
+            id: None,
+            file_name,
+            size: None,
+            size_name: None,
+        });
+
+        let send = self.encrypt(send_view)?;
+        let encrypted_file_name = require!(send.file.as_ref()).file_name.to_string();
+
+        let encrypted = tempfile::NamedTempFile::new()?;
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = Send::get_key(&send.key, enc.get_key(&None)?)?;
+        let size = encrypt_stream(
+            &key,
+            BufReader::new(File::open(path)?),
+            BufWriter::new(encrypted.as_file()),
+        )?;
+
+        let mut request: SendRequestModel = send.into();
+        request.file_length = Some(size as i64);
+
+        let config = self.client.internal.get_api_configurations().await;
+        let upload =
+            bitwarden_api_api::apis::sends_api::sends_file_v2_post(&config.api, Some(request))
+                .await
+                .map_err(Error::from)?;
+
+        let (Some(url), Some(send_response)) = (upload.url, upload.send_response) else {
+            return Err(SendError::MissingUploadData);
+        };
+        let send_id = require!(send_response.id).to_string();
+        let file_id = require!(send_response.file.as_ref().and_then(|f| f.id.clone()));
+
+        let result = match upload.file_upload_type {
+            Some(FileUploadType::Azure) => {
+                files::upload_azure(self.client, &url, encrypted.path()).await
+            }
+            _ => {
+                files::upload_self_hosted(
+                    self.client,
+                    &format!("sends/{send_id}/file/{file_id}"),
+                    encrypted_file_name,
+                    encrypted.path(),
+                )
+                .await
+            }
+        };
+
+        // The Send was already created, remove it so it doesn't reference a file that doesn't
+        // exist
+        if let Err(e) = result {
+            _ = bitwarden_api_api::apis::sends_api::sends_id_delete(&config.api, &send_id).await;
+            return Err(e.into());
+        }
+
+        self.process_response(*send_response)
+    }
+
+    /// Update an existing Send. The file of a file Send can't be replaced.
+    pub async fn update(&self, send_view: SendView) -> Result<SendView, SendError> {
+        let id = require!(send_view.id);
+        let request: SendRequestModel = self.encrypt(send_view)?.into();
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response = bitwarden_api_api::apis::sends_api::sends_id_put(
+            &config.api,
+            &id.to_string(),
+            Some(request),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        self.process_response(response)
+    }
+
+    /// Remove the password protecting a Send
+    pub async fn remove_password(&self, id: Uuid) -> Result<SendView, SendError> {
+        let config = self.client.internal.get_api_configurations().await;
+        let response = bitwarden_api_api::apis::sends_api::sends_id_remove_password_put(
+            &config.api,
+            &id.to_string(),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        self.process_response(response)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<(), SendError> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::sends_api::sends_id_delete(&config.api, &id.to_string())
+            .await
+            .map_err(Error::from)?;
+
+        Ok(())
+    }
+
+    /// The link to share a Send, on the web vault of the server the client is connected to
+    pub async fn share_url(&self, send_view: &SendView) -> Result<String, SendError> {
+        let config = self.client.internal.get_api_configurations().await;
+
+        send_view
+            .share_url(&web_vault_url(&config.api.base_path))
+            .ok_or(SendError::NotShareable)
+    }
+
+    /// Open a Send from its share link, parsed with [SendLink::parse]. Receiving a Send doesn't
+    /// require being logged in, the requests are sent to the server the link was created on.
+    pub async fn receive(
+        &self,
+        link: &SendLink,
+        password: Option<&str>,
+    ) -> Result<SendAccessView, SendError> {
+        let response: SendAccessResponseModel = self
+            .access_request(link, &format!("sends/access/{}", link.access_id), password)
+            .await?;
+
+        let send: SendAccess = response.try_into()?;
+        Ok(send.decrypt_with_key(&link.get_key()?)?)
+    }
+
+    /// Download the decrypted file of a file Send opened with [ClientSends::receive] to
+    /// `destination`
+    pub async fn receive_file(
+        &self,
+        link: &SendLink,
+        send: &SendAccessView,
+        password: Option<&str>,
+        destination: &Path,
+    ) -> Result<(), SendError> {
+        let file_id = require!(send.file.as_ref().and_then(|f| f.id.as_deref()));
+
+        let download: SendFileDownloadDataResponseModel = self
+            .access_request(
+                link,
+                &format!("sends/{}/access/file/{file_id}", send.id),
+                password,
+            )
+            .await?;
+        let url = require!(download.url);
+
+        files::download_and_decrypt(self.client, &url, destination, |encrypted_path, _| {
+            Ok(files::decrypt_file(
+                &link.get_key()?,
+                encrypted_path,
+                destination,
+            )?)
+        })
+        .await
+    }
+
+    /// The generated API client discards the response body of the Send access endpoints, so the
+    /// requests are made directly. They're unauthenticated, anyone with the link can access the
+    /// Send.
+    async fn access_request<T: DeserializeOwned>(
+        &self,
+        link: &SendLink,
+        path: &str,
+        password: Option<&str>,
+    ) -> Result<T, SendError> {
+        let request = SendAccessRequestModel {
+            password: password.map(|p| link.password_hash(p)).transpose()?,
+        };
+
+        let response = self
+            .client
+            .internal
+            .get_http_client()
+            .post(format!("{}/{path}", link.api_url()))
+            .json(&request)
+            .send()
+            .await?;
+
+        match response.status() {
+            StatusCode::UNAUTHORIZED => Err(SendError::PasswordRequired),
+            StatusCode::BAD_REQUEST if password.is_some() => Err(SendError::InvalidPassword),
+            _ => Ok(response.error_for_status()?.json().await?),
+        }
+    }
+
+    fn process_response(&self, response: SendResponseModel) -> Result<SendView, SendError> {
+        let send: Send = response.try_into()?;
+        Ok(self.decrypt(send)?)
+    }
+}
+
+/// Bitwarden cloud serves the API from `api.` and the web vault from `vault.`, while self-hosted
+/// servers serve the API from `/api` under the web vault.
+fn web_vault_url(api_url: &str) -> String {
+    let api_url = api_url.trim_end_matches('/');
+    match api_url.strip_suffix("/api") {
+        Some(url) => url.to_owned(),
+        None => api_url.replacen("://api.", "://vault.", 1),
+    }
+}
+
 pub trait ClientSendsExt<'a> {
     fn sends(&'a self) -> ClientSends<'a>;
 }
@@ -93,3 +361,28 @@ impl<'a> ClientSendsExt<'a> for Client {
         ClientSends::new(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_vault_url() {
+        assert_eq!(
+            web_vault_url("https://api.bitwarden.com"),
+            "https://vault.bitwarden.com"
+        );
+        assert_eq!(
+            web_vault_url("https://api.bitwarden.eu/"),
+            "https://vault.bitwarden.eu"
+        );
+        assert_eq!(
+            web_vault_url("https://bitwarden.example.com/api"),
+            "https://bitwarden.example.com"
+        );
+        assert_eq!(
+            web_vault_url("http://localhost:4000"),
+            "http://localhost:4000"
+        );
+    }
+}
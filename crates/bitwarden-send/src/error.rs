@@ -9,3 +9,32 @@ pub enum SendParseError {
     #[error(transparent)]
     MissingFieldError(#[from] bitwarden_core::MissingFieldError),
 }
+
+#[derive(Debug, Error)]
+pub enum SendError {
+    #[error(transparent)]
+    Core(#[from] bitwarden_core::Error),
+    #[error(transparent)]
+    Crypto(#[from] bitwarden_crypto::CryptoError),
+    #[error(transparent)]
+    SendParse(#[from] SendParseError),
+    #[error(transparent)]
+    MissingFieldError(#[from] bitwarden_core::MissingFieldError),
+    #[error(transparent)]
+    VaultLocked(#[from] bitwarden_core::VaultLocked),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("The server didn't provide the details required to upload the file")]
+    MissingUploadData,
+    #[error("The Send has no access id or key, it needs to be saved on the server first")]
+    NotShareable,
+    #[error("The link isn't a valid Send link")]
+    InvalidLink,
+    #[error("The Send is protected by a password")]
+    PasswordRequired,
+    #[error("Invalid password")]
+    InvalidPassword,
+}
@@ -3,9 +3,13 @@ uniffi::setup_scaffolding!();
 #[cfg(feature = "uniffi")]
 mod uniffi_support;
 
+mod access;
+pub use access::{SendAccessView, SendLink};
 mod error;
-pub use error::SendParseError;
+pub use error::{SendError, SendParseError};
 mod client_sends;
 pub use client_sends::{ClientSends, ClientSendsExt};
 mod send;
-pub use send::{Send, SendListView, SendView};
+pub use send::{
+    Send, SendFile, SendFileView, SendListView, SendText, SendTextView, SendType, SendView,
+};
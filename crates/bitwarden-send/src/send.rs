@@ -2,7 +2,9 @@ use base64::{
     engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
     Engine,
 };
-use bitwarden_api_api::models::{SendFileModel, SendResponseModel, SendTextModel};
+use bitwarden_api_api::models::{
+    SendFileModel, SendRequestModel, SendResponseModel, SendTextModel,
+};
 use bitwarden_core::require;
 use bitwarden_crypto::{
     derive_shareable_key, generate_random_bytes, CryptoError, EncString, KeyDecryptable,
@@ -150,10 +152,16 @@ impl Send {
         Self::derive_shareable_key(&key)
     }
 
-    fn derive_shareable_key(key: &[u8]) -> Result<SymmetricCryptoKey, CryptoError> {
+    pub(crate) fn derive_shareable_key(key: &[u8]) -> Result<SymmetricCryptoKey, CryptoError> {
         let key = Zeroizing::new(key.try_into().map_err(|_| CryptoError::InvalidKeyLen)?);
         Ok(derive_shareable_key(key, "send", Some("send")))
     }
+
+    /// Hash the password of a Send with its unstretched key, the server only ever sees the hash
+    pub(crate) fn hash_password(password: &str, key: &[u8]) -> String {
+        let password = bitwarden_crypto::pbkdf2(password.as_bytes(), key, SEND_ITERATIONS);
+        STANDARD.encode(password)
+    }
 }
 
 impl KeyDecryptable<SymmetricCryptoKey, SendTextView> for SendText {
@@ -280,10 +288,9 @@ impl KeyEncryptable<SymmetricCryptoKey, Send> for SendView {
             name: self.name.encrypt_with_key(&send_key)?,
             notes: self.notes.encrypt_with_key(&send_key)?,
             key: k.encrypt_with_key(key)?,
-            password: self.new_password.map(|password| {
-                let password = bitwarden_crypto::pbkdf2(password.as_bytes(), &k, SEND_ITERATIONS);
-                STANDARD.encode(password)
-            }),
+            password: self
+                .new_password
+                .map(|password| Send::hash_password(&password, &k)),
 
             r#type: self.r#type,
             file: self.file.encrypt_with_key(&send_key)?,
@@ -359,6 +366,71 @@ impl TryFrom<SendTextModel> for SendText {
     }
 }
 
+impl From<SendType> for bitwarden_api_api::models::SendType {
+    fn from(t: SendType) -> Self {
+        match t {
+            SendType::Text => bitwarden_api_api::models::SendType::Text,
+            SendType::File => bitwarden_api_api::models::SendType::File,
+        }
+    }
+}
+
+impl From<SendFile> for SendFileModel {
+    fn from(file: SendFile) -> Self {
+        Self {
+            id: file.id,
+            file_name: Some(file.file_name.to_string()),
+            size: file.size.and_then(|s| s.parse().ok()),
+            size_name: file.size_name,
+        }
+    }
+}
+
+impl From<SendText> for SendTextModel {
+    fn from(text: SendText) -> Self {
+        Self {
+            text: text.text.map(|t| t.to_string()),
+            hidden: Some(text.hidden),
+        }
+    }
+}
+
+impl From<Send> for SendRequestModel {
+    /// The password is only included when a new one was set, the server keeps the existing
+    /// password otherwise
+    fn from(send: Send) -> Self {
+        Self {
+            r#type: Some(send.r#type.into()),
+            file_length: None,
+            name: Some(send.name.to_string()),
+            notes: send.notes.map(|n| n.to_string()),
+            key: send.key.to_string(),
+            max_access_count: send.max_access_count.map(|c| c as i32),
+            expiration_date: send.expiration_date.map(|d| d.to_rfc3339()),
+            deletion_date: send.deletion_date.to_rfc3339(),
+            file: send.file.map(|f| Box::new(f.into())),
+            text: send.text.map(|t| Box::new(t.into())),
+            password: send.password,
+            disabled: send.disabled,
+            hide_email: Some(send.hide_email),
+        }
+    }
+}
+
+impl SendView {
+    /// The link to share with the recipients of the Send. The key is part of the URL fragment, so
+    /// it's never sent to the server when the link is opened.
+    pub fn share_url(&self, web_vault_url: &str) -> Option<String> {
+        let access_id = self.access_id.as_deref()?;
+        let key = self.key.as_deref()?;
+
+        Some(format!(
+            "{}/#/send/{access_id}/{key}",
+            web_vault_url.trim_end_matches('/')
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -603,4 +675,73 @@ mod tests {
         assert_eq!(v.new_password, None);
         assert!(v.has_password);
     }
+
+    fn text_view() -> SendView {
+        SendView {
+            id: None,
+            access_id: None,
+            name: "Test".to_string(),
+            notes: None,
+            key: None,
+            new_password: Some("abc123".to_owned()),
+            has_password: false,
+            r#type: SendType::Text,
+            file: None,
+            text: Some(SendTextView {
+                text: Some("This is a test".to_owned()),
+                hidden: true,
+            }),
+            max_access_count: Some(1),
+            access_count: 0,
+            disabled: false,
+            hide_email: true,
+            revision_date: "2024-01-07T23:56:48.207363Z".parse().unwrap(),
+            deletion_date: "2024-01-14T23:56:48Z".parse().unwrap(),
+            expiration_date: None,
+        }
+    }
+
+    #[test]
+    fn test_send_request_model() {
+        let enc = build_encryption_settings();
+        let key = enc.get_key(&None).unwrap();
+
+        let send: Send = text_view().encrypt_with_key(key).unwrap();
+        let name = send.name.to_string();
+        let password = send.password.clone();
+
+        let request: SendRequestModel = send.into();
+
+        assert_eq!(
+            request.r#type,
+            Some(bitwarden_api_api::models::SendType::Text)
+        );
+        assert_eq!(request.name, Some(name));
+        assert_eq!(request.password, password);
+        assert!(request.password.is_some());
+        assert_eq!(request.max_access_count, Some(1));
+        assert_eq!(request.deletion_date, "2024-01-14T23:56:48+00:00");
+        assert_eq!(request.hide_email, Some(true));
+        assert_eq!(request.text.unwrap().hidden, Some(true));
+        assert!(request.file.is_none());
+    }
+
+    #[test]
+    fn test_share_url() {
+        let view = SendView {
+            access_id: Some("ct2APRQtJk-BLLDwAYqhRA".to_owned()),
+            key: Some("Pgui0FK85cNhBGWHAlBHBw".to_owned()),
+            ..text_view()
+        };
+
+        assert_eq!(
+            view.share_url("https://vault.bitwarden.com/").unwrap(),
+            "https://vault.bitwarden.com/#/send/ct2APRQtJk-BLLDwAYqhRA/Pgui0FK85cNhBGWHAlBHBw"
+        );
+
+        // Sends that were never saved can't be shared
+        assert!(text_view()
+            .share_url("https://vault.bitwarden.com")
+            .is_none());
+    }
 }
@@ -0,0 +1,105 @@
+use bitwarden_api_api::models::{
+    GrantedProjectAccessPolicyPermissionDetailsResponseModel, GroupAccessPolicyResponseModel,
+    ServiceAccountAccessPolicyResponseModel, UserAccessPolicyResponseModel,
+};
+use bitwarden_core::{require, Error};
+use bitwarden_crypto::{EncString, KeyDecryptable, SymmetricCryptoKey};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AccessPolicyGranteeType {
+    /// An organization user, identified by their organization user ID
+    User,
+    Group,
+    ServiceAccount,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessPolicyResponse {
+    pub grantee_id: Uuid,
+    pub grantee_type: AccessPolicyGranteeType,
+    pub grantee_name: Option<String>,
+    pub read: bool,
+    pub write: bool,
+}
+
+impl AccessPolicyResponse {
+    pub(crate) fn process_user_response(
+        response: UserAccessPolicyResponseModel,
+    ) -> Result<Self, Error> {
+        Ok(AccessPolicyResponse {
+            grantee_id: require!(response.organization_user_id),
+            grantee_type: AccessPolicyGranteeType::User,
+            grantee_name: response.organization_user_name,
+            read: require!(response.read),
+            write: require!(response.write),
+        })
+    }
+
+    pub(crate) fn process_group_response(
+        response: GroupAccessPolicyResponseModel,
+    ) -> Result<Self, Error> {
+        Ok(AccessPolicyResponse {
+            grantee_id: require!(response.group_id),
+            grantee_type: AccessPolicyGranteeType::Group,
+            grantee_name: response.group_name,
+            read: require!(response.read),
+            write: require!(response.write),
+        })
+    }
+
+    pub(crate) fn process_service_account_response(
+        response: ServiceAccountAccessPolicyResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        Ok(AccessPolicyResponse {
+            grantee_id: require!(response.service_account_id),
+            grantee_type: AccessPolicyGranteeType::ServiceAccount,
+            grantee_name: decrypt_name(response.service_account_name, enc_key)?,
+            read: require!(response.read),
+            write: require!(response.write),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GrantedProjectAccessPolicyResponse {
+    pub project_id: Uuid,
+    pub project_name: Option<String>,
+    pub read: bool,
+    pub write: bool,
+    /// Whether the current user is allowed to manage this policy
+    pub has_permission: bool,
+}
+
+impl GrantedProjectAccessPolicyResponse {
+    pub(crate) fn process_response(
+        response: GrantedProjectAccessPolicyPermissionDetailsResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        let policy = require!(response.access_policy);
+
+        Ok(GrantedProjectAccessPolicyResponse {
+            project_id: require!(policy.granted_project_id),
+            project_name: decrypt_name(policy.granted_project_name, enc_key)?,
+            read: require!(policy.read),
+            write: require!(policy.write),
+            has_permission: response.has_permission.unwrap_or_default(),
+        })
+    }
+}
+
+fn decrypt_name(
+    name: Option<String>,
+    enc_key: &SymmetricCryptoKey,
+) -> Result<Option<String>, Error> {
+    name.map(|name| -> Result<String, Error> {
+        Ok(name.parse::<EncString>()?.decrypt_with_key(enc_key)?)
+    })
+    .transpose()
+}
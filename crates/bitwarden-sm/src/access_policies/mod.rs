@@ -0,0 +1,22 @@
+mod access_policy_response;
+mod project;
+mod service_account;
+
+pub use access_policy_response::{
+    AccessPolicyGranteeType, AccessPolicyResponse, GrantedProjectAccessPolicyResponse,
+};
+pub(crate) use project::{
+    get_project_access_policies, grant_project_access, revoke_project_access,
+    update_project_access_policies,
+};
+pub use project::{
+    AccessPolicyRequest, ProjectAccessGrantRequest, ProjectAccessPoliciesGetRequest,
+    ProjectAccessPoliciesPutRequest, ProjectAccessPoliciesResponse, ProjectAccessRevokeRequest,
+};
+pub(crate) use service_account::{
+    get_service_account_granted_policies, update_service_account_granted_policies,
+};
+pub use service_account::{
+    GrantedAccessPolicyRequest, ServiceAccountGrantedPoliciesGetRequest,
+    ServiceAccountGrantedPoliciesPutRequest, ServiceAccountGrantedPoliciesResponse,
+};
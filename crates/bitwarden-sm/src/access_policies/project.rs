@@ -0,0 +1,378 @@
+use bitwarden_api_api::models::{
+    PeopleAccessPoliciesRequestModel, ProjectPeopleAccessPoliciesResponseModel,
+    ProjectServiceAccountsAccessPoliciesRequestModel,
+    ProjectServiceAccountsAccessPoliciesResponseModel,
+};
+use bitwarden_core::{client::Client, Error};
+use bitwarden_crypto::SymmetricCryptoKey;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{AccessPolicyGranteeType, AccessPolicyResponse};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessPolicyRequest {
+    /// ID of the user, group or service account being granted access
+    pub grantee_id: Uuid,
+    pub read: bool,
+    pub write: bool,
+}
+
+impl From<&AccessPolicyRequest> for bitwarden_api_api::models::AccessPolicyRequest {
+    fn from(policy: &AccessPolicyRequest) -> Self {
+        Self {
+            grantee_id: policy.grantee_id,
+            read: policy.read,
+            write: policy.write,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectAccessPoliciesGetRequest {
+    /// Organization of the project
+    pub organization_id: Uuid,
+    /// ID of the project to retrieve the access policies from
+    pub project_id: Uuid,
+}
+
+/// Replaces all the access policies of a project
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectAccessPoliciesPutRequest {
+    /// Organization of the project
+    pub organization_id: Uuid,
+    /// ID of the project to modify
+    pub project_id: Uuid,
+    pub user_access_policies: Vec<AccessPolicyRequest>,
+    pub group_access_policies: Vec<AccessPolicyRequest>,
+    pub service_account_access_policies: Vec<AccessPolicyRequest>,
+}
+
+impl ProjectAccessPoliciesPutRequest {
+    fn from_response(
+        organization_id: Uuid,
+        project_id: Uuid,
+        response: &ProjectAccessPoliciesResponse,
+    ) -> Self {
+        let mut request = ProjectAccessPoliciesPutRequest {
+            organization_id,
+            project_id,
+            user_access_policies: Vec::new(),
+            group_access_policies: Vec::new(),
+            service_account_access_policies: Vec::new(),
+        };
+
+        for policy in &response.data {
+            request
+                .policies_mut(policy.grantee_type)
+                .push(AccessPolicyRequest {
+                    grantee_id: policy.grantee_id,
+                    read: policy.read,
+                    write: policy.write,
+                });
+        }
+
+        request
+    }
+
+    fn policies_mut(
+        &mut self,
+        grantee_type: AccessPolicyGranteeType,
+    ) -> &mut Vec<AccessPolicyRequest> {
+        match grantee_type {
+            AccessPolicyGranteeType::User => &mut self.user_access_policies,
+            AccessPolicyGranteeType::Group => &mut self.group_access_policies,
+            AccessPolicyGranteeType::ServiceAccount => &mut self.service_account_access_policies,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectAccessGrantRequest {
+    /// Organization of the project
+    pub organization_id: Uuid,
+    /// ID of the project to grant access to
+    pub project_id: Uuid,
+    /// ID of the user, group or service account being granted access
+    pub grantee_id: Uuid,
+    pub grantee_type: AccessPolicyGranteeType,
+    pub read: bool,
+    pub write: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectAccessRevokeRequest {
+    /// Organization of the project
+    pub organization_id: Uuid,
+    /// ID of the project to revoke access to
+    pub project_id: Uuid,
+    /// ID of the user, group or service account losing access
+    pub grantee_id: Uuid,
+    pub grantee_type: AccessPolicyGranteeType,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectAccessPoliciesResponse {
+    pub data: Vec<AccessPolicyResponse>,
+}
+
+impl ProjectAccessPoliciesResponse {
+    pub(crate) fn process_response(
+        people: ProjectPeopleAccessPoliciesResponseModel,
+        service_accounts: ProjectServiceAccountsAccessPoliciesResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        let users = people
+            .user_access_policies
+            .unwrap_or_default()
+            .into_iter()
+            .map(AccessPolicyResponse::process_user_response);
+        let groups = people
+            .group_access_policies
+            .unwrap_or_default()
+            .into_iter()
+            .map(AccessPolicyResponse::process_group_response);
+        let service_accounts = service_accounts
+            .service_account_access_policies
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| AccessPolicyResponse::process_service_account_response(r, enc_key));
+
+        Ok(ProjectAccessPoliciesResponse {
+            data: users
+                .chain(groups)
+                .chain(service_accounts)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+pub(crate) async fn get_project_access_policies(
+    client: &Client,
+    input: &ProjectAccessPoliciesGetRequest,
+) -> Result<ProjectAccessPoliciesResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let people =
+        bitwarden_api_api::apis::access_policies_api::projects_id_access_policies_people_get(
+            &config.api,
+            input.project_id,
+        )
+        .await?;
+    let service_accounts =
+        bitwarden_api_api::apis::access_policies_api::projects_id_access_policies_service_accounts_get(
+            &config.api,
+            input.project_id,
+        )
+        .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    ProjectAccessPoliciesResponse::process_response(people, service_accounts, key)
+}
+
+pub(crate) async fn update_project_access_policies(
+    client: &Client,
+    input: &ProjectAccessPoliciesPutRequest,
+) -> Result<ProjectAccessPoliciesResponse, Error> {
+    let people = PeopleAccessPoliciesRequestModel {
+        user_access_policy_requests: Some(
+            input.user_access_policies.iter().map(Into::into).collect(),
+        ),
+        group_access_policy_requests: Some(
+            input.group_access_policies.iter().map(Into::into).collect(),
+        ),
+    };
+    let service_accounts = ProjectServiceAccountsAccessPoliciesRequestModel {
+        service_account_access_policy_requests: Some(
+            input
+                .service_account_access_policies
+                .iter()
+                .map(Into::into)
+                .collect(),
+        ),
+    };
+
+    let config = client.internal.get_api_configurations().await;
+    let people =
+        bitwarden_api_api::apis::access_policies_api::projects_id_access_policies_people_put(
+            &config.api,
+            input.project_id,
+            Some(people),
+        )
+        .await?;
+    let service_accounts =
+        bitwarden_api_api::apis::access_policies_api::projects_id_access_policies_service_accounts_put(
+            &config.api,
+            input.project_id,
+            Some(service_accounts),
+        )
+        .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    ProjectAccessPoliciesResponse::process_response(people, service_accounts, key)
+}
+
+pub(crate) async fn grant_project_access(
+    client: &Client,
+    input: &ProjectAccessGrantRequest,
+) -> Result<ProjectAccessPoliciesResponse, Error> {
+    if !input.read && !input.write {
+        return Err("Either read or write access needs to be granted".into());
+    }
+
+    let mut request = current_policies(client, input.organization_id, input.project_id).await?;
+    apply_grant(&mut request, input);
+
+    update_project_access_policies(client, &request).await
+}
+
+pub(crate) async fn revoke_project_access(
+    client: &Client,
+    input: &ProjectAccessRevokeRequest,
+) -> Result<ProjectAccessPoliciesResponse, Error> {
+    let mut request = current_policies(client, input.organization_id, input.project_id).await?;
+    apply_revoke(&mut request, input)?;
+
+    update_project_access_policies(client, &request).await
+}
+
+async fn current_policies(
+    client: &Client,
+    organization_id: Uuid,
+    project_id: Uuid,
+) -> Result<ProjectAccessPoliciesPutRequest, Error> {
+    let current = get_project_access_policies(
+        client,
+        &ProjectAccessPoliciesGetRequest {
+            organization_id,
+            project_id,
+        },
+    )
+    .await?;
+
+    Ok(ProjectAccessPoliciesPutRequest::from_response(
+        organization_id,
+        project_id,
+        &current,
+    ))
+}
+
+fn apply_grant(request: &mut ProjectAccessPoliciesPutRequest, input: &ProjectAccessGrantRequest) {
+    let policies = request.policies_mut(input.grantee_type);
+    policies.retain(|p| p.grantee_id != input.grantee_id);
+    policies.push(AccessPolicyRequest {
+        grantee_id: input.grantee_id,
+        read: input.read,
+        write: input.write,
+    });
+}
+
+fn apply_revoke(
+    request: &mut ProjectAccessPoliciesPutRequest,
+    input: &ProjectAccessRevokeRequest,
+) -> Result<(), Error> {
+    let policies = request.policies_mut(input.grantee_type);
+    let count = policies.len();
+    policies.retain(|p| p.grantee_id != input.grantee_id);
+
+    if policies.len() == count {
+        return Err(format!("No access policy found for grantee {}", input.grantee_id).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(
+        grantee_type: AccessPolicyGranteeType,
+        read: bool,
+        write: bool,
+    ) -> AccessPolicyResponse {
+        AccessPolicyResponse {
+            grantee_id: Uuid::new_v4(),
+            grantee_type,
+            grantee_name: None,
+            read,
+            write,
+        }
+    }
+
+    #[test]
+    fn test_grant_replaces_existing_policy() {
+        let user = policy(AccessPolicyGranteeType::User, true, false);
+        let service_account = policy(AccessPolicyGranteeType::ServiceAccount, true, false);
+        let mut request = ProjectAccessPoliciesPutRequest::from_response(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &ProjectAccessPoliciesResponse {
+                data: vec![user, service_account],
+            },
+        );
+        let service_account_id = request.service_account_access_policies[0].grantee_id;
+
+        apply_grant(
+            &mut request,
+            &ProjectAccessGrantRequest {
+                organization_id: request.organization_id,
+                project_id: request.project_id,
+                grantee_id: service_account_id,
+                grantee_type: AccessPolicyGranteeType::ServiceAccount,
+                read: true,
+                write: true,
+            },
+        );
+
+        assert_eq!(request.user_access_policies.len(), 1);
+        assert!(request.group_access_policies.is_empty());
+        assert_eq!(
+            request.service_account_access_policies,
+            vec![AccessPolicyRequest {
+                grantee_id: service_account_id,
+                read: true,
+                write: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_revoke_removes_policy() {
+        let group = policy(AccessPolicyGranteeType::Group, true, true);
+        let group_id = group.grantee_id;
+        let mut request = ProjectAccessPoliciesPutRequest::from_response(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &ProjectAccessPoliciesResponse { data: vec![group] },
+        );
+
+        let mut revoke = ProjectAccessRevokeRequest {
+            organization_id: request.organization_id,
+            project_id: request.project_id,
+            grantee_id: group_id,
+            grantee_type: AccessPolicyGranteeType::User,
+        };
+        assert_eq!(
+            apply_revoke(&mut request, &revoke).unwrap_err().to_string(),
+            format!(
+                "Internal error: No access policy found for grantee {}",
+                group_id
+            )
+        );
+
+        revoke.grantee_type = AccessPolicyGranteeType::Group;
+        apply_revoke(&mut request, &revoke).unwrap();
+        assert!(request.group_access_policies.is_empty());
+    }
+}
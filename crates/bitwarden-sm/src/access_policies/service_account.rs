@@ -0,0 +1,113 @@
+use bitwarden_api_api::models::{
+    ServiceAccountGrantedPoliciesPermissionDetailsResponseModel,
+    ServiceAccountGrantedPoliciesRequestModel,
+};
+use bitwarden_core::{client::Client, Error};
+use bitwarden_crypto::SymmetricCryptoKey;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::GrantedProjectAccessPolicyResponse;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GrantedAccessPolicyRequest {
+    /// ID of the project the service account is granted access to
+    pub project_id: Uuid,
+    pub read: bool,
+    pub write: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountGrantedPoliciesGetRequest {
+    /// Organization of the service account
+    pub organization_id: Uuid,
+    /// ID of the service account to retrieve the granted projects from
+    pub service_account_id: Uuid,
+}
+
+/// Replaces all the projects a service account has access to
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountGrantedPoliciesPutRequest {
+    /// Organization of the service account
+    pub organization_id: Uuid,
+    /// ID of the service account to modify
+    pub service_account_id: Uuid,
+    pub project_access_policies: Vec<GrantedAccessPolicyRequest>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountGrantedPoliciesResponse {
+    pub data: Vec<GrantedProjectAccessPolicyResponse>,
+}
+
+impl ServiceAccountGrantedPoliciesResponse {
+    pub(crate) fn process_response(
+        response: ServiceAccountGrantedPoliciesPermissionDetailsResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        Ok(ServiceAccountGrantedPoliciesResponse {
+            data: response
+                .granted_project_policies
+                .unwrap_or_default()
+                .into_iter()
+                .map(|r| GrantedProjectAccessPolicyResponse::process_response(r, enc_key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+pub(crate) async fn get_service_account_granted_policies(
+    client: &Client,
+    input: &ServiceAccountGrantedPoliciesGetRequest,
+) -> Result<ServiceAccountGrantedPoliciesResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res =
+        bitwarden_api_api::apis::access_policies_api::service_accounts_id_granted_policies_get(
+            &config.api,
+            input.service_account_id,
+        )
+        .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    ServiceAccountGrantedPoliciesResponse::process_response(res, key)
+}
+
+pub(crate) async fn update_service_account_granted_policies(
+    client: &Client,
+    input: &ServiceAccountGrantedPoliciesPutRequest,
+) -> Result<ServiceAccountGrantedPoliciesResponse, Error> {
+    let request = ServiceAccountGrantedPoliciesRequestModel {
+        project_granted_policy_requests: Some(
+            input
+                .project_access_policies
+                .iter()
+                .map(|p| bitwarden_api_api::models::GrantedAccessPolicyRequest {
+                    granted_id: p.project_id,
+                    read: p.read,
+                    write: p.write,
+                })
+                .collect(),
+        ),
+    };
+
+    let config = client.internal.get_api_configurations().await;
+    let res =
+        bitwarden_api_api::apis::access_policies_api::service_accounts_id_granted_policies_put(
+            &config.api,
+            input.service_account_id,
+            Some(request),
+        )
+        .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    ServiceAccountGrantedPoliciesResponse::process_response(res, key)
+}
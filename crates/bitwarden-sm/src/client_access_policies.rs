@@ -0,0 +1,74 @@
+use bitwarden_core::{Client, Error};
+
+use crate::access_policies::{
+    get_project_access_policies, get_service_account_granted_policies, grant_project_access,
+    revoke_project_access, update_project_access_policies, update_service_account_granted_policies,
+    ProjectAccessGrantRequest, ProjectAccessPoliciesGetRequest, ProjectAccessPoliciesPutRequest,
+    ProjectAccessPoliciesResponse, ProjectAccessRevokeRequest,
+    ServiceAccountGrantedPoliciesGetRequest, ServiceAccountGrantedPoliciesPutRequest,
+    ServiceAccountGrantedPoliciesResponse,
+};
+
+pub struct ClientAccessPolicies<'a> {
+    pub client: &'a Client,
+}
+
+impl<'a> ClientAccessPolicies<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_project(
+        &self,
+        input: &ProjectAccessPoliciesGetRequest,
+    ) -> Result<ProjectAccessPoliciesResponse, Error> {
+        get_project_access_policies(self.client, input).await
+    }
+
+    pub async fn update_project(
+        &self,
+        input: &ProjectAccessPoliciesPutRequest,
+    ) -> Result<ProjectAccessPoliciesResponse, Error> {
+        update_project_access_policies(self.client, input).await
+    }
+
+    /// Grant a single user, group or service account access to a project, replacing their
+    /// existing access policy if there is one
+    pub async fn grant_project(
+        &self,
+        input: &ProjectAccessGrantRequest,
+    ) -> Result<ProjectAccessPoliciesResponse, Error> {
+        grant_project_access(self.client, input).await
+    }
+
+    pub async fn revoke_project(
+        &self,
+        input: &ProjectAccessRevokeRequest,
+    ) -> Result<ProjectAccessPoliciesResponse, Error> {
+        revoke_project_access(self.client, input).await
+    }
+
+    pub async fn get_service_account_granted(
+        &self,
+        input: &ServiceAccountGrantedPoliciesGetRequest,
+    ) -> Result<ServiceAccountGrantedPoliciesResponse, Error> {
+        get_service_account_granted_policies(self.client, input).await
+    }
+
+    pub async fn update_service_account_granted(
+        &self,
+        input: &ServiceAccountGrantedPoliciesPutRequest,
+    ) -> Result<ServiceAccountGrantedPoliciesResponse, Error> {
+        update_service_account_granted_policies(self.client, input).await
+    }
+}
+
+pub trait ClientAccessPoliciesExt<'a> {
+    fn access_policies(&'a self) -> ClientAccessPolicies<'a>;
+}
+
+impl<'a> ClientAccessPoliciesExt<'a> for Client {
+    fn access_policies(&'a self) -> ClientAccessPolicies<'a> {
+        ClientAccessPolicies::new(self)
+    }
+}
@@ -0,0 +1,43 @@
+use bitwarden_core::{Client, Error};
+
+use crate::events::{
+    list_all_service_account_events, list_service_account_events, EventsResponse,
+    ServiceAccountEventsRequest,
+};
+
+pub struct ClientEvents<'a> {
+    pub client: &'a Client,
+}
+
+impl<'a> ClientEvents<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Retrieve a single page of events of a service account
+    pub async fn list_service_account(
+        &self,
+        input: &ServiceAccountEventsRequest,
+    ) -> Result<EventsResponse, Error> {
+        list_service_account_events(self.client, input).await
+    }
+
+    /// Retrieve all the events of a service account, following the continuation tokens until the
+    /// last page
+    pub async fn list_all_service_account(
+        &self,
+        input: &ServiceAccountEventsRequest,
+    ) -> Result<EventsResponse, Error> {
+        list_all_service_account_events(self.client, input).await
+    }
+}
+
+pub trait ClientEventsExt<'a> {
+    fn events(&'a self) -> ClientEvents<'a>;
+}
+
+impl<'a> ClientEventsExt<'a> for Client {
+    fn events(&'a self) -> ClientEvents<'a> {
+        ClientEvents::new(self)
+    }
+}
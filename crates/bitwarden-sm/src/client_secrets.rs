@@ -1,11 +1,19 @@
-use bitwarden_core::{Client, Error};
-
-use crate::secrets::{
-    create_secret, delete_secrets, get_secret, get_secrets_by_ids, list_secrets,
-    list_secrets_by_project, sync_secrets, update_secret, SecretCreateRequest, SecretGetRequest,
-    SecretIdentifiersByProjectRequest, SecretIdentifiersRequest, SecretIdentifiersResponse,
-    SecretPutRequest, SecretResponse, SecretsDeleteRequest, SecretsDeleteResponse,
-    SecretsGetRequest, SecretsResponse, SecretsSyncRequest, SecretsSyncResponse,
+use bitwarden_core::{secrets_manager::cache::SecretsCacheSettings, Client, Error};
+
+use crate::{
+    porting::{
+        export_secrets_manager, import_secrets_manager, SecretsManagerExport,
+        SecretsManagerExportRequest, SecretsManagerImportRequest,
+    },
+    secrets::{
+        create_secret, delete_secrets, empty_trash, get_secret, get_secrets_by_ids, list_secrets,
+        list_secrets_by_project, list_trash, restore_secrets, sync_secrets, update_secret,
+        SecretCreateRequest, SecretGetRequest, SecretIdentifiersByProjectRequest,
+        SecretIdentifiersRequest, SecretIdentifiersResponse, SecretPutRequest, SecretResponse,
+        SecretsDeleteRequest, SecretsDeleteResponse, SecretsGetRequest, SecretsResponse,
+        SecretsRestoreRequest, SecretsSyncRequest, SecretsSyncResponse, SecretsTrashEmptyRequest,
+        SecretsTrashRequest, SecretsTrashResponse, SecretsWatchRequest, SecretsWatcher,
+    },
 };
 
 pub struct ClientSecrets<'a> {
@@ -17,6 +25,13 @@ impl<'a> ClientSecrets<'a> {
         Self { client }
     }
 
+    /// Enable the encrypted cache of the last successful sync, which `get`, `get_by_ids`, `list`,
+    /// `list_by_project` and `sync` fall back to when the server is unreachable. Enable it before
+    /// logging in, to allow logging in from the state file while offline.
+    pub fn set_cache(&self, settings: Option<SecretsCacheSettings>) {
+        self.client.internal.set_secrets_cache(settings);
+    }
+
     pub async fn get(&self, input: &SecretGetRequest) -> Result<SecretResponse, Error> {
         get_secret(self.client, input).await
     }
@@ -54,9 +69,46 @@ impl<'a> ClientSecrets<'a> {
         delete_secrets(self.client, input).await
     }
 
+    /// List the deleted secrets that can still be restored
+    pub async fn list_trash(
+        &self,
+        input: &SecretsTrashRequest,
+    ) -> Result<SecretsTrashResponse, Error> {
+        list_trash(self.client, input).await
+    }
+
+    pub async fn restore(&self, input: SecretsRestoreRequest) -> Result<(), Error> {
+        restore_secrets(self.client, input).await
+    }
+
+    /// Permanently delete secrets that were previously deleted
+    pub async fn empty_trash(&self, input: SecretsTrashEmptyRequest) -> Result<(), Error> {
+        empty_trash(self.client, input).await
+    }
+
     pub async fn sync(&self, input: &SecretsSyncRequest) -> Result<SecretsSyncResponse, Error> {
         sync_secrets(self.client, input).await
     }
+
+    /// Export all the projects and secrets of an organization, decrypted with the organization key
+    pub async fn export(
+        &self,
+        input: &SecretsManagerExportRequest,
+    ) -> Result<SecretsManagerExport, Error> {
+        export_secrets_manager(self.client, input).await
+    }
+
+    /// Import projects and secrets from a [SecretsManagerExport] into an organization, encrypting
+    /// them with that organization's key
+    pub async fn import(&self, input: &SecretsManagerImportRequest) -> Result<(), Error> {
+        import_secrets_manager(self.client, input).await
+    }
+
+    /// Create a [SecretsWatcher] that uses the sync endpoint to report only the secrets that
+    /// changed between polls
+    pub fn watch(&self, input: &SecretsWatchRequest) -> SecretsWatcher<'a> {
+        SecretsWatcher::new(self.client, input)
+    }
 }
 
 pub trait ClientSecretsExt<'a> {
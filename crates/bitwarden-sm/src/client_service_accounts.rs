@@ -0,0 +1,91 @@
+use bitwarden_core::{Client, Error};
+
+use crate::service_accounts::{
+    access_tokens::{
+        create_access_token, list_access_tokens, revoke_access_tokens, AccessTokenCreateRequest,
+        AccessTokenCreateResponse, AccessTokensListRequest, AccessTokensResponse,
+        AccessTokensRevokeRequest,
+    },
+    create_service_account, delete_service_accounts, get_service_account, list_service_accounts,
+    update_service_account, ServiceAccountCreateRequest, ServiceAccountGetRequest,
+    ServiceAccountPutRequest, ServiceAccountResponse, ServiceAccountsDeleteRequest,
+    ServiceAccountsDeleteResponse, ServiceAccountsListRequest, ServiceAccountsResponse,
+};
+
+pub struct ClientServiceAccounts<'a> {
+    pub client: &'a Client,
+}
+
+impl<'a> ClientServiceAccounts<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get(
+        &self,
+        input: &ServiceAccountGetRequest,
+    ) -> Result<ServiceAccountResponse, Error> {
+        get_service_account(self.client, input).await
+    }
+
+    pub async fn create(
+        &self,
+        input: &ServiceAccountCreateRequest,
+    ) -> Result<ServiceAccountResponse, Error> {
+        create_service_account(self.client, input).await
+    }
+
+    pub async fn list(
+        &self,
+        input: &ServiceAccountsListRequest,
+    ) -> Result<ServiceAccountsResponse, Error> {
+        list_service_accounts(self.client, input).await
+    }
+
+    pub async fn update(
+        &self,
+        input: &ServiceAccountPutRequest,
+    ) -> Result<ServiceAccountResponse, Error> {
+        update_service_account(self.client, input).await
+    }
+
+    pub async fn delete(
+        &self,
+        input: ServiceAccountsDeleteRequest,
+    ) -> Result<ServiceAccountsDeleteResponse, Error> {
+        delete_service_accounts(self.client, input).await
+    }
+
+    pub async fn list_access_tokens(
+        &self,
+        input: &AccessTokensListRequest,
+    ) -> Result<AccessTokensResponse, Error> {
+        list_access_tokens(self.client, input).await
+    }
+
+    /// Create a new access token for a service account. The returned token can't be retrieved
+    /// again later.
+    pub async fn create_access_token(
+        &self,
+        input: &AccessTokenCreateRequest,
+    ) -> Result<AccessTokenCreateResponse, Error> {
+        create_access_token(self.client, input).await
+    }
+
+    pub async fn revoke_access_tokens(
+        &self,
+        input: AccessTokensRevokeRequest,
+    ) -> Result<(), Error> {
+        revoke_access_tokens(self.client, input).await
+    }
+}
+
+pub trait ClientServiceAccountsExt<'a> {
+    fn service_accounts(&'a self) -> ClientServiceAccounts<'a>;
+}
+
+impl<'a> ClientServiceAccountsExt<'a> for Client {
+    fn service_accounts(&'a self) -> ClientServiceAccounts<'a> {
+        ClientServiceAccounts::new(self)
+    }
+}
@@ -0,0 +1,78 @@
+use bitwarden_api_api::models::EventResponseModel;
+use bitwarden_core::{require, Error};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum EventType {
+    SecretRetrieved,
+    /// An event type without a Secrets Manager specific meaning, with its numeric value
+    Other(i64),
+}
+
+impl From<bitwarden_api_api::models::EventType> for EventType {
+    fn from(event_type: bitwarden_api_api::models::EventType) -> Self {
+        match event_type {
+            bitwarden_api_api::models::EventType::Secret_Retrieved => EventType::SecretRetrieved,
+            other => EventType::Other(other as i64),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EventResponse {
+    pub event_type: EventType,
+    pub date: DateTime<Utc>,
+    pub organization_id: Option<Uuid>,
+    pub service_account_id: Option<Uuid>,
+    pub secret_id: Option<Uuid>,
+    pub acting_user_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+}
+
+impl EventResponse {
+    pub(crate) fn process_response(response: EventResponseModel) -> Result<Self, Error> {
+        Ok(EventResponse {
+            event_type: require!(response.r#type).into(),
+            date: require!(response.date).parse()?,
+            organization_id: response.organization_id,
+            service_account_id: response.service_account_id,
+            secret_id: response.secret_id,
+            acting_user_id: response.acting_user_id,
+            ip_address: response.ip_address,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_response() {
+        let secret_id = Uuid::new_v4();
+        let event = EventResponse::process_response(EventResponseModel {
+            r#type: Some(bitwarden_api_api::models::EventType::Secret_Retrieved),
+            date: Some("2024-05-01T12:00:00Z".to_owned()),
+            secret_id: Some(secret_id),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(event.event_type, EventType::SecretRetrieved);
+        assert_eq!(event.secret_id, Some(secret_id));
+
+        let event = EventResponse::process_response(EventResponseModel {
+            r#type: Some(bitwarden_api_api::models::EventType::User_LoggedIn),
+            date: Some("2024-05-01T12:00:00Z".to_owned()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(event.event_type, EventType::Other(1000));
+    }
+}
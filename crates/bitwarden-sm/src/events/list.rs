@@ -0,0 +1,90 @@
+use bitwarden_api_api::models::EventResponseModelListResponseModel;
+use bitwarden_core::{client::Client, Error};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::EventResponse;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountEventsRequest {
+    /// Service account to retrieve the events of
+    pub service_account_id: Uuid,
+    /// Only return events on or after this date. The server defaults to 30 days before `end`
+    pub start: Option<DateTime<Utc>>,
+    /// Only return events on or before this date. The server defaults to now
+    pub end: Option<DateTime<Utc>>,
+    /// Token returned by a previous request, to retrieve the next page of events
+    pub continuation_token: Option<String>,
+}
+
+pub(crate) async fn list_service_account_events(
+    client: &Client,
+    input: &ServiceAccountEventsRequest,
+) -> Result<EventsResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::secrets_manager_events_api::sm_events_service_accounts_service_account_id_get(
+        &config.api,
+        input.service_account_id,
+        input.start.map(|date| date.to_rfc3339()),
+        input.end.map(|date| date.to_rfc3339()),
+        input.continuation_token.as_deref(),
+    )
+    .await?;
+
+    EventsResponse::process_response(res)
+}
+
+pub(crate) async fn list_all_service_account_events(
+    client: &Client,
+    input: &ServiceAccountEventsRequest,
+) -> Result<EventsResponse, Error> {
+    let mut request = ServiceAccountEventsRequest {
+        service_account_id: input.service_account_id,
+        start: input.start,
+        end: input.end,
+        continuation_token: input.continuation_token.clone(),
+    };
+    let mut data = Vec::new();
+
+    loop {
+        let mut page = list_service_account_events(client, &request).await?;
+        data.append(&mut page.data);
+
+        match page.continuation_token {
+            Some(token) => request.continuation_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(EventsResponse {
+        data,
+        continuation_token: None,
+    })
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EventsResponse {
+    pub data: Vec<EventResponse>,
+    /// Set when there are more events, pass it to the next request to retrieve them
+    pub continuation_token: Option<String>,
+}
+
+impl EventsResponse {
+    pub(crate) fn process_response(
+        response: EventResponseModelListResponseModel,
+    ) -> Result<Self, Error> {
+        Ok(EventsResponse {
+            data: response
+                .data
+                .unwrap_or_default()
+                .into_iter()
+                .map(EventResponse::process_response)
+                .collect::<Result<_, _>>()?,
+            continuation_token: response.continuation_token.filter(|t| !t.is_empty()),
+        })
+    }
+}
@@ -0,0 +1,6 @@
+mod event_response;
+mod list;
+
+pub use event_response::{EventResponse, EventType};
+pub(crate) use list::{list_all_service_account_events, list_service_account_events};
+pub use list::{EventsResponse, ServiceAccountEventsRequest};
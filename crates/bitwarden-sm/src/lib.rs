@@ -1,7 +1,17 @@
+pub mod access_policies;
+mod client_access_policies;
+mod client_events;
 mod client_projects;
 mod client_secrets;
+mod client_service_accounts;
+pub mod events;
+pub mod porting;
 pub mod projects;
 pub mod secrets;
+pub mod service_accounts;
 
+pub use client_access_policies::{ClientAccessPolicies, ClientAccessPoliciesExt};
+pub use client_events::{ClientEvents, ClientEventsExt};
 pub use client_projects::{ClientProjects, ClientProjectsExt};
 pub use client_secrets::{ClientSecrets, ClientSecretsExt};
+pub use client_service_accounts::{ClientServiceAccounts, ClientServiceAccountsExt};
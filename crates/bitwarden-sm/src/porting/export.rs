@@ -0,0 +1,124 @@
+use bitwarden_api_api::models::{
+    InnerProjectExportResponseModel, InnerSecretExportResponseModel, SmExportResponseModel,
+};
+use bitwarden_core::{client::Client, require, Error};
+use bitwarden_crypto::{EncString, KeyDecryptable, SymmetricCryptoKey};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Version of the [SecretsManagerExport] document, increased on every incompatible change
+pub const SECRETS_MANAGER_EXPORT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsManagerExportRequest {
+    /// Organization to export all the projects and secrets from
+    pub organization_id: Uuid,
+}
+
+/// Decrypted projects and secrets of an organization, which can be imported into another
+/// organization with [SecretsManagerImportRequest](super::SecretsManagerImportRequest)
+#[derive(Serialize, Deserialize, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsManagerExport {
+    pub version: u32,
+    pub projects: Vec<ProjectExport>,
+    pub secrets: Vec<SecretExport>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectExport {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretExport {
+    pub id: Uuid,
+    pub key: String,
+    pub value: String,
+    pub note: String,
+    /// IDs of the projects in this export the secret belongs to
+    pub project_ids: Vec<Uuid>,
+}
+
+pub(crate) async fn export_secrets_manager(
+    client: &Client,
+    input: &SecretsManagerExportRequest,
+) -> Result<SecretsManagerExport, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::secrets_manager_porting_api::sm_organization_id_export_get(
+        &config.api,
+        input.organization_id,
+    )
+    .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    SecretsManagerExport::process_response(res, key)
+}
+
+impl SecretsManagerExport {
+    pub(crate) fn process_response(
+        response: SmExportResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        Ok(SecretsManagerExport {
+            version: SECRETS_MANAGER_EXPORT_VERSION,
+            projects: response
+                .projects
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| ProjectExport::process_response(p, enc_key))
+                .collect::<Result<_, _>>()?,
+            secrets: response
+                .secrets
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| SecretExport::process_response(s, enc_key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl ProjectExport {
+    fn process_response(
+        response: InnerProjectExportResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        Ok(ProjectExport {
+            id: require!(response.id),
+            name: require!(response.name)
+                .parse::<EncString>()?
+                .decrypt_with_key(enc_key)?,
+        })
+    }
+}
+
+impl SecretExport {
+    fn process_response(
+        response: InnerSecretExportResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        let note = match response.note {
+            Some(note) => note.parse::<EncString>()?.decrypt_with_key(enc_key)?,
+            None => String::new(),
+        };
+
+        Ok(SecretExport {
+            id: require!(response.id),
+            key: require!(response.key)
+                .parse::<EncString>()?
+                .decrypt_with_key(enc_key)?,
+            value: require!(response.value)
+                .parse::<EncString>()?
+                .decrypt_with_key(enc_key)?,
+            note,
+            project_ids: response.project_ids.unwrap_or_default(),
+        })
+    }
+}
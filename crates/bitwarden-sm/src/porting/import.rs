@@ -0,0 +1,147 @@
+use bitwarden_api_api::models::{
+    InnerProjectImportRequestModel, InnerSecretImportRequestModel, SmImportRequestModel,
+};
+use bitwarden_core::{client::Client, Error};
+use bitwarden_crypto::{KeyEncryptable, SymmetricCryptoKey};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{SecretsManagerExport, SECRETS_MANAGER_EXPORT_VERSION};
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsManagerImportRequest {
+    /// Organization to import the projects and secrets into
+    pub organization_id: Uuid,
+    pub data: SecretsManagerExport,
+}
+
+pub(crate) async fn import_secrets_manager(
+    client: &Client,
+    input: &SecretsManagerImportRequest,
+) -> Result<(), Error> {
+    if input.data.version != SECRETS_MANAGER_EXPORT_VERSION {
+        return Err(format!(
+            "Unsupported export version {}, expected version {}",
+            input.data.version, SECRETS_MANAGER_EXPORT_VERSION
+        )
+        .into());
+    }
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    let request = encrypt_import(&input.data, key)?;
+
+    let config = client.internal.get_api_configurations().await;
+    bitwarden_api_api::apis::secrets_manager_porting_api::sm_organization_id_import_post(
+        &config.api,
+        input.organization_id,
+        Some(request),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn encrypt_import(
+    data: &SecretsManagerExport,
+    key: &SymmetricCryptoKey,
+) -> Result<SmImportRequestModel, Error> {
+    let projects = data
+        .projects
+        .iter()
+        .map(|p| {
+            Ok(InnerProjectImportRequestModel {
+                id: p.id,
+                name: p.name.clone().trim().encrypt_with_key(key)?.to_string(),
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    let secrets = data
+        .secrets
+        .iter()
+        .map(|s| {
+            Ok(InnerSecretImportRequestModel {
+                id: s.id,
+                key: s.key.clone().trim().encrypt_with_key(key)?.to_string(),
+                value: s.value.clone().encrypt_with_key(key)?.to_string(),
+                note: s.note.clone().trim().encrypt_with_key(key)?.to_string(),
+                project_ids: s.project_ids.clone(),
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(SmImportRequestModel {
+        projects: Some(projects),
+        secrets: Some(secrets),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitwarden_api_api::models::{
+        InnerProjectExportResponseModel, InnerSecretExportResponseModel, SmExportResponseModel,
+    };
+
+    use super::*;
+    use crate::porting::{ProjectExport, SecretExport};
+
+    #[test]
+    fn test_import_roundtrips_through_export() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let project_id = Uuid::new_v4();
+        let data = SecretsManagerExport {
+            version: SECRETS_MANAGER_EXPORT_VERSION,
+            projects: vec![ProjectExport {
+                id: project_id,
+                name: "Production".to_owned(),
+            }],
+            secrets: vec![SecretExport {
+                id: Uuid::new_v4(),
+                key: "DB_PASSWORD".to_owned(),
+                value: "s3cr3t".to_owned(),
+                note: "rotated monthly".to_owned(),
+                project_ids: vec![project_id],
+            }],
+        };
+
+        let request = encrypt_import(&data, &key).unwrap();
+        let projects = request.projects.unwrap();
+        let secrets = request.secrets.unwrap();
+        assert!(!secrets[0].value.contains("s3cr3t"));
+
+        // The server returns the same encrypted values on export
+        let response = SmExportResponseModel {
+            object: None,
+            projects: Some(
+                projects
+                    .into_iter()
+                    .map(|p| InnerProjectExportResponseModel {
+                        id: Some(p.id),
+                        name: Some(p.name),
+                    })
+                    .collect(),
+            ),
+            secrets: Some(
+                secrets
+                    .into_iter()
+                    .map(|s| InnerSecretExportResponseModel {
+                        id: Some(s.id),
+                        key: Some(s.key),
+                        value: Some(s.value),
+                        note: Some(s.note),
+                        project_ids: Some(s.project_ids),
+                    })
+                    .collect(),
+            ),
+        };
+
+        assert_eq!(
+            SecretsManagerExport::process_response(response, &key).unwrap(),
+            data
+        );
+    }
+}
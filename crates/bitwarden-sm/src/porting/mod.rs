@@ -0,0 +1,10 @@
+mod export;
+mod import;
+
+pub(crate) use export::export_secrets_manager;
+pub use export::{
+    ProjectExport, SecretExport, SecretsManagerExport, SecretsManagerExportRequest,
+    SECRETS_MANAGER_EXPORT_VERSION,
+};
+pub(crate) use import::import_secrets_manager;
+pub use import::SecretsManagerImportRequest;
@@ -0,0 +1,28 @@
+use bitwarden_core::{Client, Error};
+
+use super::SecretResponse;
+
+/// Store the secrets of a successful sync in the encrypted secrets cache, if it's enabled
+pub(crate) fn update(client: &Client, secrets: &[SecretResponse]) {
+    // A failure to write the cache shouldn't fail the request that produced the secrets
+    _ = client.internal.write_secrets_cache(secrets);
+}
+
+/// Fall back to the encrypted secrets cache when the server can't be reached. Any other error,
+/// or a missing cache, returns the original error
+pub(crate) fn fallback(client: &Client, error: Error) -> Result<Vec<SecretResponse>, Error> {
+    if !is_unreachable(&error) {
+        return Err(error);
+    }
+
+    client.internal.read_secrets_cache().ok_or(error)
+}
+
+fn is_unreachable(error: &Error) -> bool {
+    match error {
+        Error::Reqwest(_) => true,
+        // Bad gateway, service unavailable and gateway timeout
+        Error::ResponseContent { status, .. } => matches!(status.as_u16(), 502..=504),
+        _ => false,
+    }
+}
@@ -3,7 +3,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::SecretResponse;
+use super::{cache, SecretResponse};
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -17,7 +17,20 @@ pub(crate) async fn get_secret(
     input: &SecretGetRequest,
 ) -> Result<SecretResponse, Error> {
     let config = client.internal.get_api_configurations().await;
-    let res = bitwarden_api_api::apis::secrets_api::secrets_id_get(&config.api, input.id).await?;
+    let res =
+        match bitwarden_api_api::apis::secrets_api::secrets_id_get(&config.api, input.id).await {
+            Ok(res) => res,
+            Err(e) => {
+                let error = e.into();
+                return match cache::fallback(client, error) {
+                    Ok(secrets) => secrets
+                        .into_iter()
+                        .find(|s| s.id == input.id)
+                        .ok_or_else(|| "The secret was not found in the secrets cache".into()),
+                    Err(error) => Err(error),
+                };
+            }
+        };
 
     let enc = client.internal.get_encryption_settings()?;
 
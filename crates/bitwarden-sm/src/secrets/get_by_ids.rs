@@ -4,7 +4,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::SecretsResponse;
+use super::{cache, SecretsResponse};
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -17,12 +17,32 @@ pub(crate) async fn get_secrets_by_ids(
     client: &Client,
     input: SecretsGetRequest,
 ) -> Result<SecretsResponse, Error> {
-    let request = Some(GetSecretsRequestModel { ids: input.ids });
+    let request = Some(GetSecretsRequestModel {
+        ids: input.ids.clone(),
+    });
 
     let config = client.internal.get_api_configurations().await;
 
     let res =
-        bitwarden_api_api::apis::secrets_api::secrets_get_by_ids_post(&config.api, request).await?;
+        match bitwarden_api_api::apis::secrets_api::secrets_get_by_ids_post(&config.api, request)
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                let cached = cache::fallback(client, e.into())?;
+                let data = input
+                    .ids
+                    .iter()
+                    .map(|id| {
+                        cached.iter().find(|s| s.id == *id).cloned().ok_or_else(|| {
+                            format!("Secret {} was not found in the secrets cache", id)
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                return Ok(SecretsResponse { data });
+            }
+        };
 
     let enc = client.internal.get_encryption_settings()?;
 
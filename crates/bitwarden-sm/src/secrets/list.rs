@@ -10,6 +10,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{cache, SecretResponse};
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct SecretIdentifiersRequest {
@@ -22,11 +24,20 @@ pub(crate) async fn list_secrets(
     input: &SecretIdentifiersRequest,
 ) -> Result<SecretIdentifiersResponse, Error> {
     let config = client.internal.get_api_configurations().await;
-    let res = bitwarden_api_api::apis::secrets_api::organizations_organization_id_secrets_get(
+    let res = match bitwarden_api_api::apis::secrets_api::organizations_organization_id_secrets_get(
         &config.api,
         input.organization_id,
     )
-    .await?;
+    .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            let cached = cache::fallback(client, e.into())?;
+            return Ok(SecretIdentifiersResponse::from_cache(cached, |s| {
+                s.organization_id == input.organization_id
+            }));
+        }
+    };
 
     let enc = client.internal.get_encryption_settings()?;
 
@@ -45,11 +56,20 @@ pub(crate) async fn list_secrets_by_project(
     input: &SecretIdentifiersByProjectRequest,
 ) -> Result<SecretIdentifiersResponse, Error> {
     let config = client.internal.get_api_configurations().await;
-    let res = bitwarden_api_api::apis::secrets_api::projects_project_id_secrets_get(
+    let res = match bitwarden_api_api::apis::secrets_api::projects_project_id_secrets_get(
         &config.api,
         input.project_id,
     )
-    .await?;
+    .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            let cached = cache::fallback(client, e.into())?;
+            return Ok(SecretIdentifiersResponse::from_cache(cached, |s| {
+                s.project_id == Some(input.project_id)
+            }));
+        }
+    };
 
     let enc = client.internal.get_encryption_settings()?;
 
@@ -76,6 +96,23 @@ impl SecretIdentifiersResponse {
                 .collect::<Result<_, _>>()?,
         })
     }
+
+    fn from_cache(
+        secrets: Vec<SecretResponse>,
+        filter: impl Fn(&SecretResponse) -> bool,
+    ) -> SecretIdentifiersResponse {
+        SecretIdentifiersResponse {
+            data: secrets
+                .into_iter()
+                .filter(filter)
+                .map(|s| SecretIdentifierResponse {
+                    id: s.id,
+                    organization_id: s.organization_id,
+                    key: s.key,
+                })
+                .collect(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -1,3 +1,4 @@
+mod cache;
 mod create;
 mod delete;
 mod get;
@@ -5,8 +6,11 @@ mod get_by_ids;
 mod list;
 mod secret_response;
 mod sync;
+mod trash;
 mod update;
+mod watch;
 
+pub use bitwarden_core::secrets_manager::cache::SecretsCacheSettings;
 pub(crate) use create::create_secret;
 pub use create::SecretCreateRequest;
 pub(crate) use delete::delete_secrets;
@@ -22,5 +26,11 @@ pub use list::{
 pub use secret_response::{SecretResponse, SecretsResponse};
 pub(crate) use sync::sync_secrets;
 pub use sync::{SecretsSyncRequest, SecretsSyncResponse};
+pub(crate) use trash::{empty_trash, list_trash, restore_secrets};
+pub use trash::{
+    SecretsRestoreRequest, SecretsTrashEmptyRequest, SecretsTrashRequest, SecretsTrashResponse,
+    TrashedSecretResponse,
+};
 pub(crate) use update::update_secret;
 pub use update::SecretPutRequest;
+pub use watch::{SecretsChangesResponse, SecretsWatchRequest, SecretsWatcher};
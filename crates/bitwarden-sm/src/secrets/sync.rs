@@ -5,7 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::SecretResponse;
+use super::{cache, SecretResponse};
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -23,16 +23,38 @@ pub(crate) async fn sync_secrets(
     let config = client.internal.get_api_configurations().await;
     let last_synced_date = input.last_synced_date.map(|date| date.to_rfc3339());
 
-    let res = bitwarden_api_api::apis::secrets_api::organizations_organization_id_secrets_sync_get(
-        &config.api,
-        input.organization_id,
-        last_synced_date,
-    )
-    .await?;
+    let res =
+        match bitwarden_api_api::apis::secrets_api::organizations_organization_id_secrets_sync_get(
+            &config.api,
+            input.organization_id,
+            last_synced_date,
+        )
+        .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                let cached = cache::fallback(client, e.into())?;
+                return Ok(SecretsSyncResponse {
+                    has_changes: true,
+                    secrets: Some(
+                        cached
+                            .into_iter()
+                            .filter(|s| s.organization_id == input.organization_id)
+                            .collect(),
+                    ),
+                });
+            }
+        };
 
     let enc = client.internal.get_encryption_settings()?;
 
-    SecretsSyncResponse::process_response(res, &enc)
+    let response = SecretsSyncResponse::process_response(res, &enc)?;
+
+    if let Some(secrets) = &response.secrets {
+        cache::update(client, secrets);
+    }
+
+    Ok(response)
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
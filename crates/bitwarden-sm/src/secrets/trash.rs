@@ -0,0 +1,219 @@
+use bitwarden_api_api::models::{
+    SecretWithProjectsListResponseModel, SecretsWithProjectsInnerSecret,
+};
+use bitwarden_core::{
+    client::{encryption_settings::EncryptionSettings, Client},
+    require, Error,
+};
+use bitwarden_crypto::{EncString, KeyDecryptable};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsTrashRequest {
+    /// Organization to retrieve the deleted secrets from
+    pub organization_id: Uuid,
+}
+
+pub(crate) async fn list_trash(
+    client: &Client,
+    input: &SecretsTrashRequest,
+) -> Result<SecretsTrashResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::trash_api::secrets_organization_id_trash_get(
+        &config.api,
+        input.organization_id,
+    )
+    .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+
+    SecretsTrashResponse::process_response(res, &enc)
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsRestoreRequest {
+    /// Organization the deleted secrets belong to
+    pub organization_id: Uuid,
+    /// IDs of the deleted secrets to restore
+    pub ids: Vec<Uuid>,
+}
+
+pub(crate) async fn restore_secrets(
+    client: &Client,
+    input: SecretsRestoreRequest,
+) -> Result<(), Error> {
+    let config = client.internal.get_api_configurations().await;
+    bitwarden_api_api::apis::trash_api::secrets_organization_id_trash_restore_post(
+        &config.api,
+        input.organization_id,
+        Some(input.ids),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsTrashEmptyRequest {
+    /// Organization the deleted secrets belong to
+    pub organization_id: Uuid,
+    /// IDs of the deleted secrets to permanently delete
+    pub ids: Vec<Uuid>,
+}
+
+pub(crate) async fn empty_trash(
+    client: &Client,
+    input: SecretsTrashEmptyRequest,
+) -> Result<(), Error> {
+    let config = client.internal.get_api_configurations().await;
+    bitwarden_api_api::apis::trash_api::secrets_organization_id_trash_empty_post(
+        &config.api,
+        input.organization_id,
+        Some(input.ids),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsTrashResponse {
+    pub data: Vec<TrashedSecretResponse>,
+}
+
+impl SecretsTrashResponse {
+    pub(crate) fn process_response(
+        response: SecretWithProjectsListResponseModel,
+        enc: &EncryptionSettings,
+    ) -> Result<SecretsTrashResponse, Error> {
+        Ok(SecretsTrashResponse {
+            data: response
+                .secrets
+                .unwrap_or_default()
+                .into_iter()
+                .map(|r| TrashedSecretResponse::process_response(r, enc))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TrashedSecretResponse {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub project_id: Option<Uuid>,
+
+    pub key: String,
+
+    pub creation_date: DateTime<Utc>,
+    /// Date of the last change, which is when the secret was deleted
+    pub revision_date: DateTime<Utc>,
+}
+
+impl TrashedSecretResponse {
+    pub(crate) fn process_response(
+        response: SecretsWithProjectsInnerSecret,
+        enc: &EncryptionSettings,
+    ) -> Result<TrashedSecretResponse, Error> {
+        let organization_id = require!(response.organization_id);
+        let enc_key = enc.get_key(&Some(organization_id))?;
+
+        let key = require!(response.key)
+            .parse::<EncString>()?
+            .decrypt_with_key(enc_key)?;
+
+        Ok(TrashedSecretResponse {
+            id: require!(response.id),
+            organization_id,
+            project_id: response
+                .projects
+                .and_then(|p| p.into_iter().next())
+                .and_then(|p| p.id),
+            key,
+            creation_date: require!(response.creation_date).parse()?,
+            revision_date: require!(response.revision_date).parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitwarden_core::ClientSettings;
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    async fn start_mock(
+        endpoint: &str,
+        organization_id: Uuid,
+        ids: &[Uuid],
+    ) -> (MockServer, Client) {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/api/secrets/{organization_id}/trash/{endpoint}"
+            )))
+            .and(body_json(ids))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new(Some(ClientSettings {
+            identity_url: format!("{}/identity", server.uri()),
+            api_url: format!("{}/api", server.uri()),
+            ..Default::default()
+        }));
+
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_restore_secrets_sends_the_selected_ids() {
+        let organization_id = Uuid::new_v4();
+        let ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let (server, client) = start_mock("restore", organization_id, &ids).await;
+
+        restore_secrets(
+            &client,
+            SecretsRestoreRequest {
+                organization_id,
+                ids,
+            },
+        )
+        .await
+        .unwrap();
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_empty_trash_sends_the_selected_ids() {
+        let organization_id = Uuid::new_v4();
+        let ids = vec![Uuid::new_v4()];
+        let (server, client) = start_mock("empty", organization_id, &ids).await;
+
+        empty_trash(
+            &client,
+            SecretsTrashEmptyRequest {
+                organization_id,
+                ids,
+            },
+        )
+        .await
+        .unwrap();
+
+        server.verify().await;
+    }
+}
@@ -0,0 +1,232 @@
+use std::{collections::HashMap, time::Duration};
+
+use bitwarden_core::{Client, Error};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{sync_secrets, SecretResponse, SecretsSyncRequest};
+
+const DEFAULT_WATCH_INTERVAL_SECONDS: u64 = 30;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsWatchRequest {
+    /// Organization to watch secrets in
+    pub organization_id: Uuid,
+    /// Optional project to limit the watched secrets to
+    pub project_id: Option<Uuid>,
+    /// Seconds to wait between two polls, defaults to 30 seconds
+    pub interval_seconds: Option<u64>,
+}
+
+/// Keeps track of the secrets seen by previous polls of the sync endpoint, so that only the
+/// differences are reported back to the caller.
+///
+/// The SDK doesn't depend on a specific async runtime, so the caller is responsible for waiting
+/// [SecretsWatcher::interval] between calls to [SecretsWatcher::poll].
+pub struct SecretsWatcher<'a> {
+    client: &'a Client,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+    interval: Duration,
+    last_synced_date: Option<DateTime<Utc>>,
+    secrets: HashMap<Uuid, SecretResponse>,
+}
+
+impl<'a> SecretsWatcher<'a> {
+    pub(crate) fn new(client: &'a Client, input: &SecretsWatchRequest) -> Self {
+        Self {
+            client,
+            organization_id: input.organization_id,
+            project_id: input.project_id,
+            interval: Duration::from_secs(
+                input
+                    .interval_seconds
+                    .unwrap_or(DEFAULT_WATCH_INTERVAL_SECONDS),
+            ),
+            last_synced_date: None,
+            secrets: HashMap::new(),
+        }
+    }
+
+    /// Time to wait between two polls
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Query the sync endpoint once and return the secrets that were added, changed or removed
+    /// since the previous poll. The first poll reports every accessible secret as added.
+    pub async fn poll(&mut self) -> Result<SecretsChangesResponse, Error> {
+        // Take the timestamp before the request, so that changes made while the request is in
+        // flight are picked up by the next poll
+        let synced_date = Utc::now();
+
+        let res = sync_secrets(
+            self.client,
+            &SecretsSyncRequest {
+                organization_id: self.organization_id,
+                last_synced_date: self.last_synced_date,
+            },
+        )
+        .await?;
+
+        self.last_synced_date = Some(synced_date);
+
+        Ok(match res.secrets {
+            Some(secrets) if res.has_changes => self.apply(secrets),
+            _ => SecretsChangesResponse::default(),
+        })
+    }
+
+    fn apply(&mut self, secrets: Vec<SecretResponse>) -> SecretsChangesResponse {
+        let mut previous = std::mem::take(&mut self.secrets);
+        let mut changes = SecretsChangesResponse::default();
+
+        for secret in secrets {
+            if self.project_id.is_some() && secret.project_id != self.project_id {
+                continue;
+            }
+
+            match previous.remove(&secret.id) {
+                Some(old) if old == secret => {}
+                Some(_) => changes.changed.push(secret.clone()),
+                None => changes.added.push(secret.clone()),
+            }
+
+            self.secrets.insert(secret.id, secret);
+        }
+
+        changes.removed = previous.into_values().collect();
+
+        for list in [
+            &mut changes.added,
+            &mut changes.changed,
+            &mut changes.removed,
+        ] {
+            list.sort_by(|a, b| a.key.cmp(&b.key).then(a.id.cmp(&b.id)));
+        }
+
+        changes
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SecretsChangesResponse {
+    pub added: Vec<SecretResponse>,
+    pub changed: Vec<SecretResponse>,
+    /// Last known state of the secrets that are no longer accessible
+    pub removed: Vec<SecretResponse>,
+}
+
+impl SecretsChangesResponse {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(id: Uuid, project_id: Option<Uuid>, key: &str, value: &str) -> SecretResponse {
+        let date = "2024-01-01T00:00:00Z".parse().unwrap();
+        SecretResponse {
+            id,
+            organization_id: Uuid::nil(),
+            project_id,
+            key: key.to_owned(),
+            value: value.to_owned(),
+            note: String::new(),
+            creation_date: date,
+            revision_date: date,
+        }
+    }
+
+    fn watcher(client: &Client, project_id: Option<Uuid>) -> SecretsWatcher<'_> {
+        SecretsWatcher::new(
+            client,
+            &SecretsWatchRequest {
+                organization_id: Uuid::nil(),
+                project_id,
+                interval_seconds: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_first_poll_reports_everything_as_added() {
+        let client = Client::new(None);
+        let mut watcher = watcher(&client, None);
+
+        let changes = watcher.apply(vec![
+            secret(Uuid::new_v4(), None, "B", "2"),
+            secret(Uuid::new_v4(), None, "A", "1"),
+        ]);
+
+        assert_eq!(
+            changes
+                .added
+                .iter()
+                .map(|s| s.key.as_str())
+                .collect::<Vec<_>>(),
+            ["A", "B"]
+        );
+        assert!(changes.changed.is_empty());
+        assert!(changes.removed.is_empty());
+        assert_eq!(watcher.interval(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_detects_added_changed_and_removed_secrets() {
+        let client = Client::new(None);
+        let mut watcher = watcher(&client, None);
+
+        let (kept, changed, removed) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        watcher.apply(vec![
+            secret(kept, None, "KEPT", "1"),
+            secret(changed, None, "CHANGED", "1"),
+            secret(removed, None, "REMOVED", "1"),
+        ]);
+
+        let added = Uuid::new_v4();
+        let changes = watcher.apply(vec![
+            secret(kept, None, "KEPT", "1"),
+            secret(changed, None, "CHANGED", "2"),
+            secret(added, None, "ADDED", "1"),
+        ]);
+
+        assert_eq!(changes.added.len(), 1);
+        assert_eq!(changes.added[0].id, added);
+        assert_eq!(changes.changed.len(), 1);
+        assert_eq!(changes.changed[0].value, "2");
+        assert_eq!(changes.removed.len(), 1);
+        assert_eq!(changes.removed[0].id, removed);
+
+        assert!(watcher
+            .apply(vec![
+                secret(kept, None, "KEPT", "1"),
+                secret(changed, None, "CHANGED", "2"),
+                secret(added, None, "ADDED", "1"),
+            ])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_ignores_secrets_outside_of_project() {
+        let client = Client::new(None);
+        let project_id = Uuid::new_v4();
+        let mut watcher = watcher(&client, Some(project_id));
+
+        let changes = watcher.apply(vec![
+            secret(Uuid::new_v4(), Some(project_id), "IN", "1"),
+            secret(Uuid::new_v4(), Some(Uuid::new_v4()), "OUT", "1"),
+            secret(Uuid::new_v4(), None, "UNASSIGNED", "1"),
+        ]);
+
+        assert_eq!(changes.added.len(), 1);
+        assert_eq!(changes.added[0].key, "IN");
+    }
+}
@@ -0,0 +1,38 @@
+use bitwarden_api_api::models::AccessTokenResponseModel;
+use bitwarden_core::{require, Error};
+use bitwarden_crypto::{EncString, KeyDecryptable, SymmetricCryptoKey};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub creation_date: DateTime<Utc>,
+    pub revision_date: DateTime<Utc>,
+}
+
+impl AccessTokenResponse {
+    pub(crate) fn process_response(
+        response: AccessTokenResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        let name = require!(response.name)
+            .parse::<EncString>()?
+            .decrypt_with_key(enc_key)?;
+
+        Ok(AccessTokenResponse {
+            id: require!(response.id),
+            name,
+            scopes: response.scopes.unwrap_or_default(),
+            expire_at: response.expire_at.map(|d| d.parse()).transpose()?,
+            creation_date: require!(response.creation_date).parse()?,
+            revision_date: require!(response.revision_date).parse()?,
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bitwarden_api_api::models::AccessTokenCreateRequestModel;
+use bitwarden_core::{require, validate_only_whitespaces, Client, Error};
+use bitwarden_crypto::{
+    derive_shareable_key, generate_random_bytes, EncString, KeyDecryptable, KeyEncryptable,
+    SymmetricCryptoKey,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+use zeroize::Zeroizing;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessTokenCreateRequest {
+    /// Organization of the service account
+    pub organization_id: Uuid,
+    /// Service account the access token will authenticate as
+    pub service_account_id: Uuid,
+    #[validate(length(min = 1, max = 200), custom(function = validate_only_whitespaces))]
+    pub name: String,
+    /// Date after which the access token can no longer be used, never expires when not set
+    pub expire_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessTokenCreateResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// The access token in the format used to log in. This is the only time it's available, the
+    /// server only stores what is needed to validate it.
+    pub access_token: String,
+    pub expire_at: Option<DateTime<Utc>>,
+    pub creation_date: DateTime<Utc>,
+}
+
+/// Payload stored by the server and decrypted with the access token on login, see
+/// `bitwarden_core::auth::login::access_token`
+#[derive(Serialize)]
+struct AccessTokenPayload {
+    #[serde(rename = "encryptionKey")]
+    encryption_key: String,
+}
+
+pub(crate) async fn create_access_token(
+    client: &Client,
+    input: &AccessTokenCreateRequest,
+) -> Result<AccessTokenCreateResponse, Error> {
+    input.validate()?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    let secret = generate_random_bytes::<[u8; 16]>();
+    let request = make_request(input, key, secret.clone())?;
+
+    let config = client.internal.get_api_configurations().await;
+    let res =
+        bitwarden_api_api::apis::service_accounts_api::service_accounts_id_access_tokens_post(
+            &config.api,
+            input.service_account_id,
+            Some(request),
+        )
+        .await?;
+
+    let id = require!(res.id);
+    let client_secret = require!(res.client_secret);
+
+    Ok(AccessTokenCreateResponse {
+        id,
+        name: require!(res.name)
+            .parse::<EncString>()?
+            .decrypt_with_key(key)?,
+        access_token: format_access_token(id, &client_secret, &secret),
+        expire_at: res.expire_at.map(|d| d.parse()).transpose()?,
+        creation_date: require!(res.creation_date).parse()?,
+    })
+}
+
+fn make_request(
+    input: &AccessTokenCreateRequest,
+    organization_key: &SymmetricCryptoKey,
+    secret: Zeroizing<[u8; 16]>,
+) -> Result<AccessTokenCreateRequestModel, Error> {
+    let encryption_key = derive_shareable_key(secret, "accesstoken", Some("sm-access-token"));
+
+    let payload = serde_json::to_string(&AccessTokenPayload {
+        encryption_key: organization_key.to_base64(),
+    })?;
+
+    Ok(AccessTokenCreateRequestModel {
+        name: input
+            .name
+            .clone()
+            .trim()
+            .encrypt_with_key(organization_key)?
+            .to_string(),
+        encrypted_payload: payload.encrypt_with_key(&encryption_key)?.to_string(),
+        key: encryption_key
+            .to_base64()
+            .encrypt_with_key(organization_key)?
+            .to_string(),
+        expire_at: input.expire_at.map(|date| date.to_rfc3339()),
+    })
+}
+
+fn format_access_token(id: Uuid, client_secret: &str, secret: &[u8; 16]) -> String {
+    format!("0.{}.{}:{}", id, client_secret, STANDARD.encode(secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use bitwarden_core::auth::AccessToken;
+
+    use super::*;
+
+    #[test]
+    fn test_minted_access_token_decrypts_payload() {
+        let organization_key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let secret = generate_random_bytes::<[u8; 16]>();
+        let input = AccessTokenCreateRequest {
+            organization_id: Uuid::new_v4(),
+            service_account_id: Uuid::new_v4(),
+            name: " token ".to_owned(),
+            expire_at: None,
+        };
+
+        let request = make_request(&input, &organization_key, secret.clone()).unwrap();
+
+        let id = Uuid::new_v4();
+        let access_token: AccessToken = format_access_token(id, "secret", &secret).parse().unwrap();
+        assert_eq!(access_token.access_token_id, id);
+        assert_eq!(access_token.client_secret, "secret");
+
+        let payload: String = request
+            .encrypted_payload
+            .parse::<EncString>()
+            .unwrap()
+            .decrypt_with_key(&access_token.encryption_key)
+            .unwrap();
+        assert_eq!(
+            payload,
+            format!(r#"{{"encryptionKey":"{}"}}"#, organization_key.to_base64())
+        );
+
+        let name: String = request
+            .name
+            .parse::<EncString>()
+            .unwrap()
+            .decrypt_with_key(&organization_key)
+            .unwrap();
+        assert_eq!(name, "token");
+    }
+
+    #[tokio::test]
+    async fn test_create_access_token_request_name_empty_string() {
+        let input = AccessTokenCreateRequest {
+            organization_id: Uuid::new_v4(),
+            service_account_id: Uuid::new_v4(),
+            name: "".to_owned(),
+            expire_at: None,
+        };
+
+        let response = create_access_token(&Client::new(None), &input).await;
+        assert_eq!(
+            response.err().unwrap().to_string(),
+            "name must not be empty"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use bitwarden_api_api::models::AccessTokenResponseModelListResponseModel;
+use bitwarden_core::{client::Client, Error};
+use bitwarden_crypto::SymmetricCryptoKey;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::AccessTokenResponse;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessTokensListRequest {
+    /// Organization of the service account
+    pub organization_id: Uuid,
+    /// Service account to retrieve the access tokens from
+    pub service_account_id: Uuid,
+}
+
+pub(crate) async fn list_access_tokens(
+    client: &Client,
+    input: &AccessTokensListRequest,
+) -> Result<AccessTokensResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::service_accounts_api::service_accounts_id_access_tokens_get(
+        &config.api,
+        input.service_account_id,
+    )
+    .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    AccessTokensResponse::process_response(res, key)
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessTokensResponse {
+    pub data: Vec<AccessTokenResponse>,
+}
+
+impl AccessTokensResponse {
+    pub(crate) fn process_response(
+        response: AccessTokenResponseModelListResponseModel,
+        enc_key: &SymmetricCryptoKey,
+    ) -> Result<Self, Error> {
+        let data = response.data.unwrap_or_default();
+
+        Ok(AccessTokensResponse {
+            data: data
+                .into_iter()
+                .map(|r| AccessTokenResponse::process_response(r, enc_key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
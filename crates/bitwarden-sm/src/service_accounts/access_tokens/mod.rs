@@ -0,0 +1,12 @@
+mod access_token_response;
+mod create;
+mod list;
+mod revoke;
+
+pub use access_token_response::AccessTokenResponse;
+pub(crate) use create::create_access_token;
+pub use create::{AccessTokenCreateRequest, AccessTokenCreateResponse};
+pub(crate) use list::list_access_tokens;
+pub use list::{AccessTokensListRequest, AccessTokensResponse};
+pub(crate) use revoke::revoke_access_tokens;
+pub use revoke::AccessTokensRevokeRequest;
@@ -0,0 +1,29 @@
+use bitwarden_api_api::models::RevokeAccessTokensRequest;
+use bitwarden_core::{client::Client, Error};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccessTokensRevokeRequest {
+    /// Service account the access tokens belong to
+    pub service_account_id: Uuid,
+    /// IDs of the access tokens to revoke
+    pub ids: Vec<Uuid>,
+}
+
+pub(crate) async fn revoke_access_tokens(
+    client: &Client,
+    input: AccessTokensRevokeRequest,
+) -> Result<(), Error> {
+    let config = client.internal.get_api_configurations().await;
+    bitwarden_api_api::apis::service_accounts_api::service_accounts_id_access_tokens_revoke_post(
+        &config.api,
+        input.service_account_id,
+        Some(RevokeAccessTokensRequest { ids: input.ids }),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use bitwarden_api_api::models::ServiceAccountCreateRequestModel;
+use bitwarden_core::{validate_only_whitespaces, Client, Error};
+use bitwarden_crypto::KeyEncryptable;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::ServiceAccountResponse;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountCreateRequest {
+    /// Organization where the service account will be created
+    pub organization_id: Uuid,
+    #[validate(length(min = 1, max = 500), custom(function = validate_only_whitespaces))]
+    pub name: String,
+}
+
+pub(crate) async fn create_service_account(
+    client: &Client,
+    input: &ServiceAccountCreateRequest,
+) -> Result<ServiceAccountResponse, Error> {
+    input.validate()?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    let service_account = Some(ServiceAccountCreateRequestModel {
+        name: input.name.clone().trim().encrypt_with_key(key)?.to_string(),
+    });
+
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::service_accounts_api::organizations_organization_id_service_accounts_post(
+        &config.api,
+        input.organization_id,
+        service_account,
+    )
+    .await?;
+
+    ServiceAccountResponse::process_response(res, &enc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_service_account(name: String) -> Result<ServiceAccountResponse, Error> {
+        let input = ServiceAccountCreateRequest {
+            organization_id: Uuid::new_v4(),
+            name,
+        };
+
+        super::create_service_account(&Client::new(None), &input).await
+    }
+
+    #[tokio::test]
+    async fn test_create_service_account_request_name_empty_string() {
+        let response = create_service_account("".into()).await;
+        assert!(response.is_err());
+        assert_eq!(
+            response.err().unwrap().to_string(),
+            "name must not be empty"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_service_account_request_name_all_whitespaces() {
+        let response = create_service_account(" \t\n".into()).await;
+        assert!(response.is_err());
+        assert_eq!(
+            response.err().unwrap().to_string(),
+            "name must not contain only whitespaces"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_service_account_request_name_501_character_length() {
+        let response = create_service_account("a".repeat(501)).await;
+        assert!(response.is_err());
+        assert_eq!(
+            response.err().unwrap().to_string(),
+            "name must not exceed 500 characters in length"
+        );
+    }
+}
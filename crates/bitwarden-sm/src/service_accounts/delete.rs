@@ -0,0 +1,67 @@
+use bitwarden_api_api::models::{
+    BulkDeleteResponseModel, BulkDeleteResponseModelListResponseModel,
+};
+use bitwarden_core::{client::Client, require, Error};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountsDeleteRequest {
+    /// IDs of the service accounts to delete
+    pub ids: Vec<Uuid>,
+}
+
+pub(crate) async fn delete_service_accounts(
+    client: &Client,
+    input: ServiceAccountsDeleteRequest,
+) -> Result<ServiceAccountsDeleteResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::service_accounts_api::service_accounts_delete_post(
+        &config.api,
+        Some(input.ids),
+    )
+    .await?;
+
+    ServiceAccountsDeleteResponse::process_response(res)
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountsDeleteResponse {
+    pub data: Vec<ServiceAccountDeleteResponse>,
+}
+
+impl ServiceAccountsDeleteResponse {
+    pub(crate) fn process_response(
+        response: BulkDeleteResponseModelListResponseModel,
+    ) -> Result<ServiceAccountsDeleteResponse, Error> {
+        Ok(ServiceAccountsDeleteResponse {
+            data: response
+                .data
+                .unwrap_or_default()
+                .into_iter()
+                .map(ServiceAccountDeleteResponse::process_response)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountDeleteResponse {
+    pub id: Uuid,
+    pub error: Option<String>,
+}
+
+impl ServiceAccountDeleteResponse {
+    pub(crate) fn process_response(
+        response: BulkDeleteResponseModel,
+    ) -> Result<ServiceAccountDeleteResponse, Error> {
+        Ok(ServiceAccountDeleteResponse {
+            id: require!(response.id),
+            error: response.error,
+        })
+    }
+}
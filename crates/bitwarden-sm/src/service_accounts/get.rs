@@ -0,0 +1,30 @@
+use bitwarden_core::{client::Client, Error};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ServiceAccountResponse;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountGetRequest {
+    /// ID of the service account to retrieve
+    pub id: Uuid,
+}
+
+pub(crate) async fn get_service_account(
+    client: &Client,
+    input: &ServiceAccountGetRequest,
+) -> Result<ServiceAccountResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+
+    let res = bitwarden_api_api::apis::service_accounts_api::service_accounts_id_get(
+        &config.api,
+        input.id,
+    )
+    .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+
+    ServiceAccountResponse::process_response(res, &enc)
+}
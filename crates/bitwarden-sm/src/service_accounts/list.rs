@@ -0,0 +1,56 @@
+use bitwarden_api_api::models::ServiceAccountSecretsDetailsResponseModelListResponseModel;
+use bitwarden_core::{
+    client::{encryption_settings::EncryptionSettings, Client},
+    Error,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ServiceAccountResponse;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountsListRequest {
+    /// Organization to retrieve all the service accounts from
+    pub organization_id: Uuid,
+}
+
+pub(crate) async fn list_service_accounts(
+    client: &Client,
+    input: &ServiceAccountsListRequest,
+) -> Result<ServiceAccountsResponse, Error> {
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::service_accounts_api::organizations_organization_id_service_accounts_get(
+        &config.api,
+        input.organization_id,
+        None,
+    )
+    .await?;
+
+    let enc = client.internal.get_encryption_settings()?;
+
+    ServiceAccountsResponse::process_response(res, &enc)
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountsResponse {
+    pub data: Vec<ServiceAccountResponse>,
+}
+
+impl ServiceAccountsResponse {
+    pub(crate) fn process_response(
+        response: ServiceAccountSecretsDetailsResponseModelListResponseModel,
+        enc: &EncryptionSettings,
+    ) -> Result<Self, Error> {
+        let data = response.data.unwrap_or_default();
+
+        Ok(ServiceAccountsResponse {
+            data: data
+                .into_iter()
+                .map(|r| ServiceAccountResponse::process_details_response(r, enc))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
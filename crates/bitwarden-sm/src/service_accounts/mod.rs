@@ -0,0 +1,19 @@
+pub mod access_tokens;
+mod create;
+mod delete;
+mod get;
+mod list;
+mod service_account_response;
+mod update;
+
+pub(crate) use create::create_service_account;
+pub use create::ServiceAccountCreateRequest;
+pub(crate) use delete::delete_service_accounts;
+pub use delete::{ServiceAccountsDeleteRequest, ServiceAccountsDeleteResponse};
+pub(crate) use get::get_service_account;
+pub use get::ServiceAccountGetRequest;
+pub(crate) use list::list_service_accounts;
+pub use list::{ServiceAccountsListRequest, ServiceAccountsResponse};
+pub use service_account_response::ServiceAccountResponse;
+pub(crate) use update::update_service_account;
+pub use update::ServiceAccountPutRequest;
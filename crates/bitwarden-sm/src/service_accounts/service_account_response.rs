@@ -0,0 +1,59 @@
+use bitwarden_api_api::models::{
+    ServiceAccountResponseModel, ServiceAccountSecretsDetailsResponseModel,
+};
+use bitwarden_core::{client::encryption_settings::EncryptionSettings, require, Error};
+use bitwarden_crypto::{EncString, KeyDecryptable};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountResponse {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub creation_date: DateTime<Utc>,
+    pub revision_date: DateTime<Utc>,
+}
+
+impl ServiceAccountResponse {
+    pub(crate) fn process_response(
+        response: ServiceAccountResponseModel,
+        enc: &EncryptionSettings,
+    ) -> Result<Self, Error> {
+        let organization_id = require!(response.organization_id);
+        let enc_key = enc.get_key(&Some(organization_id))?;
+
+        let name = require!(response.name)
+            .parse::<EncString>()?
+            .decrypt_with_key(enc_key)?;
+
+        Ok(ServiceAccountResponse {
+            id: require!(response.id),
+            organization_id,
+            name,
+
+            creation_date: require!(response.creation_date).parse()?,
+            revision_date: require!(response.revision_date).parse()?,
+        })
+    }
+
+    pub(crate) fn process_details_response(
+        response: ServiceAccountSecretsDetailsResponseModel,
+        enc: &EncryptionSettings,
+    ) -> Result<Self, Error> {
+        Self::process_response(
+            ServiceAccountResponseModel {
+                object: response.object,
+                id: response.id,
+                organization_id: response.organization_id,
+                name: response.name,
+                creation_date: response.creation_date,
+                revision_date: response.revision_date,
+            },
+            enc,
+        )
+    }
+}
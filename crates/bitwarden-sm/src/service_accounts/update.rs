@@ -0,0 +1,44 @@
+use bitwarden_api_api::models::ServiceAccountUpdateRequestModel;
+use bitwarden_core::{validate_only_whitespaces, Client, Error};
+use bitwarden_crypto::KeyEncryptable;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::ServiceAccountResponse;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceAccountPutRequest {
+    /// ID of the service account to modify
+    pub id: Uuid,
+    /// Organization ID of the service account to modify
+    pub organization_id: Uuid,
+    #[validate(length(min = 1, max = 500), custom(function = validate_only_whitespaces))]
+    pub name: String,
+}
+
+pub(crate) async fn update_service_account(
+    client: &Client,
+    input: &ServiceAccountPutRequest,
+) -> Result<ServiceAccountResponse, Error> {
+    input.validate()?;
+
+    let enc = client.internal.get_encryption_settings()?;
+    let key = enc.get_key(&Some(input.organization_id))?;
+
+    let service_account = Some(ServiceAccountUpdateRequestModel {
+        name: input.name.clone().trim().encrypt_with_key(key)?.to_string(),
+    });
+
+    let config = client.internal.get_api_configurations().await;
+    let res = bitwarden_api_api::apis::service_accounts_api::service_accounts_id_put(
+        &config.api,
+        input.id,
+        service_account,
+    )
+    .await?;
+
+    ServiceAccountResponse::process_response(res, &enc)
+}
@@ -1,14 +1,15 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use bitwarden::{
     error::Error,
-    exporters::{ClientExportersExt, ExportFormat},
+    exporters::{ClientExportersExt, ExportFormat, ImportFormat, ImportResult},
     generators::{
         ClientGeneratorExt, PassphraseGeneratorRequest, PasswordGeneratorRequest,
         UsernameGeneratorRequest,
     },
     vault::{Cipher, Collection, Folder},
 };
+use uuid::Uuid;
 
 use crate::{error::Result, Client};
 
@@ -55,7 +56,7 @@ impl ClientGenerators {
 #[derive(uniffi::Object)]
 pub struct ClientExporters(pub(crate) Arc<Client>);
 
-#[uniffi::export]
+#[uniffi::export(async_runtime = "tokio")]
 impl ClientExporters {
     /// **API Draft:** Export user vault
     pub fn export_vault(
@@ -72,9 +73,26 @@ impl ClientExporters {
             .map_err(Error::ExportError)?)
     }
 
+    /// **API Draft:** Export user vault as a zip archive including the decrypted attachments
+    pub async fn export_vault_archive(
+        &self,
+        folders: Vec<Folder>,
+        ciphers: Vec<Cipher>,
+        destination_path: String,
+    ) -> Result<()> {
+        Ok(self
+            .0
+             .0
+            .exporters()
+            .export_vault_archive(folders, ciphers, Path::new(&destination_path))
+            .await
+            .map_err(Error::ExportError)?)
+    }
+
     /// **API Draft:** Export organization vault
     pub fn export_organization_vault(
         &self,
+        organization_id: Uuid,
         collections: Vec<Collection>,
         ciphers: Vec<Cipher>,
         format: ExportFormat,
@@ -83,7 +101,18 @@ impl ClientExporters {
             .0
              .0
             .exporters()
-            .export_organization_vault(collections, ciphers, format)
+            .export_organization_vault(organization_id, collections, ciphers, format)
             .map_err(Error::ExportError)?)
     }
+
+    /// **API Draft:** Import an export into the user vault
+    pub async fn import_vault(&self, data: Vec<u8>, format: ImportFormat) -> Result<ImportResult> {
+        Ok(self
+            .0
+             .0
+            .exporters()
+            .import_vault(data, format)
+            .await
+            .map_err(Error::ImportError)?)
+    }
 }
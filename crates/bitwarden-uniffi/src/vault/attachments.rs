@@ -1,7 +1,8 @@
 use std::{path::Path, sync::Arc};
 
-use bitwarden::vault::{
-    Attachment, AttachmentEncryptResult, AttachmentView, Cipher, ClientVaultExt,
+use bitwarden::{
+    error::Error,
+    vault::{Attachment, AttachmentEncryptResult, AttachmentView, Cipher, ClientVaultExt},
 };
 
 use crate::{Client, Result};
@@ -71,4 +72,33 @@ impl ClientAttachments {
             Path::new(&decrypted_file_path),
         )?)
     }
+
+    /// Encrypt a file and upload it as a new attachment, returning the updated cipher
+    pub async fn upload_attachment(&self, cipher: Cipher, file_path: String) -> Result<Cipher> {
+        Ok(self
+            .0
+             .0
+            .vault()
+            .attachments()
+            .upload_attachment(cipher, Path::new(&file_path))
+            .await
+            .map_err(Error::Attachment)?)
+    }
+
+    /// Download an attachment and write the decrypted contents to the file system
+    pub async fn download_attachment(
+        &self,
+        cipher: Cipher,
+        attachment_id: String,
+        destination_path: String,
+    ) -> Result<()> {
+        Ok(self
+            .0
+             .0
+            .vault()
+            .attachments()
+            .download_attachment(cipher, &attachment_id, Path::new(&destination_path))
+            .await
+            .map_err(Error::Attachment)?)
+    }
 }
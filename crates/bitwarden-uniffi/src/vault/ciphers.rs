@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use bitwarden::{
     error::Error,
-    vault::{Cipher, CipherListView, CipherView, ClientVaultExt},
+    vault::{
+        Cipher, CipherCollectionsRequest, CipherListView, CipherMoveRequest, CipherSearchQuery,
+        CipherView, ClientVaultExt, UriMatchType,
+    },
 };
 use bitwarden_vault::Fido2CredentialView;
 use uuid::Uuid;
@@ -55,4 +58,55 @@ impl ClientCiphers {
             .move_to_organization(cipher, organization_id)
             .map_err(Error::Cipher)?)
     }
+
+    /// Search and filter a decrypted cipher list, best matches first
+    pub fn search(
+        &self,
+        ciphers: Vec<CipherListView>,
+        query: CipherSearchQuery,
+    ) -> Vec<CipherListView> {
+        self.0 .0.vault().ciphers().search(ciphers, &query)
+    }
+
+    /// Find the login ciphers matching a URL, used for autofill
+    pub fn find_matching(
+        &self,
+        ciphers: Vec<CipherView>,
+        url: String,
+        default_match: Option<UriMatchType>,
+        equivalent_domains: Vec<Vec<String>>,
+    ) -> Vec<CipherView> {
+        self.0
+             .0
+            .vault()
+            .ciphers()
+            .find_matching(ciphers, &url, default_match, &equivalent_domains)
+    }
+
+    /// Move ciphers to a folder
+    pub async fn move_to_folder(&self, request: CipherMoveRequest) -> Result<()> {
+        Ok(self
+            .0
+             .0
+            .vault()
+            .ciphers()
+            .move_to_folder(&request)
+            .await
+            .map_err(Error::Cipher)?)
+    }
+
+    /// Replace the collections of an organization cipher
+    pub async fn update_collections(
+        &self,
+        request: CipherCollectionsRequest,
+    ) -> Result<CipherView> {
+        Ok(self
+            .0
+             .0
+            .vault()
+            .ciphers()
+            .update_collections(&request)
+            .await
+            .map_err(Error::Cipher)?)
+    }
 }
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use bitwarden::vault::{ClientVaultExt, Collection, CollectionView};
+use bitwarden::vault::{
+    ClientVaultExt, Collection, CollectionCreateRequest, CollectionDeleteRequest,
+    CollectionUpdateRequest, CollectionView,
+};
 
 use crate::{Client, Result};
 
@@ -9,6 +12,11 @@ pub struct ClientCollections(pub Arc<Client>);
 
 #[uniffi::export]
 impl ClientCollections {
+    /// Encrypt collection
+    pub fn encrypt(&self, collection: CollectionView) -> Result<Collection> {
+        Ok(self.0 .0.vault().collections().encrypt(collection)?)
+    }
+
     /// Decrypt collection
     pub fn decrypt(&self, collection: Collection) -> Result<CollectionView> {
         Ok(self.0 .0.vault().collections().decrypt(collection)?)
@@ -18,4 +26,19 @@ impl ClientCollections {
     pub fn decrypt_list(&self, collections: Vec<Collection>) -> Result<Vec<CollectionView>> {
         Ok(self.0 .0.vault().collections().decrypt_list(collections)?)
     }
+
+    /// Create a collection in an organization
+    pub async fn create(&self, request: CollectionCreateRequest) -> Result<CollectionView> {
+        Ok(self.0 .0.vault().collections().create(&request).await?)
+    }
+
+    /// Update a collection
+    pub async fn update(&self, request: CollectionUpdateRequest) -> Result<CollectionView> {
+        Ok(self.0 .0.vault().collections().update(&request).await?)
+    }
+
+    /// Delete a collection
+    pub async fn delete(&self, request: CollectionDeleteRequest) -> Result<()> {
+        Ok(self.0 .0.vault().collections().delete(&request).await?)
+    }
 }
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use bitwarden::vault::{ClientVaultExt, Folder, FolderView};
+use bitwarden::vault::{
+    ClientVaultExt, Folder, FolderCreateRequest, FolderDeleteRequest, FolderUpdateRequest,
+    FolderView,
+};
 
 use crate::{Client, Result};
 
@@ -23,4 +26,19 @@ impl ClientFolders {
     pub fn decrypt_list(&self, folders: Vec<Folder>) -> Result<Vec<FolderView>> {
         Ok(self.0 .0.vault().folders().decrypt_list(folders)?)
     }
+
+    /// Create a folder
+    pub async fn create(&self, request: FolderCreateRequest) -> Result<FolderView> {
+        Ok(self.0 .0.vault().folders().create(&request).await?)
+    }
+
+    /// Rename a folder
+    pub async fn update(&self, request: FolderUpdateRequest) -> Result<FolderView> {
+        Ok(self.0 .0.vault().folders().update(&request).await?)
+    }
+
+    /// Delete a folder, the ciphers it contains are moved out of it
+    pub async fn delete(&self, request: FolderDeleteRequest) -> Result<()> {
+        Ok(self.0 .0.vault().folders().delete(&request).await?)
+    }
 }
@@ -1,12 +1,35 @@
+use bitwarden_core::MissingFieldError;
 use bitwarden_crypto::{
     CryptoError, EncString, KeyDecryptable, KeyEncryptable, SymmetricCryptoKey,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use super::Cipher;
 use crate::VaultParseError;
 
+#[derive(Debug, Error)]
+pub enum AttachmentError {
+    #[error(transparent)]
+    MissingFieldError(#[from] MissingFieldError),
+    #[error(transparent)]
+    Core(#[from] bitwarden_core::Error),
+    #[error(transparent)]
+    Crypto(#[from] CryptoError),
+    #[error(transparent)]
+    VaultParse(#[from] VaultParseError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("The server didn't provide the details required to upload the attachment")]
+    MissingUploadData,
+    #[error("The encrypted attachment is {actual} bytes but {expected} bytes were expected")]
+    SizeMismatch { expected: u64, actual: u64 },
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -66,18 +89,15 @@ impl<'a> KeyEncryptable<SymmetricCryptoKey, AttachmentEncryptResult> for Attachm
         let ciphers_key = ciphers_key.as_ref().unwrap_or(key);
 
         let mut attachment = self.attachment;
+        let attachment_key = attachment.generate_key(ciphers_key)?;
 
-        // Because this is a new attachment, we have to generate a key for it, encrypt the contents
-        // with it, and then encrypt the key with the cipher key
-        let attachment_key = SymmetricCryptoKey::generate(rand::thread_rng());
-        let encrypted_contents = self.contents.encrypt_with_key(&attachment_key)?;
-        attachment.key = Some(attachment_key.to_vec().encrypt_with_key(ciphers_key)?);
-
-        let contents = encrypted_contents.to_buffer()?;
+        let contents = self
+            .contents
+            .encrypt_with_key(&attachment_key)?
+            .to_buffer()?;
 
         // Once we have the encrypted contents, we can set the size of the attachment
-        attachment.size = Some(contents.len().to_string());
-        attachment.size_name = Some(size_name(contents.len()));
+        attachment.set_size(contents.len());
 
         Ok(AttachmentEncryptResult {
             attachment: attachment.encrypt_with_key(ciphers_key)?,
@@ -86,6 +106,44 @@ impl<'a> KeyEncryptable<SymmetricCryptoKey, AttachmentEncryptResult> for Attachm
     }
 }
 
+impl AttachmentView {
+    /// Because this is a new attachment, we have to generate a key for it, which is stored
+    /// encrypted with the cipher key. Returns the key to encrypt the contents with.
+    pub(crate) fn generate_key(
+        &mut self,
+        ciphers_key: &SymmetricCryptoKey,
+    ) -> Result<SymmetricCryptoKey, CryptoError> {
+        let attachment_key = SymmetricCryptoKey::generate(rand::thread_rng());
+        self.key = Some(attachment_key.to_vec().encrypt_with_key(ciphers_key)?);
+        Ok(attachment_key)
+    }
+
+    /// Set the size of the encrypted contents
+    pub(crate) fn set_size(&mut self, size: usize) {
+        self.size = Some(size.to_string());
+        self.size_name = Some(size_name(size));
+    }
+}
+
+impl Attachment {
+    /// Check the length of the downloaded encrypted contents, catching truncated downloads before
+    /// attempting to decrypt them. The size recorded on upload is the length of the encrypted
+    /// file written by `encrypt_file`, not of the decrypted contents.
+    pub(crate) fn verify_encrypted_size(&self, size: u64) -> Result<(), AttachmentError> {
+        let Some(expected) = self.size.as_deref().and_then(|s| s.parse().ok()) else {
+            return Ok(());
+        };
+
+        if size != expected {
+            return Err(AttachmentError::SizeMismatch {
+                expected,
+                actual: size,
+            });
+        }
+        Ok(())
+    }
+}
+
 fn size_name(size: usize) -> String {
     let units = ["Bytes", "KB", "MB", "GB", "TB"];
     let size = size as f64;
@@ -98,18 +156,28 @@ fn size_name(size: usize) -> String {
 
 impl KeyDecryptable<SymmetricCryptoKey, Vec<u8>> for AttachmentFile {
     fn decrypt_with_key(&self, key: &SymmetricCryptoKey) -> Result<Vec<u8>, CryptoError> {
-        let ciphers_key = Cipher::get_cipher_key(key, &self.cipher.key)?;
+        let content_key = self.attachment.content_key(&self.cipher, key)?;
+        self.contents.decrypt_with_key(&content_key)
+    }
+}
+
+impl Attachment {
+    /// Get the key the attachment contents are encrypted with
+    pub(crate) fn content_key(
+        &self,
+        cipher: &Cipher,
+        key: &SymmetricCryptoKey,
+    ) -> Result<SymmetricCryptoKey, CryptoError> {
+        let ciphers_key = Cipher::get_cipher_key(key, &cipher.key)?;
         let ciphers_key = ciphers_key.as_ref().unwrap_or(key);
 
         // Version 2 or 3, `AttachmentKey` or `CipherKey(AttachmentKey)`
-        if let Some(attachment_key) = &self.attachment.key {
+        if let Some(attachment_key) = &self.key {
             let mut content_key: Vec<u8> = attachment_key.decrypt_with_key(ciphers_key)?;
-            let content_key = SymmetricCryptoKey::try_from(content_key.as_mut_slice())?;
-
-            self.contents.decrypt_with_key(&content_key)
+            SymmetricCryptoKey::try_from(content_key.as_mut_slice())
         } else {
             // Legacy attachment version 1, use user/org key
-            self.contents.decrypt_with_key(key)
+            Ok(key.clone())
         }
     }
 }
@@ -157,6 +225,15 @@ impl TryFrom<bitwarden_api_api::models::AttachmentResponseModel> for Attachment
     }
 }
 
+impl From<Attachment> for bitwarden_api_api::models::CipherAttachmentModel {
+    fn from(attachment: Attachment) -> Self {
+        Self {
+            file_name: attachment.file_name.map(|f| f.to_string()),
+            key: attachment.key.map(|k| k.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use base64::{engine::general_purpose::STANDARD, Engine};
@@ -275,6 +352,17 @@ mod tests {
         let enc_file = STANDARD.decode(b"Ao00qr1xLsV+ZNQpYZ/UwEwOWo3hheKwCYcOGIbsorZ6JIG2vLWfWEXCVqP0hDuzRvmx8otApNZr8pJYLNwCe1aQ+ySHQYGkdubFjoMojulMbQ959Y4SJ6Its/EnVvpbDnxpXTDpbutDxyhxfq1P3lstL2G9rObJRrxiwdGlRGu1h94UA1fCCkIUQux5LcqUee6W4MyQmRnsUziH8gGzmtI=").unwrap();
         let original = STANDARD.decode(b"rMweTemxOL9D0iWWfRxiY3enxiZ5IrwWD6ef2apGO6MvgdGhy2fpwmATmn7BpSj9lRumddLLXm7u8zSp6hnXt1hS71YDNh78LjGKGhGL4sbg8uNnpa/I6GK/83jzqGYN7+ESbg==").unwrap();
 
+        // Files encrypted as a buffer can be decrypted as a stream
+        let content_key = attachment.content_key(&cipher, &user_key).unwrap();
+        let mut streamed = Vec::new();
+        bitwarden_crypto::decrypt_stream(
+            &content_key,
+            std::io::Cursor::new(&enc_file),
+            &mut streamed,
+        )
+        .unwrap();
+        assert_eq!(streamed, original);
+
         let dec = AttachmentFile {
             cipher,
             attachment,
@@ -339,4 +427,32 @@ mod tests {
 
         assert_eq!(dec, original);
     }
+
+    #[test]
+    fn test_verify_encrypted_size() {
+        let attachment = Attachment {
+            id: None,
+            url: None,
+            size: Some("4".into()),
+            size_name: Some("4 Bytes".into()),
+            file_name: None,
+            key: None,
+        };
+
+        assert!(attachment.verify_encrypted_size(4).is_ok());
+        assert!(matches!(
+            attachment.verify_encrypted_size(3),
+            Err(super::AttachmentError::SizeMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+
+        // Attachments without a recorded size can't be verified
+        let attachment = Attachment {
+            size: None,
+            ..attachment
+        };
+        assert!(attachment.verify_encrypted_size(3).is_ok());
+    }
 }
@@ -71,3 +71,16 @@ impl TryFrom<CipherCardModel> for Card {
         })
     }
 }
+
+impl From<Card> for CipherCardModel {
+    fn from(card: Card) -> Self {
+        Self {
+            cardholder_name: card.cardholder_name.map(|e| e.to_string()),
+            brand: card.brand.map(|e| e.to_string()),
+            number: card.number.map(|e| e.to_string()),
+            exp_month: card.exp_month.map(|e| e.to_string()),
+            exp_year: card.exp_year.map(|e| e.to_string()),
+            code: card.code.map(|e| e.to_string()),
+        }
+    }
+}
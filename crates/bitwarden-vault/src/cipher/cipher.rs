@@ -1,4 +1,6 @@
-use bitwarden_api_api::models::CipherDetailsResponseModel;
+use bitwarden_api_api::models::{
+    CipherDetailsResponseModel, CipherRequestModel, CipherResponseModel,
+};
 use bitwarden_core::{require, MissingFieldError, VaultLocked};
 use bitwarden_crypto::{
     CryptoError, EncString, KeyContainer, KeyDecryptable, KeyEncryptable, LocateKey,
@@ -17,7 +19,7 @@ use super::{
     secure_note,
 };
 use crate::{
-    password_history, Fido2CredentialFullView, Fido2CredentialView, Login, LoginView,
+    password_history, Fido2CredentialFullView, Fido2CredentialView, Login, LoginUriView, LoginView,
     VaultParseError,
 };
 
@@ -31,9 +33,13 @@ pub enum CipherError {
     CryptoError(#[from] CryptoError),
     #[error("This cipher contains attachments without keys. Those attachments will need to be reuploaded to complete the operation")]
     AttachmentsWithoutKeys,
+    #[error(transparent)]
+    VaultParse(#[from] VaultParseError),
+    #[error(transparent)]
+    Core(#[from] bitwarden_core::Error),
 }
 
-#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, Debug, JsonSchema)]
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, Debug, JsonSchema, PartialEq)]
 #[repr(u8)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum CipherType {
@@ -126,20 +132,21 @@ pub struct CipherView {
     pub revision_date: DateTime<Utc>,
 }
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum CipherListViewType {
     Login {
         has_fido2: bool,
         totp: Option<EncString>,
+        uris: Option<Vec<LoginUriView>>,
     },
     SecureNote,
     Card,
     Identity,
 }
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct CipherListView {
@@ -169,6 +176,26 @@ pub struct CipherListView {
     pub revision_date: DateTime<Utc>,
 }
 
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CipherMoveRequest {
+    /// IDs of the ciphers to move
+    pub ids: Vec<Uuid>,
+    /// Destination folder, `None` removes the ciphers from their folder
+    pub folder_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CipherCollectionsRequest {
+    /// ID of an organization cipher
+    pub id: Uuid,
+    /// The collections the cipher will belong to, replacing the current ones
+    pub collection_ids: Vec<Uuid>,
+}
+
 impl CipherListView {
     pub(crate) fn get_totp_key(
         self,
@@ -273,7 +300,7 @@ impl Cipher {
     /// Note that some ciphers do not have individual encryption keys,
     /// in which case this will return Ok(None) and the key associated
     /// with this cipher's user or organization must be used instead
-    pub(super) fn get_cipher_key(
+    pub(crate) fn get_cipher_key(
         key: &SymmetricCryptoKey,
         ciphers_key: &Option<EncString>,
     ) -> Result<Option<SymmetricCryptoKey>, CryptoError> {
@@ -548,6 +575,7 @@ impl KeyDecryptable<SymmetricCryptoKey, CipherListView> for Cipher {
                     CipherListViewType::Login {
                         has_fido2: login.fido2_credentials.is_some(),
                         totp: login.totp.clone(),
+                        uris: login.uris.decrypt_with_key(key).ok().flatten(),
                     }
                 }
                 CipherType::SecureNote => CipherListViewType::SecureNote,
@@ -643,6 +671,95 @@ impl TryFrom<CipherDetailsResponseModel> for Cipher {
     }
 }
 
+impl TryFrom<CipherResponseModel> for Cipher {
+    type Error = VaultParseError;
+
+    /// The response doesn't include the collections, `collection_ids` is left empty
+    fn try_from(cipher: CipherResponseModel) -> Result<Self, Self::Error> {
+        CipherDetailsResponseModel {
+            object: cipher.object,
+            id: cipher.id,
+            organization_id: cipher.organization_id,
+            r#type: cipher.r#type,
+            data: cipher.data,
+            name: cipher.name,
+            notes: cipher.notes,
+            login: cipher.login,
+            card: cipher.card,
+            identity: cipher.identity,
+            secure_note: cipher.secure_note,
+            fields: cipher.fields,
+            password_history: cipher.password_history,
+            attachments: cipher.attachments,
+            organization_use_totp: cipher.organization_use_totp,
+            revision_date: cipher.revision_date,
+            creation_date: cipher.creation_date,
+            deleted_date: cipher.deleted_date,
+            reprompt: cipher.reprompt,
+            key: cipher.key,
+            folder_id: cipher.folder_id,
+            favorite: cipher.favorite,
+            edit: cipher.edit,
+            view_password: cipher.view_password,
+            collection_ids: None,
+        }
+        .try_into()
+    }
+}
+
+impl From<Cipher> for CipherRequestModel {
+    fn from(cipher: Cipher) -> Self {
+        Self {
+            r#type: Some(cipher.r#type.into()),
+            organization_id: cipher.organization_id.map(|id| id.to_string()),
+            folder_id: cipher.folder_id.map(|id| id.to_string()),
+            favorite: Some(cipher.favorite),
+            reprompt: Some(cipher.reprompt.into()),
+            key: cipher.key.map(|k| k.to_string()),
+            name: cipher.name.to_string(),
+            notes: cipher.notes.map(|n| n.to_string()),
+            fields: cipher
+                .fields
+                .map(|f| f.into_iter().map(|f| f.into()).collect()),
+            password_history: cipher
+                .password_history
+                .map(|p| p.into_iter().map(|p| p.into()).collect()),
+            attachments: None,
+            attachments2: cipher.attachments.map(|a| {
+                a.into_iter()
+                    .filter_map(|a| Some((a.id.clone()?, a.into())))
+                    .collect()
+            }),
+            login: cipher.login.map(|l| Box::new(l.into())),
+            card: cipher.card.map(|c| Box::new(c.into())),
+            identity: cipher.identity.map(|i| Box::new(i.into())),
+            secure_note: cipher.secure_note.map(|s| Box::new(s.into())),
+            // Lets the server reject updates made to an outdated version of the cipher
+            last_known_revision_date: cipher.id.map(|_| cipher.revision_date.to_rfc3339()),
+        }
+    }
+}
+
+impl From<CipherType> for bitwarden_api_api::models::CipherType {
+    fn from(t: CipherType) -> Self {
+        match t {
+            CipherType::Login => Self::Login,
+            CipherType::SecureNote => Self::SecureNote,
+            CipherType::Card => Self::Card,
+            CipherType::Identity => Self::Identity,
+        }
+    }
+}
+
+impl From<CipherRepromptType> for bitwarden_api_api::models::CipherRepromptType {
+    fn from(t: CipherRepromptType) -> Self {
+        match t {
+            CipherRepromptType::None => Self::None,
+            CipherRepromptType::Password => Self::Password,
+        }
+    }
+}
+
 impl From<bitwarden_api_api::models::CipherType> for CipherType {
     fn from(t: bitwarden_api_api::models::CipherType) -> Self {
         match t {
@@ -781,7 +898,8 @@ mod tests {
                 sub_title: "test_username".to_string(),
                 r#type: CipherListViewType::Login {
                     has_fido2: true,
-                    totp: cipher.login.as_ref().unwrap().totp.clone()
+                    totp: cipher.login.as_ref().unwrap().totp.clone(),
+                    uris: None,
                 },
                 favorite: cipher.favorite,
                 reprompt: cipher.reprompt,
@@ -795,6 +913,51 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_cipher_request_model() {
+        let key = SymmetricCryptoKey::generate(rand::thread_rng());
+        let cipher = generate_cipher().encrypt_with_key(&key).unwrap();
+
+        let request: CipherRequestModel = cipher.clone().into();
+
+        assert_eq!(request.name, cipher.name.to_string());
+        assert_eq!(
+            request.r#type,
+            Some(bitwarden_api_api::models::CipherType::Login)
+        );
+        let login = request.login.unwrap();
+        assert_eq!(
+            login.username,
+            cipher
+                .login
+                .as_ref()
+                .unwrap()
+                .username
+                .as_ref()
+                .map(|u| u.to_string())
+        );
+        assert_eq!(
+            request.last_known_revision_date.as_deref(),
+            Some("2024-01-30T17:55:36.150+00:00")
+        );
+
+        // The request decrypts to the same cipher
+        let mut new_cipher = cipher.clone();
+        new_cipher.id = None;
+        let request: CipherRequestModel = new_cipher.into();
+        assert!(request.last_known_revision_date.is_none());
+        let username: Option<String> = request
+            .login
+            .unwrap()
+            .username
+            .unwrap()
+            .parse::<EncString>()
+            .unwrap()
+            .decrypt_with_key(&key)
+            .unwrap();
+        assert_eq!(username.as_deref(), Some("test_username"));
+    }
+
     #[test]
     fn test_generate_cipher_key() {
         let key = SymmetricCryptoKey::generate(rand::thread_rng());
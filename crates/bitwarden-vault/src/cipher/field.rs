@@ -90,3 +90,25 @@ impl From<bitwarden_api_api::models::FieldType> for FieldType {
         }
     }
 }
+
+impl From<Field> for CipherFieldModel {
+    fn from(field: Field) -> Self {
+        Self {
+            r#type: Some(field.r#type.into()),
+            name: field.name.map(|n| n.to_string()),
+            value: field.value.map(|v| v.to_string()),
+            linked_id: field.linked_id.map(|id| u32::from(id) as i32),
+        }
+    }
+}
+
+impl From<FieldType> for bitwarden_api_api::models::FieldType {
+    fn from(value: FieldType) -> Self {
+        match value {
+            FieldType::Text => Self::Text,
+            FieldType::Hidden => Self::Hidden,
+            FieldType::Boolean => Self::Boolean,
+            FieldType::Linked => Self::Linked,
+        }
+    }
+}
@@ -131,3 +131,28 @@ impl TryFrom<CipherIdentityModel> for Identity {
         })
     }
 }
+
+impl From<Identity> for CipherIdentityModel {
+    fn from(identity: Identity) -> Self {
+        Self {
+            title: identity.title.map(|e| e.to_string()),
+            first_name: identity.first_name.map(|e| e.to_string()),
+            middle_name: identity.middle_name.map(|e| e.to_string()),
+            last_name: identity.last_name.map(|e| e.to_string()),
+            address1: identity.address1.map(|e| e.to_string()),
+            address2: identity.address2.map(|e| e.to_string()),
+            address3: identity.address3.map(|e| e.to_string()),
+            city: identity.city.map(|e| e.to_string()),
+            state: identity.state.map(|e| e.to_string()),
+            postal_code: identity.postal_code.map(|e| e.to_string()),
+            country: identity.country.map(|e| e.to_string()),
+            company: identity.company.map(|e| e.to_string()),
+            email: identity.email.map(|e| e.to_string()),
+            phone: identity.phone.map(|e| e.to_string()),
+            ssn: identity.ssn.map(|e| e.to_string()),
+            username: identity.username.map(|e| e.to_string()),
+            passport_number: identity.passport_number.map(|e| e.to_string()),
+            license_number: identity.license_number.map(|e| e.to_string()),
+        }
+    }
+}
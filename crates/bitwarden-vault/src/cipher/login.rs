@@ -11,7 +11,7 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::VaultParseError;
 
-#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, Debug, JsonSchema)]
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, Debug, JsonSchema, PartialEq)]
 #[repr(u8)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
@@ -33,7 +33,7 @@ pub struct LoginUri {
     pub uri_checksum: Option<EncString>,
 }
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct LoginUriView {
@@ -443,6 +443,68 @@ impl TryFrom<bitwarden_api_api::models::CipherFido2CredentialModel> for Fido2Cre
     }
 }
 
+impl From<Login> for CipherLoginModel {
+    fn from(login: Login) -> Self {
+        Self {
+            uri: None,
+            uris: login
+                .uris
+                .map(|u| u.into_iter().map(|u| u.into()).collect()),
+            username: login.username.map(|u| u.to_string()),
+            password: login.password.map(|p| p.to_string()),
+            password_revision_date: login.password_revision_date.map(|d| d.to_rfc3339()),
+            totp: login.totp.map(|t| t.to_string()),
+            autofill_on_page_load: login.autofill_on_page_load,
+            fido2_credentials: login
+                .fido2_credentials
+                .map(|c| c.into_iter().map(|c| c.into()).collect()),
+        }
+    }
+}
+
+impl From<LoginUri> for CipherLoginUriModel {
+    fn from(uri: LoginUri) -> Self {
+        Self {
+            uri: uri.uri.map(|u| u.to_string()),
+            uri_checksum: uri.uri_checksum.map(|c| c.to_string()),
+            r#match: uri.r#match.map(|m| m.into()),
+        }
+    }
+}
+
+impl From<UriMatchType> for bitwarden_api_api::models::UriMatchType {
+    fn from(value: UriMatchType) -> Self {
+        match value {
+            UriMatchType::Domain => Self::Domain,
+            UriMatchType::Host => Self::Host,
+            UriMatchType::StartsWith => Self::StartsWith,
+            UriMatchType::Exact => Self::Exact,
+            UriMatchType::RegularExpression => Self::RegularExpression,
+            UriMatchType::Never => Self::Never,
+        }
+    }
+}
+
+impl From<Fido2Credential> for bitwarden_api_api::models::CipherFido2CredentialModel {
+    fn from(value: Fido2Credential) -> Self {
+        Self {
+            credential_id: Some(value.credential_id.to_string()),
+            key_type: Some(value.key_type.to_string()),
+            key_algorithm: Some(value.key_algorithm.to_string()),
+            key_curve: Some(value.key_curve.to_string()),
+            key_value: Some(value.key_value.to_string()),
+            rp_id: Some(value.rp_id.to_string()),
+            rp_name: value.rp_name.map(|n| n.to_string()),
+            user_handle: value.user_handle.map(|h| h.to_string()),
+            user_name: value.user_name.map(|n| n.to_string()),
+            user_display_name: value.user_display_name.map(|n| n.to_string()),
+            counter: Some(value.counter.to_string()),
+            discoverable: Some(value.discoverable.to_string()),
+            creation_date: value.creation_date.to_rfc3339(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -7,15 +7,25 @@ pub(crate) mod identity;
 pub(crate) mod linked_id;
 pub(crate) mod local_data;
 pub(crate) mod login;
+pub(crate) mod search;
 pub(crate) mod secure_note;
+pub(crate) mod uri_match;
 
 pub use attachment::{
-    Attachment, AttachmentEncryptResult, AttachmentFile, AttachmentFileView, AttachmentView,
+    Attachment, AttachmentEncryptResult, AttachmentError, AttachmentFile, AttachmentFileView,
+    AttachmentView,
 };
-pub use cipher::{Cipher, CipherError, CipherListView, CipherRepromptType, CipherType, CipherView};
-pub use field::FieldView;
+pub use card::CardView;
+pub use cipher::{
+    Cipher, CipherCollectionsRequest, CipherError, CipherListView, CipherMoveRequest,
+    CipherRepromptType, CipherType, CipherView,
+};
+pub use field::{FieldType, FieldView};
+pub use identity::IdentityView;
+pub use linked_id::LinkedIdType;
 pub use login::{
     Fido2Credential, Fido2CredentialFullView, Fido2CredentialNewView, Fido2CredentialView, Login,
-    LoginUriView, LoginView,
+    LoginUriView, LoginView, UriMatchType,
 };
-pub use secure_note::SecureNoteType;
+pub use search::CipherSearchQuery;
+pub use secure_note::{SecureNoteType, SecureNoteView};
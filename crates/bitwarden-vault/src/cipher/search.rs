@@ -0,0 +1,312 @@
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::cipher::{CipherListView, CipherListViewType, CipherType};
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CipherSearchQuery {
+    /// Text to search for in the name, subtitle and login URIs. Every whitespace separated term
+    /// needs to match for a cipher to be included. Matching is case insensitive.
+    pub text: Option<String>,
+
+    pub r#type: Option<CipherType>,
+    pub folder_id: Option<Uuid>,
+    pub collection_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub favorite: Option<bool>,
+    /// Search the trash instead of the vault, defaults to false
+    #[serde(default)]
+    pub deleted: bool,
+    pub has_fido2: Option<bool>,
+    pub has_totp: Option<bool>,
+}
+
+/// Filter and rank ciphers matching the query.
+///
+/// When searching by text, the best matches are returned first: a match on the name ranks above
+/// one on the subtitle, which ranks above one on a URI. Ties, and results of queries without text,
+/// are ordered by favorite first and then by name.
+pub(crate) fn search_ciphers(
+    ciphers: Vec<CipherListView>,
+    query: &CipherSearchQuery,
+) -> Vec<CipherListView> {
+    let text = query.text.as_deref().unwrap_or_default().to_lowercase();
+    let terms: Vec<&str> = text.split_whitespace().collect();
+
+    let mut results: Vec<(u32, CipherListView)> = ciphers
+        .into_iter()
+        .filter(|c| query.matches_filters(c))
+        .filter_map(|c| score(&c, &terms).map(|s| (s, c)))
+        .collect();
+
+    results.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| b.favorite.cmp(&a.favorite))
+            .then_with(|| compare_names(a, b))
+    });
+
+    results.into_iter().map(|(_, c)| c).collect()
+}
+
+impl CipherSearchQuery {
+    fn matches_filters(&self, cipher: &CipherListView) -> bool {
+        let (has_fido2, has_totp) = match &cipher.r#type {
+            CipherListViewType::Login {
+                has_fido2, totp, ..
+            } => (*has_fido2, totp.is_some()),
+            _ => (false, false),
+        };
+
+        cipher.deleted_date.is_some() == self.deleted
+            && self.r#type.map_or(true, |t| t == cipher_type(cipher))
+            && self.folder_id.map_or(true, |f| cipher.folder_id == Some(f))
+            && self
+                .collection_id
+                .map_or(true, |c| cipher.collection_ids.contains(&c))
+            && self
+                .organization_id
+                .map_or(true, |o| cipher.organization_id == Some(o))
+            && self.favorite.map_or(true, |f| cipher.favorite == f)
+            && self.has_fido2.map_or(true, |f| has_fido2 == f)
+            && self.has_totp.map_or(true, |t| has_totp == t)
+    }
+}
+
+fn cipher_type(cipher: &CipherListView) -> CipherType {
+    match cipher.r#type {
+        CipherListViewType::Login { .. } => CipherType::Login,
+        CipherListViewType::SecureNote => CipherType::SecureNote,
+        CipherListViewType::Card => CipherType::Card,
+        CipherListViewType::Identity => CipherType::Identity,
+    }
+}
+
+/// Score how well the cipher matches all the terms, or `None` if any term doesn't match.
+fn score(cipher: &CipherListView, terms: &[&str]) -> Option<u32> {
+    if terms.is_empty() {
+        return Some(0);
+    }
+
+    let name = cipher.name.to_lowercase();
+    let sub_title = cipher.sub_title.to_lowercase();
+    let uris: Vec<String> = match &cipher.r#type {
+        CipherListViewType::Login {
+            uris: Some(uris), ..
+        } => uris
+            .iter()
+            .filter_map(|u| u.uri.as_deref())
+            .map(str::to_lowercase)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    terms.iter().try_fold(0, |total, term| {
+        let term_score = [
+            score_field(&name, term, 100),
+            score_field(&sub_title, term, 30),
+            uris.iter()
+                .map(|u| score_field(u, term, 10))
+                .max()
+                .unwrap_or(0),
+        ]
+        .into_iter()
+        .max()
+        .filter(|s| *s > 0)?;
+
+        Some(total + term_score)
+    })
+}
+
+/// Score a single field, an exact match is worth `weight` and weaker matches a fraction of it.
+fn score_field(field: &str, term: &str, weight: u32) -> u32 {
+    if field == term {
+        weight
+    } else if field.starts_with(term) {
+        weight * 3 / 4
+    } else if field
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(term))
+    {
+        weight / 2
+    } else if field.contains(term) {
+        weight / 4
+    } else {
+        0
+    }
+}
+
+fn compare_names(a: &CipherListView, b: &CipherListView) -> Ordering {
+    a.name
+        .to_lowercase()
+        .cmp(&b.name.to_lowercase())
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CipherRepromptType, LoginUriView};
+
+    fn login(name: &str, username: &str, uri: Option<&str>) -> CipherListView {
+        CipherListView {
+            id: Some(Uuid::new_v4()),
+            organization_id: None,
+            folder_id: None,
+            collection_ids: vec![],
+            key: None,
+            name: name.to_owned(),
+            sub_title: username.to_owned(),
+            r#type: CipherListViewType::Login {
+                has_fido2: false,
+                totp: None,
+                uris: uri.map(|u| {
+                    vec![LoginUriView {
+                        uri: Some(u.to_owned()),
+                        r#match: None,
+                        uri_checksum: None,
+                    }]
+                }),
+            },
+            favorite: false,
+            reprompt: CipherRepromptType::None,
+            edit: true,
+            view_password: true,
+            attachments: 0,
+            creation_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
+            deleted_date: None,
+            revision_date: "2024-01-30T17:55:36.150Z".parse().unwrap(),
+        }
+    }
+
+    fn names(ciphers: &[CipherListView]) -> Vec<&str> {
+        ciphers.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    fn search(ciphers: &[CipherListView], query: CipherSearchQuery) -> Vec<CipherListView> {
+        search_ciphers(ciphers.to_vec(), &query)
+    }
+
+    fn text(text: &str) -> CipherSearchQuery {
+        CipherSearchQuery {
+            text: Some(text.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let ciphers = vec![
+            login("Work email", "me@github.com", None),
+            login("My GitHub account", "octocat", None),
+            login("Code hosting", "octocat", Some("https://github.com/login")),
+            login("GitHub", "octocat", None),
+            login("Bank", "me", None),
+        ];
+
+        let results = search(&ciphers, text("GitHub"));
+        assert_eq!(
+            names(&results),
+            vec!["GitHub", "My GitHub account", "Work email", "Code hosting"]
+        );
+    }
+
+    #[test]
+    fn test_search_all_terms_must_match() {
+        let ciphers = vec![
+            login("GitHub", "work", None),
+            login("GitHub", "personal", None),
+        ];
+
+        let results = search(&ciphers, text("github work"));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].sub_title, "work");
+    }
+
+    #[test]
+    fn test_search_without_text_sorts_by_favorite_and_name() {
+        let mut favorite = login("zebra", "", None);
+        favorite.favorite = true;
+        let ciphers = vec![login("beta", "", None), favorite, login("Alpha", "", None)];
+
+        let results = search(&ciphers, CipherSearchQuery::default());
+        assert_eq!(names(&results), vec!["zebra", "Alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_filters() {
+        let folder_id = Uuid::new_v4();
+        let collection_id = Uuid::new_v4();
+
+        let mut in_folder = login("In folder", "", None);
+        in_folder.folder_id = Some(folder_id);
+
+        let mut in_collection = login("In collection", "", None);
+        in_collection.collection_ids = vec![collection_id];
+
+        let mut with_totp = login("With TOTP", "", None);
+        with_totp.r#type = CipherListViewType::Login {
+            has_fido2: true,
+            totp: Some("2.hqdioUAc81FsKQmO1XuLQg==|oDRdsJrQjoFu9NrFVy8tcJBAFKBx95gHaXZnWdXbKpsxWnOr2sKipIG43pKKUFuq|3gKZMiboceIB5SLVOULKg2iuyu6xzos22dfJbvx0EHk=".parse().unwrap()),
+            uris: None,
+        };
+
+        let mut note = login("Note", "", None);
+        note.r#type = CipherListViewType::SecureNote;
+
+        let mut trashed = login("Trashed", "", None);
+        trashed.deleted_date = Some("2024-02-01T00:00:00Z".parse().unwrap());
+
+        let ciphers = vec![in_folder, in_collection, with_totp, note, trashed];
+
+        let filtered = |query: CipherSearchQuery| names(&search(&ciphers, query)).join(",");
+
+        assert_eq!(
+            filtered(CipherSearchQuery {
+                folder_id: Some(folder_id),
+                ..Default::default()
+            }),
+            "In folder"
+        );
+        assert_eq!(
+            filtered(CipherSearchQuery {
+                collection_id: Some(collection_id),
+                ..Default::default()
+            }),
+            "In collection"
+        );
+        assert_eq!(
+            filtered(CipherSearchQuery {
+                has_totp: Some(true),
+                ..Default::default()
+            }),
+            "With TOTP"
+        );
+        assert_eq!(
+            filtered(CipherSearchQuery {
+                has_fido2: Some(true),
+                ..Default::default()
+            }),
+            "With TOTP"
+        );
+        assert_eq!(
+            filtered(CipherSearchQuery {
+                r#type: Some(CipherType::SecureNote),
+                ..Default::default()
+            }),
+            "Note"
+        );
+        assert_eq!(
+            filtered(CipherSearchQuery {
+                deleted: true,
+                ..Default::default()
+            }),
+            "Trashed"
+        );
+    }
+}
@@ -61,3 +61,19 @@ impl From<bitwarden_api_api::models::SecureNoteType> for SecureNoteType {
         }
     }
 }
+
+impl From<SecureNote> for CipherSecureNoteModel {
+    fn from(secure_note: SecureNote) -> Self {
+        Self {
+            r#type: Some(secure_note.r#type.into()),
+        }
+    }
+}
+
+impl From<SecureNoteType> for bitwarden_api_api::models::SecureNoteType {
+    fn from(value: SecureNoteType) -> Self {
+        match value {
+            SecureNoteType::Generic => Self::Generic,
+        }
+    }
+}
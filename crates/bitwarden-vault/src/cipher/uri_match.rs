@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use regex::Regex;
+use url::{Host, Url};
+
+use super::login::{LoginUriView, UriMatchType};
+
+/// Evaluates a page URL against the URIs stored in login items, following the rules of each
+/// [UriMatchType].
+pub(crate) struct UriMatcher<'a> {
+    url: &'a str,
+    host: Option<String>,
+    /// The base domain of the page URL, together with any domains the user considers equivalent
+    domains: HashSet<String>,
+}
+
+impl<'a> UriMatcher<'a> {
+    pub(crate) fn new(url: &'a str, equivalent_domains: &[Vec<String>]) -> Self {
+        let parsed = parse_url(url);
+        let host = parsed.as_ref().and_then(host_with_port);
+
+        let mut domains = HashSet::new();
+        if let Some(domain) = parsed.as_ref().and_then(base_domain) {
+            equivalent_domains
+                .iter()
+                .filter(|set| set.iter().any(|d| d.eq_ignore_ascii_case(&domain)))
+                .flatten()
+                .for_each(|d| {
+                    domains.insert(d.to_lowercase());
+                });
+            domains.insert(domain);
+        }
+
+        Self { url, host, domains }
+    }
+
+    /// Returns `true` if the login URI matches the page URL. `default_match` is used when the
+    /// login URI doesn't specify a match type.
+    pub(crate) fn matches(&self, uri: &LoginUriView, default_match: UriMatchType) -> bool {
+        let Some(login_uri) = uri.uri.as_deref().map(str::trim) else {
+            return false;
+        };
+        if login_uri.is_empty() {
+            return false;
+        }
+
+        match uri.r#match.unwrap_or(default_match) {
+            UriMatchType::Domain => {
+                // App identifiers have no domain to compare
+                if is_app_uri(login_uri) {
+                    return self.url == login_uri;
+                }
+                parse_url(login_uri)
+                    .as_ref()
+                    .and_then(base_domain)
+                    .is_some_and(|d| self.domains.contains(&d))
+            }
+            UriMatchType::Host => {
+                let host = parse_url(login_uri).as_ref().and_then(host_with_port);
+                host.is_some() && host == self.host
+            }
+            UriMatchType::StartsWith => self.url.starts_with(login_uri),
+            UriMatchType::Exact => self.url == login_uri,
+            UriMatchType::RegularExpression => {
+                Regex::new(login_uri).is_ok_and(|r| r.is_match(self.url))
+            }
+            UriMatchType::Never => false,
+        }
+    }
+}
+
+fn is_app_uri(uri: &str) -> bool {
+    uri.starts_with("androidapp://") || uri.starts_with("iosapp://")
+}
+
+/// Parse a URL, assuming `http` when no scheme was provided since users commonly store bare
+/// hostnames.
+fn parse_url(url: &str) -> Option<Url> {
+    let url = url.trim();
+    if url.contains("://") {
+        Url::parse(url).ok()
+    } else {
+        Url::parse(&format!("http://{url}")).ok()
+    }
+}
+
+fn host_with_port(url: &Url) -> Option<String> {
+    let host = url.host_str()?.to_lowercase();
+    Some(match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host,
+    })
+}
+
+/// The registrable domain of the URL according to the public suffix list, e.g. `example.co.uk`
+/// for `https://login.example.co.uk`. IP addresses and hosts without a registrable domain, such
+/// as `localhost`, are compared as is.
+fn base_domain(url: &Url) -> Option<String> {
+    match url.host()? {
+        Host::Domain(domain) => {
+            let domain = domain.trim_end_matches('.').to_lowercase();
+            Some(
+                psl::domain_str(&domain)
+                    .map(str::to_owned)
+                    .unwrap_or(domain),
+            )
+        }
+        Host::Ipv4(ip) => Some(ip.to_string()),
+        Host::Ipv6(ip) => Some(format!("[{ip}]")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(uri: &str, r#match: Option<UriMatchType>) -> LoginUriView {
+        LoginUriView {
+            uri: Some(uri.to_owned()),
+            r#match,
+            uri_checksum: None,
+        }
+    }
+
+    fn matches(url: &str, login_uri: &str, r#match: UriMatchType) -> bool {
+        UriMatcher::new(url, &[]).matches(&uri(login_uri, Some(r#match)), UriMatchType::Domain)
+    }
+
+    #[test]
+    fn test_domain() {
+        let url = "https://vault.bitwarden.com/#/login";
+        assert!(matches(url, "https://bitwarden.com", UriMatchType::Domain));
+        assert!(matches(url, "accounts.bitwarden.com", UriMatchType::Domain));
+        assert!(!matches(url, "https://bitwarden.net", UriMatchType::Domain));
+
+        // Subdomains of a public suffix are different sites
+        let url = "https://alice.github.io";
+        assert!(!matches(url, "https://bob.github.io", UriMatchType::Domain));
+        assert!(matches(
+            "https://login.example.co.uk",
+            "example.co.uk",
+            UriMatchType::Domain
+        ));
+
+        assert!(matches(
+            "http://localhost:8080",
+            "localhost",
+            UriMatchType::Domain
+        ));
+        assert!(matches(
+            "http://192.168.1.1/admin",
+            "192.168.1.1",
+            UriMatchType::Domain
+        ));
+        assert!(!matches(
+            "http://192.168.1.2",
+            "192.168.1.1",
+            UriMatchType::Domain
+        ));
+    }
+
+    #[test]
+    fn test_domain_app_uri() {
+        assert!(matches(
+            "androidapp://com.bitwarden",
+            "androidapp://com.bitwarden",
+            UriMatchType::Domain
+        ));
+        assert!(!matches(
+            "https://bitwarden.com",
+            "androidapp://com.bitwarden",
+            UriMatchType::Domain
+        ));
+    }
+
+    #[test]
+    fn test_equivalent_domains() {
+        let equivalent = vec![
+            vec!["google.com".to_owned(), "youtube.com".to_owned()],
+            vec!["apple.com".to_owned(), "icloud.com".to_owned()],
+        ];
+        let matcher = UriMatcher::new("https://www.youtube.com/watch", &equivalent);
+
+        assert!(matcher.matches(&uri("accounts.google.com", None), UriMatchType::Domain));
+        assert!(!matcher.matches(&uri("icloud.com", None), UriMatchType::Domain));
+        // Equivalent domains only apply to domain matching
+        assert!(!matcher.matches(
+            &uri("https://google.com", Some(UriMatchType::Host)),
+            UriMatchType::Domain
+        ));
+    }
+
+    #[test]
+    fn test_host() {
+        let url = "https://vault.bitwarden.com:8443/#/login";
+        assert!(matches(url, "vault.bitwarden.com:8443", UriMatchType::Host));
+        assert!(!matches(url, "vault.bitwarden.com", UriMatchType::Host));
+        assert!(!matches(url, "bitwarden.com:8443", UriMatchType::Host));
+        assert!(matches(
+            "https://vault.bitwarden.com/",
+            "https://vault.bitwarden.com:443",
+            UriMatchType::Host
+        ));
+    }
+
+    #[test]
+    fn test_starts_with_and_exact() {
+        let url = "https://bitwarden.com/login?next=1";
+        assert!(matches(
+            url,
+            "https://bitwarden.com/login",
+            UriMatchType::StartsWith
+        ));
+        assert!(!matches(
+            url,
+            "https://bitwarden.com/login",
+            UriMatchType::Exact
+        ));
+        assert!(matches(url, url, UriMatchType::Exact));
+        assert!(!matches(
+            url,
+            "http://bitwarden.com",
+            UriMatchType::StartsWith
+        ));
+    }
+
+    #[test]
+    fn test_regular_expression() {
+        let url = "https://bitwarden.com/login";
+        assert!(matches(
+            url,
+            r"^https://(www\.)?bitwarden\.com/",
+            UriMatchType::RegularExpression
+        ));
+        assert!(!matches(
+            url,
+            r"^https://vault\.",
+            UriMatchType::RegularExpression
+        ));
+        // Invalid expressions never match
+        assert!(!matches(url, "(", UriMatchType::RegularExpression));
+    }
+
+    #[test]
+    fn test_never() {
+        let url = "https://bitwarden.com";
+        assert!(!matches(url, url, UriMatchType::Never));
+    }
+
+    #[test]
+    fn test_default_match() {
+        let url = "https://vault.bitwarden.com";
+        let login_uri = uri("https://bitwarden.com", None);
+
+        assert!(UriMatcher::new(url, &[]).matches(&login_uri, UriMatchType::Domain));
+        assert!(!UriMatcher::new(url, &[]).matches(&login_uri, UriMatchType::Host));
+    }
+}
@@ -1,8 +1,12 @@
+use std::sync::Arc;
+
 use bitwarden_core::Client;
+use uuid::Uuid;
 
 use crate::{
-    sync::{sync, SyncError},
-    SyncRequest, SyncResponse,
+    store::{RegisteredVaultStore, VaultData, VaultStore, VaultStoreError},
+    sync::{sync, sync_incremental, SyncError},
+    Cipher, Collection, Folder, SyncRequest, SyncResponse, SyncResult,
 };
 
 pub struct ClientVault<'a> {
@@ -17,6 +21,64 @@ impl<'a> ClientVault<'a> {
     pub async fn sync(&self, input: &SyncRequest) -> Result<SyncResponse, SyncError> {
         sync(self.client, input).await
     }
+
+    /// Check the account revision date and only download the vault when it changed since the last
+    /// sync, reporting which items were added, modified or removed. Requires a store configured
+    /// with [ClientVault::set_store], which holds the snapshot the changes are computed against.
+    pub async fn sync_incremental(&self, input: &SyncRequest) -> Result<SyncResult, SyncError> {
+        sync_incremental(self.client, input).await
+    }
+
+    /// Persist the encrypted vault after every sync, allowing the `list_*` and `get_*` methods to
+    /// be answered without contacting the server. `None` disables the store, the stored data is
+    /// kept until [VaultStore::clear] is called.
+    pub fn set_store(&self, store: Option<Box<dyn VaultStore>>) {
+        self.client
+            .internal
+            .set_store(store.map(|s| Arc::new(RegisteredVaultStore(s))));
+    }
+
+    /// Get the encrypted vault as of the last sync. The organization keys stored with it are
+    /// initialized when the client is unlocked, so its organization items can be decrypted without
+    /// syncing.
+    pub fn stored(&self) -> Result<VaultData, VaultStoreError> {
+        let store = self
+            .client
+            .internal
+            .get_store::<RegisteredVaultStore>()
+            .ok_or(VaultStoreError::NotConfigured)?;
+
+        let data = store
+            .0
+            .load()?
+            .ok_or(VaultStoreError::NotSynced)?
+            .check_account(self.client.internal.get_user_id())?;
+        data.initialize_org_crypto(self.client)?;
+
+        Ok(data)
+    }
+
+    /// List the stored ciphers, use `ciphers().decrypt_list` to decrypt them
+    pub fn list_ciphers(&self) -> Result<Vec<Cipher>, VaultStoreError> {
+        Ok(self.stored()?.ciphers)
+    }
+
+    /// Get a stored cipher by id, `None` if the vault doesn't contain it
+    pub fn get_cipher(&self, id: Uuid) -> Result<Option<Cipher>, VaultStoreError> {
+        Ok(self
+            .stored()?
+            .ciphers
+            .into_iter()
+            .find(|c| c.id == Some(id)))
+    }
+
+    pub fn list_folders(&self) -> Result<Vec<Folder>, VaultStoreError> {
+        Ok(self.stored()?.folders)
+    }
+
+    pub fn list_collections(&self) -> Result<Vec<Collection>, VaultStoreError> {
+        Ok(self.stored()?.collections)
+    }
 }
 
 pub trait ClientVaultExt<'a> {
@@ -28,3 +90,81 @@ impl<'a> ClientVaultExt<'a> for Client {
         ClientVault::new(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileVaultStore;
+
+    #[test]
+    fn test_stored_vault() {
+        let client = Client::new(None);
+        let vault = client.vault();
+
+        assert!(matches!(
+            vault.list_ciphers(),
+            Err(VaultStoreError::NotConfigured)
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileVaultStore::new(dir.path().join("vault.json"));
+        vault.set_store(Some(Box::new(FileVaultStore::new(
+            dir.path().join("vault.json"),
+        ))));
+
+        assert!(matches!(
+            vault.list_ciphers(),
+            Err(VaultStoreError::NotSynced)
+        ));
+
+        let cipher: Cipher = serde_json::from_value(serde_json::json!({
+            "id": "358f2b2b-9326-4e5e-94a8-b18100bb0908",
+            "organizationId": null,
+            "folderId": null,
+            "collectionIds": [],
+            "key": null,
+            "name": "2.+oPT8B4xJhyhQRe1VkIx0A==|PBtC/bZkggXR+fSnL/pG7g==|UkjRD0VpnUYkjRC/05ZLdEBAmRbr3qWRyJey2bUvR9w=",
+            "notes": null,
+            "type": 2,
+            "login": null,
+            "identity": null,
+            "card": null,
+            "secureNote": { "type": 0 },
+            "favorite": false,
+            "reprompt": 0,
+            "organizationUseTotp": true,
+            "edit": true,
+            "viewPassword": true,
+            "localData": null,
+            "attachments": null,
+            "fields": null,
+            "passwordHistory": null,
+            "creationDate": "2024-05-31T11:20:58.4566667Z",
+            "deletedDate": null,
+            "revisionDate": "2024-05-31T11:20:58.4566667Z"
+        }))
+        .unwrap();
+
+        store
+            .save(&VaultData {
+                user_id: Uuid::new_v4(),
+                revision_date: "2024-05-31T11:20:58Z".parse().unwrap(),
+                organization_keys: Default::default(),
+                ciphers: vec![cipher],
+                folders: vec![],
+                collections: vec![],
+            })
+            .unwrap();
+
+        assert_eq!(vault.list_ciphers().unwrap().len(), 1);
+        let id = "358f2b2b-9326-4e5e-94a8-b18100bb0908".parse().unwrap();
+        assert!(vault.get_cipher(id).unwrap().is_some());
+        assert!(vault.get_cipher(Uuid::new_v4()).unwrap().is_none());
+
+        vault.set_store(None);
+        assert!(matches!(
+            vault.list_ciphers(),
+            Err(VaultStoreError::NotConfigured)
+        ));
+    }
+}
@@ -1,7 +1,8 @@
-use bitwarden_api_api::models::CollectionDetailsResponseModel;
+use bitwarden_api_api::models::{CollectionDetailsResponseModel, CollectionResponseModel};
 use bitwarden_core::require;
 use bitwarden_crypto::{
-    CryptoError, EncString, KeyContainer, KeyDecryptable, LocateKey, SymmetricCryptoKey,
+    CryptoError, EncString, KeyContainer, KeyDecryptable, KeyEncryptable, LocateKey,
+    SymmetricCryptoKey,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,7 +10,7 @@ use uuid::Uuid;
 
 use crate::VaultParseError;
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct Collection {
@@ -37,6 +38,38 @@ pub struct CollectionView {
     pub read_only: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CollectionCreateRequest {
+    /// Organization where the collection will be created
+    pub organization_id: Uuid,
+    pub name: String,
+    pub external_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CollectionUpdateRequest {
+    /// ID of the collection to modify
+    pub id: Uuid,
+    /// Organization the collection belongs to
+    pub organization_id: Uuid,
+    pub name: String,
+    pub external_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CollectionDeleteRequest {
+    /// ID of the collection to delete
+    pub id: Uuid,
+    /// Organization the collection belongs to
+    pub organization_id: Uuid,
+}
+
 impl LocateKey for Collection {
     fn locate_key<'a>(
         &self,
@@ -46,6 +79,29 @@ impl LocateKey for Collection {
         enc.get_key(&Some(self.organization_id))
     }
 }
+impl LocateKey for CollectionView {
+    fn locate_key<'a>(
+        &self,
+        enc: &'a dyn KeyContainer,
+        _: &Option<Uuid>,
+    ) -> Result<&'a SymmetricCryptoKey, CryptoError> {
+        enc.get_key(&Some(self.organization_id))
+    }
+}
+impl KeyEncryptable<SymmetricCryptoKey, Collection> for CollectionView {
+    fn encrypt_with_key(self, key: &SymmetricCryptoKey) -> Result<Collection, CryptoError> {
+        Ok(Collection {
+            id: self.id,
+            organization_id: self.organization_id,
+
+            name: self.name.encrypt_with_key(key)?,
+
+            external_id: self.external_id,
+            hide_passwords: self.hide_passwords,
+            read_only: self.read_only,
+        })
+    }
+}
 impl KeyDecryptable<SymmetricCryptoKey, CollectionView> for Collection {
     fn decrypt_with_key(&self, key: &SymmetricCryptoKey) -> Result<CollectionView, CryptoError> {
         Ok(CollectionView {
@@ -61,6 +117,23 @@ impl KeyDecryptable<SymmetricCryptoKey, CollectionView> for Collection {
     }
 }
 
+impl TryFrom<CollectionResponseModel> for Collection {
+    type Error = VaultParseError;
+
+    /// The response doesn't include the permissions of the current user, which default to full
+    /// access as only collection managers can create and edit collections
+    fn try_from(collection: CollectionResponseModel) -> Result<Self, Self::Error> {
+        Ok(Collection {
+            id: collection.id,
+            organization_id: require!(collection.organization_id),
+            name: require!(collection.name).parse()?,
+            external_id: collection.external_id,
+            hide_passwords: false,
+            read_only: false,
+        })
+    }
+}
+
 impl TryFrom<CollectionDetailsResponseModel> for Collection {
     type Error = VaultParseError;
 
@@ -9,3 +9,13 @@ pub enum VaultParseError {
     #[error(transparent)]
     MissingFieldError(#[from] bitwarden_core::MissingFieldError),
 }
+
+impl From<VaultParseError> for bitwarden_core::Error {
+    fn from(e: VaultParseError) -> Self {
+        match e {
+            VaultParseError::Chrono(e) => Self::Chrono(e),
+            VaultParseError::Crypto(e) => Self::Crypto(e),
+            VaultParseError::MissingFieldError(e) => Self::MissingFieldError(e),
+        }
+    }
+}
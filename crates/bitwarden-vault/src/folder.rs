@@ -1,4 +1,4 @@
-use bitwarden_api_api::models::FolderResponseModel;
+use bitwarden_api_api::models::{FolderResponseModel, FolderWithIdRequestModel};
 use bitwarden_core::require;
 use bitwarden_crypto::{
     CryptoError, EncString, KeyDecryptable, KeyEncryptable, SymmetricCryptoKey,
@@ -10,12 +10,12 @@ use uuid::Uuid;
 
 use crate::VaultParseError;
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct Folder {
-    id: Option<Uuid>,
-    name: EncString,
+    pub(crate) id: Option<Uuid>,
+    pub(crate) name: EncString,
     revision_date: DateTime<Utc>,
 }
 
@@ -28,6 +28,30 @@ pub struct FolderView {
     pub revision_date: DateTime<Utc>,
 }
 
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FolderCreateRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FolderUpdateRequest {
+    /// ID of the folder to rename
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FolderDeleteRequest {
+    /// ID of the folder to delete, the ciphers it contains are moved out of it
+    pub id: Uuid,
+}
+
 impl KeyEncryptable<SymmetricCryptoKey, Folder> for FolderView {
     fn encrypt_with_key(self, key: &SymmetricCryptoKey) -> Result<Folder, CryptoError> {
         Ok(Folder {
@@ -59,3 +83,12 @@ impl TryFrom<FolderResponseModel> for Folder {
         })
     }
 }
+
+impl From<Folder> for FolderWithIdRequestModel {
+    fn from(folder: Folder) -> Self {
+        Self {
+            name: folder.name.to_string(),
+            id: folder.id,
+        }
+    }
+}
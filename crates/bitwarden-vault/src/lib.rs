@@ -6,9 +6,14 @@ mod uniffi_support;
 mod cipher;
 pub use cipher::*;
 mod collection;
-pub use collection::{Collection, CollectionView};
+pub use collection::{
+    Collection, CollectionCreateRequest, CollectionDeleteRequest, CollectionUpdateRequest,
+    CollectionView,
+};
 mod folder;
-pub use folder::{Folder, FolderView};
+pub use folder::{
+    Folder, FolderCreateRequest, FolderDeleteRequest, FolderUpdateRequest, FolderView,
+};
 mod password_history;
 pub use password_history::{PasswordHistory, PasswordHistoryView};
 mod domain;
@@ -21,5 +26,7 @@ mod client_vault;
 pub use client_vault::{ClientVault, ClientVaultExt};
 mod client_totp;
 mod mobile;
+mod store;
+pub use store::{FileVaultStore, VaultData, VaultStore, VaultStoreError};
 mod sync;
-pub use sync::{SyncRequest, SyncResponse};
+pub use sync::{DomainResponse, SyncChanges, SyncError, SyncRequest, SyncResponse, SyncResult};
@@ -1,11 +1,16 @@
-use std::path::Path;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
 
-use bitwarden_core::{Client, Error};
-use bitwarden_crypto::{EncString, KeyDecryptable, KeyEncryptable, LocateKey};
+use bitwarden_api_api::models::{AttachmentRequestModel, FileUploadType};
+use bitwarden_core::{files, require, Client, Error};
+use bitwarden_crypto::{encrypt_stream, EncString, KeyDecryptable, KeyEncryptable, LocateKey};
 
 use crate::{
-    Attachment, AttachmentEncryptResult, AttachmentFile, AttachmentFileView, AttachmentView,
-    Cipher, ClientVault,
+    store::store_cipher, Attachment, AttachmentEncryptResult, AttachmentError, AttachmentFile,
+    AttachmentFileView, AttachmentView, Cipher, ClientVault,
 };
 
 pub struct ClientAttachments<'a> {
@@ -29,20 +34,29 @@ impl<'a> ClientAttachments<'a> {
         }
         .encrypt_with_key(key)?)
     }
+    /// Encrypt a file without loading it in memory. The encrypted file has the same format as
+    /// the contents returned by [ClientAttachments::encrypt_buffer].
     pub fn encrypt_file(
         &self,
         cipher: Cipher,
-        attachment: AttachmentView,
+        mut attachment: AttachmentView,
         decrypted_file_path: &Path,
         encrypted_file_path: &Path,
     ) -> Result<Attachment, Error> {
-        let data = std::fs::read(decrypted_file_path)?;
-        let AttachmentEncryptResult {
-            attachment,
-            contents,
-        } = self.encrypt_buffer(cipher, attachment, &data)?;
-        std::fs::write(encrypted_file_path, contents)?;
-        Ok(attachment)
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = cipher.locate_key(&enc, &None)?;
+        let ciphers_key = Cipher::get_cipher_key(key, &cipher.key)?;
+        let ciphers_key = ciphers_key.as_ref().unwrap_or(key);
+
+        let attachment_key = attachment.generate_key(ciphers_key)?;
+        let size = encrypt_stream(
+            &attachment_key,
+            BufReader::new(File::open(decrypted_file_path)?),
+            BufWriter::new(File::create(encrypted_file_path)?),
+        )?;
+        attachment.set_size(size as usize);
+
+        Ok(attachment.encrypt_with_key(ciphers_key)?)
     }
 
     pub fn decrypt_buffer(
@@ -62,6 +76,8 @@ impl<'a> ClientAttachments<'a> {
         .decrypt_with_key(key)
         .map_err(Error::Crypto)
     }
+    /// Decrypt a file without loading it in memory. Nothing is written to `decrypted_file_path`
+    /// unless the encrypted file is intact.
     pub fn decrypt_file(
         &self,
         cipher: Cipher,
@@ -69,10 +85,131 @@ impl<'a> ClientAttachments<'a> {
         encrypted_file_path: &Path,
         decrypted_file_path: &Path,
     ) -> Result<(), Error> {
-        let data = std::fs::read(encrypted_file_path)?;
-        let decrypted = self.decrypt_buffer(cipher, attachment, &data)?;
-        std::fs::write(decrypted_file_path, decrypted)?;
-        Ok(())
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = cipher.locate_key(&enc, &None)?;
+        let content_key = attachment.content_key(&cipher, key)?;
+
+        files::decrypt_file(&content_key, encrypted_file_path, decrypted_file_path)
+    }
+}
+
+impl<'a> ClientAttachments<'a> {
+    /// Encrypt the file at `path` and upload it as a new attachment of `cipher`. Returns the
+    /// updated cipher, which includes the new attachment.
+    pub async fn upload_attachment(
+        &self,
+        cipher: Cipher,
+        path: &Path,
+    ) -> Result<Cipher, AttachmentError> {
+        let cipher_id = require!(cipher.id);
+        let collection_ids = cipher.collection_ids.clone();
+        let file_name = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .ok_or(Error::Internal(
+                "The attachment path has no file name".into(),
+            ))?;
+
+        // Encrypted to a temporary file, so large attachments are never held in memory
+        let encrypted = tempfile::NamedTempFile::new()?;
+        let attachment = self.encrypt_file(
+            cipher,
+            AttachmentView {
+                id: None,
+                url: None,
+                size: None,
+                size_name: None,
+                file_name: Some(file_name),
+                key: None,
+            },
+            path,
+            encrypted.path(),
+        )?;
+        let encrypted_file_name = require!(attachment.file_name).to_string();
+        let encrypted_size = encrypted.as_file().metadata()?.len();
+
+        let config = self.client.internal.get_api_configurations().await;
+        let upload = bitwarden_api_api::apis::ciphers_api::ciphers_id_attachment_v2_post(
+            &config.api,
+            cipher_id,
+            Some(AttachmentRequestModel {
+                key: attachment.key.map(|k| k.to_string()),
+                file_name: Some(encrypted_file_name.clone()),
+                file_size: Some(encrypted_size as i64),
+                admin_request: Some(false),
+            }),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        let (Some(attachment_id), Some(url), Some(cipher_response)) =
+            (upload.attachment_id, upload.url, upload.cipher_response)
+        else {
+            return Err(AttachmentError::MissingUploadData);
+        };
+
+        let result = match upload.file_upload_type {
+            Some(FileUploadType::Azure) => {
+                files::upload_azure(self.client, &url, encrypted.path()).await
+            }
+            _ => {
+                files::upload_self_hosted(
+                    self.client,
+                    &format!("ciphers/{cipher_id}/attachment/{attachment_id}"),
+                    encrypted_file_name,
+                    encrypted.path(),
+                )
+                .await
+            }
+        };
+
+        // The attachment was already created, remove it so the cipher doesn't reference a file that
+        // doesn't exist
+        if let Err(e) = result {
+            _ = bitwarden_api_api::apis::ciphers_api::ciphers_id_attachment_attachment_id_delete(
+                &config.api,
+                cipher_id,
+                &attachment_id,
+            )
+            .await;
+            return Err(e.into());
+        }
+
+        let mut cipher: Cipher = (*cipher_response).try_into()?;
+        cipher.collection_ids = collection_ids;
+        store_cipher(self.client, &cipher);
+
+        Ok(cipher)
+    }
+
+    /// Download an attachment of `cipher`, verify its encrypted size and write the decrypted
+    /// contents to `destination`.
+    pub async fn download_attachment(
+        &self,
+        cipher: Cipher,
+        attachment_id: &str,
+        destination: &Path,
+    ) -> Result<(), AttachmentError> {
+        let cipher_id = require!(cipher.id);
+
+        // The server returns a short lived download URL
+        let config = self.client.internal.get_api_configurations().await;
+        let response =
+            bitwarden_api_api::apis::ciphers_api::ciphers_id_attachment_attachment_id_get(
+                &config.api,
+                cipher_id,
+                attachment_id,
+            )
+            .await
+            .map_err(Error::from)?;
+        let url = require!(response.url.clone());
+        let attachment: Attachment = response.try_into()?;
+
+        files::download_and_decrypt(self.client, &url, destination, |encrypted_path, size| {
+            attachment.verify_encrypted_size(size)?;
+            Ok(self.decrypt_file(cipher, attachment, encrypted_path, destination)?)
+        })
+        .await
     }
 }
 
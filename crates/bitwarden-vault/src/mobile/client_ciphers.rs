@@ -1,8 +1,17 @@
-use bitwarden_core::{Client, Error};
+use bitwarden_api_api::models::{
+    CipherBulkMoveRequestModel, CipherCollectionsRequestModel, CipherCreateRequestModel,
+    CipherShareRequestModel,
+};
+use bitwarden_core::{require, Client, Error};
 use bitwarden_crypto::{KeyDecryptable, KeyEncryptable, LocateKey};
 use uuid::Uuid;
 
-use crate::{Cipher, CipherError, CipherListView, CipherView, ClientVault};
+use crate::{
+    cipher::{search::search_ciphers, uri_match::UriMatcher},
+    store::{store_cipher, update_stored},
+    Cipher, CipherCollectionsRequest, CipherError, CipherListView, CipherMoveRequest,
+    CipherSearchQuery, CipherType, CipherView, ClientVault, UriMatchType,
+};
 
 pub struct ClientCiphers<'a> {
     pub(crate) client: &'a Client,
@@ -77,6 +86,219 @@ impl<'a> ClientCiphers<'a> {
         cipher_view.move_to_organization(&enc, organization_id)?;
         Ok(cipher_view)
     }
+
+    /// Create a new cipher on the server. Ciphers belonging to an organization are assigned to the
+    /// collections in `collection_ids`.
+    pub async fn create(&self, cipher_view: CipherView) -> Result<CipherView, CipherError> {
+        let collection_ids = cipher_view.collection_ids.clone();
+        let cipher = self.encrypt(cipher_view)?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response = if cipher.organization_id.is_some() {
+            bitwarden_api_api::apis::ciphers_api::ciphers_create_post(
+                &config.api,
+                Some(CipherCreateRequestModel {
+                    collection_ids: Some(collection_ids.clone()),
+                    cipher: Box::new(cipher.into()),
+                }),
+            )
+            .await
+            .map_err(Error::from)?
+        } else {
+            bitwarden_api_api::apis::ciphers_api::ciphers_post(&config.api, Some(cipher.into()))
+                .await
+                .map_err(Error::from)?
+        };
+
+        self.process_response(response.try_into()?, collection_ids)
+    }
+
+    /// Update an existing cipher. The server rejects the update if the cipher was modified since
+    /// `revision_date`.
+    pub async fn update(&self, cipher_view: CipherView) -> Result<CipherView, CipherError> {
+        let id = require!(cipher_view.id);
+        let collection_ids = cipher_view.collection_ids.clone();
+        let cipher = self.encrypt(cipher_view)?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response = bitwarden_api_api::apis::ciphers_api::ciphers_id_put(
+            &config.api,
+            id,
+            Some(cipher.into()),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        self.process_response(response.try_into()?, collection_ids)
+    }
+
+    /// Move a cipher to the trash, it can be restored with [ClientCiphers::restore]
+    pub async fn soft_delete(&self, id: Uuid) -> Result<(), CipherError> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::ciphers_api::ciphers_id_delete_put(&config.api, id)
+            .await
+            .map_err(Error::from)?;
+
+        // The server sets the deletion date, the next sync brings the exact value
+        update_stored(self.client, |data| {
+            if let Some(cipher) = data.ciphers.iter_mut().find(|c| c.id == Some(id)) {
+                cipher.deleted_date = Some(chrono::Utc::now());
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Restore a cipher from the trash
+    pub async fn restore(&self, id: Uuid) -> Result<CipherView, CipherError> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::ciphers_api::ciphers_id_restore_put(&config.api, id)
+            .await
+            .map_err(Error::from)?;
+
+        // The restore response doesn't include the collections, fetch the full details instead
+        let response =
+            bitwarden_api_api::apis::ciphers_api::ciphers_id_details_get(&config.api, id)
+                .await
+                .map_err(Error::from)?;
+        let cipher: Cipher = response.try_into()?;
+        let collection_ids = cipher.collection_ids.clone();
+
+        self.process_response(cipher, collection_ids)
+    }
+
+    /// Share a personal cipher with an organization, re-encrypting it with the organization key
+    pub async fn share(
+        &self,
+        cipher_view: CipherView,
+        organization_id: Uuid,
+        collection_ids: Vec<Uuid>,
+    ) -> Result<CipherView, CipherError> {
+        let id = require!(cipher_view.id);
+        let mut cipher_view = self.move_to_organization(cipher_view, organization_id)?;
+        cipher_view.collection_ids = collection_ids.clone();
+        let cipher = self.encrypt(cipher_view)?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response = bitwarden_api_api::apis::ciphers_api::ciphers_id_share_put(
+            &config.api,
+            id,
+            Some(CipherShareRequestModel {
+                collection_ids: collection_ids.iter().map(|c| c.to_string()).collect(),
+                cipher: Box::new(cipher.into()),
+            }),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        self.process_response(response.try_into()?, collection_ids)
+    }
+
+    /// Permanently delete a cipher, this can't be undone
+    pub async fn delete(&self, id: Uuid) -> Result<(), CipherError> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::ciphers_api::ciphers_id_delete(&config.api, id)
+            .await
+            .map_err(Error::from)?;
+
+        update_stored(self.client, |data| {
+            data.ciphers.retain(|c| c.id != Some(id))
+        });
+
+        Ok(())
+    }
+
+    /// Move ciphers to a folder, or out of their folder when `folder_id` is `None`
+    pub async fn move_to_folder(&self, input: &CipherMoveRequest) -> Result<(), CipherError> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::ciphers_api::ciphers_move_put(
+            &config.api,
+            Some(CipherBulkMoveRequestModel {
+                ids: input.ids.iter().map(|id| id.to_string()).collect(),
+                folder_id: input.folder_id.map(|id| id.to_string()),
+            }),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        update_stored(self.client, |data| {
+            data.ciphers
+                .iter_mut()
+                .filter(|c| c.id.is_some_and(|id| input.ids.contains(&id)))
+                .for_each(|c| c.folder_id = input.folder_id);
+        });
+
+        Ok(())
+    }
+
+    /// Replace the collections an organization cipher belongs to
+    pub async fn update_collections(
+        &self,
+        input: &CipherCollectionsRequest,
+    ) -> Result<CipherView, CipherError> {
+        let config = self.client.internal.get_api_configurations().await;
+        let response = bitwarden_api_api::apis::ciphers_api::ciphers_id_collections_put(
+            &config.api,
+            input.id,
+            Some(CipherCollectionsRequestModel {
+                collection_ids: input.collection_ids.iter().map(|c| c.to_string()).collect(),
+            }),
+        )
+        .await
+        .map_err(Error::from)?;
+
+        self.process_response(response.try_into()?, input.collection_ids.clone())
+    }
+
+    /// Store the cipher returned by the server and decrypt it
+    fn process_response(
+        &self,
+        mut cipher: Cipher,
+        collection_ids: Vec<Uuid>,
+    ) -> Result<CipherView, CipherError> {
+        cipher.collection_ids = collection_ids;
+        store_cipher(self.client, &cipher);
+        Ok(self.decrypt(cipher)?)
+    }
+
+    /// Search and filter decrypted ciphers, returning the best matches first. See
+    /// [CipherSearchQuery] for the available filters.
+    pub fn search(
+        &self,
+        ciphers: Vec<CipherListView>,
+        query: &CipherSearchQuery,
+    ) -> Vec<CipherListView> {
+        search_ciphers(ciphers, query)
+    }
+
+    /// Find the login ciphers that should be offered for autofill on `url`.
+    ///
+    /// Login URIs without an explicit match type use `default_match`, falling back to
+    /// [UriMatchType::Domain]. `equivalent_domains` are the sets of domains the user considers the
+    /// same site, usually
+    /// [DomainResponse::equivalent_domains](crate::DomainResponse::equivalent_domains) from the
+    /// last sync. Deleted ciphers are never returned.
+    pub fn find_matching(
+        &self,
+        ciphers: Vec<CipherView>,
+        url: &str,
+        default_match: Option<UriMatchType>,
+        equivalent_domains: &[Vec<String>],
+    ) -> Vec<CipherView> {
+        let matcher = UriMatcher::new(url, equivalent_domains);
+        let default_match = default_match.unwrap_or(UriMatchType::Domain);
+
+        ciphers
+            .into_iter()
+            .filter(|c| c.deleted_date.is_none() && matches!(c.r#type, CipherType::Login))
+            .filter(|c| {
+                c.login
+                    .as_ref()
+                    .and_then(|l| l.uris.as_ref())
+                    .is_some_and(|uris| uris.iter().any(|u| matcher.matches(u, default_match)))
+            })
+            .collect()
+    }
 }
 
 impl<'a> ClientVault<'a> {
@@ -90,10 +312,17 @@ impl<'a> ClientVault<'a> {
 #[cfg(test)]
 mod tests {
 
-    use bitwarden_core::client::test_accounts::test_bitwarden_com_account;
+    use bitwarden_core::{client::test_accounts::test_bitwarden_com_account, ClientSettings};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
 
     use super::*;
-    use crate::{Attachment, CipherRepromptType, CipherType, ClientVaultExt, Login};
+    use crate::{
+        Attachment, CipherRepromptType, CipherType, ClientVaultExt, FileVaultStore, Login,
+        VaultData, VaultStore,
+    };
 
     #[tokio::test]
     async fn test_decrypt_list() {
@@ -330,4 +559,137 @@ mod tests {
 
         assert_eq!(content, b"Hello");
     }
+
+    #[tokio::test]
+    async fn test_find_matching() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+        let view = client.vault().ciphers().decrypt(test_cipher()).unwrap();
+
+        let with_uri = |uri: &str, r#match: Option<UriMatchType>| {
+            let mut view = view.clone();
+            view.login.as_mut().unwrap().uris = Some(vec![crate::LoginUriView {
+                uri: Some(uri.to_owned()),
+                r#match,
+                uri_checksum: None,
+            }]);
+            view
+        };
+
+        let mut deleted = with_uri("https://bitwarden.com", None);
+        deleted.deleted_date = Some("2024-06-01T00:00:00Z".parse().unwrap());
+
+        let ciphers = vec![
+            with_uri("https://bitwarden.com", None),
+            with_uri("https://vault.bitwarden.com", Some(UriMatchType::Exact)),
+            with_uri("https://example.com", None),
+            deleted,
+        ];
+
+        let matching = client.vault().ciphers().find_matching(
+            ciphers.clone(),
+            "https://vault.bitwarden.com/#/login",
+            None,
+            &[],
+        );
+        assert_eq!(matching.len(), 1);
+        assert_eq!(
+            matching[0].login.as_ref().unwrap().uris.as_ref().unwrap()[0].uri,
+            Some("https://bitwarden.com".to_owned())
+        );
+
+        let matching = client.vault().ciphers().find_matching(
+            ciphers.clone(),
+            "https://example.net",
+            Some(UriMatchType::Host),
+            &[vec!["example.com".to_owned(), "example.net".to_owned()]],
+        );
+        assert!(matching.is_empty());
+
+        let matching = client.vault().ciphers().find_matching(
+            ciphers,
+            "https://example.net",
+            None,
+            &[vec!["example.com".to_owned(), "example.net".to_owned()]],
+        );
+        assert_eq!(matching.len(), 1);
+    }
+
+    /// Responds with the cipher in the request, as the server would return it after saving it
+    fn saved_cipher(request: &Request) -> ResponseTemplate {
+        let mut cipher: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        cipher["id"] = "358f2b2b-9326-4e5e-94a8-b18100bb0908".into();
+        cipher["creationDate"] = "2024-05-31T11:20:58.4566667Z".into();
+        cipher["revisionDate"] = "2024-06-01T08:00:00Z".into();
+        ResponseTemplate::new(200).set_body_json(cipher)
+    }
+
+    #[tokio::test]
+    async fn test_create_update_soft_delete_stored() {
+        let server = MockServer::start().await;
+        let id: Uuid = "358f2b2b-9326-4e5e-94a8-b18100bb0908".parse().unwrap();
+        Mock::given(method("POST"))
+            .and(path("/api/ciphers"))
+            .respond_with(saved_cipher)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(format!("/api/ciphers/{id}")))
+            .respond_with(saved_cipher)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(format!("/api/ciphers/{id}/delete")))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = Client::init_test_account_with_settings(
+            test_bitwarden_com_account(),
+            Some(ClientSettings {
+                identity_url: format!("{}/identity", server.uri()),
+                api_url: format!("{}/api", server.uri()),
+                ..Default::default()
+            }),
+        )
+        .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileVaultStore::new(dir.path().join("vault.json"));
+        store
+            .save(&VaultData {
+                user_id: "b1fd4bf2-9643-4787-87f3-b0f00189c33b".parse().unwrap(),
+                revision_date: "2024-05-31T11:20:58Z".parse().unwrap(),
+                organization_keys: Default::default(),
+                ciphers: vec![],
+                folders: vec![],
+                collections: vec![],
+            })
+            .unwrap();
+        client.vault().set_store(Some(Box::new(FileVaultStore::new(
+            dir.path().join("vault.json"),
+        ))));
+
+        let mut view = client.vault().ciphers().decrypt(test_cipher()).unwrap();
+        view.id = None;
+        let created = client.vault().ciphers().create(view).await.unwrap();
+        assert_eq!(created.id, Some(id));
+        let stored = store.load().unwrap().unwrap().ciphers;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].id, Some(id));
+
+        let mut view = created;
+        view.name = "Renamed item".to_owned();
+        let updated = client.vault().ciphers().update(view).await.unwrap();
+        assert_eq!(updated.name, "Renamed item");
+        let stored = client.vault().get_cipher(id).unwrap().unwrap();
+        assert_eq!(
+            client.vault().ciphers().decrypt(stored).unwrap().name,
+            "Renamed item"
+        );
+
+        client.vault().ciphers().soft_delete(id).await.unwrap();
+        let stored = store.load().unwrap().unwrap().ciphers;
+        assert_eq!(stored.len(), 1);
+        assert!(stored[0].deleted_date.is_some());
+    }
 }
@@ -1,13 +1,28 @@
+use bitwarden_api_api::models::CollectionRequestModel;
 use bitwarden_core::{Client, Error};
-use bitwarden_crypto::{KeyDecryptable, LocateKey};
+use bitwarden_crypto::{KeyDecryptable, KeyEncryptable, LocateKey};
+use uuid::Uuid;
 
-use crate::{ClientVault, Collection, CollectionView};
+use crate::{
+    store::{store_collection, update_stored},
+    ClientVault, Collection, CollectionCreateRequest, CollectionDeleteRequest,
+    CollectionUpdateRequest, CollectionView,
+};
 
 pub struct ClientCollections<'a> {
     pub(crate) client: &'a Client,
 }
 
 impl<'a> ClientCollections<'a> {
+    pub fn encrypt(&self, collection_view: CollectionView) -> Result<Collection, Error> {
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = collection_view.locate_key(&enc, &None)?;
+
+        let collection = collection_view.encrypt_with_key(key)?;
+
+        Ok(collection)
+    }
+
     pub fn decrypt(&self, collection: Collection) -> Result<CollectionView, Error> {
         let enc = self.client.internal.get_encryption_settings()?;
         let key = collection.locate_key(&enc, &None)?;
@@ -30,6 +45,91 @@ impl<'a> ClientCollections<'a> {
 
         views
     }
+
+    /// Create a collection in an organization, requires permission to manage its collections
+    pub async fn create(&self, input: &CollectionCreateRequest) -> Result<CollectionView, Error> {
+        let request = self.request_model(
+            input.organization_id,
+            &input.name,
+            input.external_id.clone(),
+        )?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response =
+            bitwarden_api_api::apis::collections_api::organizations_org_id_collections_post(
+                &config.api,
+                input.organization_id,
+                Some(request),
+            )
+            .await?;
+
+        self.process_response(response.try_into()?)
+    }
+
+    /// Rename a collection or change its external id
+    pub async fn update(&self, input: &CollectionUpdateRequest) -> Result<CollectionView, Error> {
+        let request = self.request_model(
+            input.organization_id,
+            &input.name,
+            input.external_id.clone(),
+        )?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response =
+            bitwarden_api_api::apis::collections_api::organizations_org_id_collections_id_put(
+                &config.api,
+                input.organization_id,
+                input.id,
+                Some(request),
+            )
+            .await?;
+
+        self.process_response(response.try_into()?)
+    }
+
+    /// Delete a collection. The ciphers it contains are kept in the organization.
+    pub async fn delete(&self, input: &CollectionDeleteRequest) -> Result<(), Error> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::collections_api::organizations_org_id_collections_id_delete(
+            &config.api,
+            input.organization_id,
+            input.id,
+        )
+        .await?;
+
+        update_stored(self.client, |data| {
+            data.collections.retain(|c| c.id != Some(input.id));
+            data.ciphers
+                .iter_mut()
+                .for_each(|c| c.collection_ids.retain(|id| *id != input.id));
+        });
+
+        Ok(())
+    }
+
+    fn request_model(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+        external_id: Option<String>,
+    ) -> Result<CollectionRequestModel, Error> {
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = enc.get_key(&Some(organization_id))?;
+
+        // Group and user access is left untouched, it's managed in the admin console
+        Ok(CollectionRequestModel {
+            name: name.to_owned().encrypt_with_key(key)?.to_string(),
+            external_id,
+            groups: None,
+            users: None,
+        })
+    }
+
+    /// Store the collection returned by the server and decrypt it
+    fn process_response(&self, collection: Collection) -> Result<CollectionView, Error> {
+        store_collection(self.client, &collection);
+        self.decrypt(collection)
+    }
 }
 
 impl<'a> ClientVault<'a> {
@@ -78,4 +178,25 @@ mod tests {
 
         assert_eq!(dec.name, "Default collection");
     }
+
+    #[tokio::test]
+    async fn test_encrypt() {
+        let client = Client::init_test_account(test_bitwarden_com_account()).await;
+
+        let collection = client
+            .vault()
+            .collections()
+            .encrypt(CollectionView {
+                id: None,
+                organization_id: "1bc9ac1e-f5aa-45f2-94bf-b181009709b8".parse().unwrap(),
+                name: "Engineering".to_owned(),
+                external_id: None,
+                hide_passwords: false,
+                read_only: false,
+            })
+            .unwrap();
+
+        let dec = client.vault().collections().decrypt(collection).unwrap();
+        assert_eq!(dec.name, "Engineering");
+    }
 }
@@ -1,7 +1,11 @@
+use bitwarden_api_api::models::FolderRequestModel;
 use bitwarden_core::{Client, Error};
 use bitwarden_crypto::{KeyDecryptable, KeyEncryptable};
 
-use crate::{ClientVault, Folder, FolderView};
+use crate::{
+    store::{store_folder, update_stored},
+    ClientVault, Folder, FolderCreateRequest, FolderDeleteRequest, FolderUpdateRequest, FolderView,
+};
 
 pub struct ClientFolders<'a> {
     pub(crate) client: &'a Client,
@@ -34,6 +38,62 @@ impl<'a> ClientFolders<'a> {
 
         Ok(views)
     }
+
+    pub async fn create(&self, input: &FolderCreateRequest) -> Result<FolderView, Error> {
+        let request = self.request_model(&input.name)?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response =
+            bitwarden_api_api::apis::folders_api::folders_post(&config.api, Some(request)).await?;
+
+        self.process_response(response.try_into()?)
+    }
+
+    pub async fn update(&self, input: &FolderUpdateRequest) -> Result<FolderView, Error> {
+        let request = self.request_model(&input.name)?;
+        let config = self.client.internal.get_api_configurations().await;
+
+        let response = bitwarden_api_api::apis::folders_api::folders_id_put(
+            &config.api,
+            &input.id.to_string(),
+            Some(request),
+        )
+        .await?;
+
+        self.process_response(response.try_into()?)
+    }
+
+    /// Delete a folder. The ciphers it contains are kept and no longer belong to any folder.
+    pub async fn delete(&self, input: &FolderDeleteRequest) -> Result<(), Error> {
+        let config = self.client.internal.get_api_configurations().await;
+        bitwarden_api_api::apis::folders_api::folders_id_delete(&config.api, &input.id.to_string())
+            .await?;
+
+        update_stored(self.client, |data| {
+            data.folders.retain(|f| f.id != Some(input.id));
+            data.ciphers
+                .iter_mut()
+                .filter(|c| c.folder_id == Some(input.id))
+                .for_each(|c| c.folder_id = None);
+        });
+
+        Ok(())
+    }
+
+    fn request_model(&self, name: &str) -> Result<FolderRequestModel, Error> {
+        let enc = self.client.internal.get_encryption_settings()?;
+        let key = enc.get_key(&None)?;
+
+        Ok(FolderRequestModel {
+            name: name.to_owned().encrypt_with_key(key)?.to_string(),
+        })
+    }
+
+    /// Store the folder returned by the server and decrypt it
+    fn process_response(&self, folder: Folder) -> Result<FolderView, Error> {
+        store_folder(self.client, &folder);
+        self.decrypt(folder)
+    }
 }
 
 impl<'a> ClientVault<'a> {
@@ -20,8 +20,8 @@ pub struct PasswordHistory {
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct PasswordHistoryView {
-    password: String,
-    last_used_date: DateTime<Utc>,
+    pub password: String,
+    pub last_used_date: DateTime<Utc>,
 }
 
 impl KeyEncryptable<SymmetricCryptoKey, PasswordHistory> for PasswordHistoryView {
@@ -55,3 +55,12 @@ impl TryFrom<CipherPasswordHistoryModel> for PasswordHistory {
         })
     }
 }
+
+impl From<PasswordHistory> for CipherPasswordHistoryModel {
+    fn from(history: PasswordHistory) -> Self {
+        Self {
+            password: history.password.to_string(),
+            last_used_date: history.last_used_date.to_rfc3339(),
+        }
+    }
+}
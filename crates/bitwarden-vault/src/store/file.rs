@@ -0,0 +1,136 @@
+use std::{
+    io::{ErrorKind, Write},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{VaultData, VaultStore, VaultStoreError};
+
+/// Version 2 added the organization keys
+const STORE_VERSION: u32 = 2;
+
+/// [VaultStore] keeping the encrypted vault in a JSON file, readable only by the current user.
+pub struct FileVaultStore {
+    path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoreFile<T> {
+    version: u32,
+    data: T,
+}
+
+impl FileVaultStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl VaultStore for FileVaultStore {
+    fn load(&self) -> Result<Option<VaultData>, VaultStoreError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let file: StoreFile<serde_json::Value> = serde_json::from_str(&content)?;
+        if file.version > STORE_VERSION {
+            return Err(VaultStoreError::UnsupportedVersion(file.version));
+        }
+        // Older files are missing data the current version needs, the next sync replaces them
+        if file.version < STORE_VERSION {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_value(file.data)?))
+    }
+
+    fn save(&self, data: &VaultData) -> Result<(), VaultStoreError> {
+        let content = serde_json::to_vec(&StoreFile {
+            version: STORE_VERSION,
+            data,
+        })?;
+
+        // Write to a temporary file first so an interrupted write never corrupts the store
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            let mut file = options.open(&tmp_path)?;
+            file.write_all(&content)?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), VaultStoreError> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data() -> VaultData {
+        VaultData {
+            user_id: "060000fb-0922-4dd3-b170-6e15cb5df8c8".parse().unwrap(),
+            revision_date: "2024-05-31T09:35:55.12Z".parse().unwrap(),
+            organization_keys: Default::default(),
+            ciphers: vec![],
+            folders: vec![],
+            collections: vec![],
+        }
+    }
+
+    #[test]
+    fn test_file_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileVaultStore::new(dir.path().join("vault.json"));
+
+        assert!(store.load().unwrap().is_none());
+
+        store.save(&test_data()).unwrap();
+        let data = store.load().unwrap().unwrap();
+        assert_eq!(data.user_id, test_data().user_id);
+        assert_eq!(data.revision_date, test_data().revision_date);
+
+        store.clear().unwrap();
+        assert!(store.load().unwrap().is_none());
+        // Clearing an empty store is not an error
+        store.clear().unwrap();
+    }
+
+    #[test]
+    fn test_file_store_unsupported_version() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("vault.json"), r#"{"version":3,"data":{}}"#).unwrap();
+
+        let store = FileVaultStore::new(dir.path().join("vault.json"));
+        assert!(matches!(
+            store.load(),
+            Err(VaultStoreError::UnsupportedVersion(3))
+        ));
+    }
+
+    #[test]
+    fn test_file_store_outdated_version() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("vault.json"), r#"{"version":1,"data":{}}"#).unwrap();
+
+        let store = FileVaultStore::new(dir.path().join("vault.json"));
+        assert!(store.load().unwrap().is_none());
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use bitwarden_core::Client;
+use bitwarden_crypto::AsymmetricEncString;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{Cipher, Collection, Folder};
+
+mod file;
+pub use file::FileVaultStore;
+
+#[derive(Debug, Error)]
+pub enum VaultStoreError {
+    #[error("No vault store has been configured")]
+    NotConfigured,
+    #[error("The vault has not been synced yet")]
+    NotSynced,
+    #[error("Unsupported vault store version {0}")]
+    UnsupportedVersion(u32),
+    #[error("The stored vault belongs to another account")]
+    OtherAccount,
+
+    #[error(transparent)]
+    Core(#[from] bitwarden_core::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+/// The encrypted vault as of the last sync. Items are stored exactly as they were received from
+/// the server, decrypting them still requires an unlocked client.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultData {
+    pub user_id: Uuid,
+    /// Account revision date reported by the server when the vault was synced
+    pub revision_date: DateTime<Utc>,
+    /// Keys of the organizations the user is a member of, encrypted with the user's private key.
+    /// Needed to decrypt the organization items when the vault is loaded without syncing.
+    pub organization_keys: HashMap<Uuid, AsymmetricEncString>,
+    pub ciphers: Vec<Cipher>,
+    pub folders: Vec<Folder>,
+    pub collections: Vec<Collection>,
+}
+
+impl VaultData {
+    /// Reject a vault stored by another account than the logged in user. Clients that don't hold
+    /// the access token don't know the user, in which case the vault is accepted.
+    pub(crate) fn check_account(self, user_id: Option<Uuid>) -> Result<Self, VaultStoreError> {
+        match user_id {
+            Some(user_id) if user_id != self.user_id => Err(VaultStoreError::OtherAccount),
+            _ => Ok(self),
+        }
+    }
+
+    /// Initialize the stored organization keys the client doesn't have yet. They're otherwise only
+    /// initialized by a full sync, which isn't needed to use the stored vault. Does nothing while
+    /// the client is locked.
+    pub(crate) fn initialize_org_crypto(&self, client: &Client) -> Result<(), VaultStoreError> {
+        let Ok(enc) = client.internal.get_encryption_settings() else {
+            return Ok(());
+        };
+        if self
+            .organization_keys
+            .keys()
+            .all(|id| enc.get_key(&Some(*id)).is_ok())
+        {
+            return Ok(());
+        }
+
+        client
+            .internal
+            .initialize_org_crypto(self.organization_keys.clone().into_iter().collect())
+            .map_err(bitwarden_core::Error::EncryptionSettings)?;
+        Ok(())
+    }
+}
+
+/// Persistent storage for the encrypted vault, enabled with
+/// [ClientVault::set_store](crate::ClientVault::set_store). Implement this trait to keep the vault
+/// in the platform's own storage, or use the bundled [FileVaultStore].
+pub trait VaultStore: Send + Sync {
+    /// Load the stored vault, `None` when nothing has been stored yet
+    fn load(&self) -> Result<Option<VaultData>, VaultStoreError>;
+    /// Replace the stored vault
+    fn save(&self, data: &VaultData) -> Result<(), VaultStoreError>;
+    /// Remove the stored vault, e.g. on logout
+    fn clear(&self) -> Result<(), VaultStoreError>;
+}
+
+/// Wrapper registered on the client, as the client only stores sized types
+pub(crate) struct RegisteredVaultStore(pub(crate) Box<dyn VaultStore>);
+
+/// Apply a change made on the server to the stored vault, so it stays current until the next sync.
+/// Does nothing when no store is configured or nothing has been synced yet.
+pub(crate) fn update_stored(client: &Client, update: impl FnOnce(&mut VaultData)) {
+    let Some(store) = client.internal.get_store::<RegisteredVaultStore>() else {
+        return;
+    };
+
+    // The change was already applied on the server, failing to store it locally shouldn't fail the
+    // request. The next sync replaces the stored vault anyway.
+    if let Ok(Some(mut data)) = store.0.load() {
+        update(&mut data);
+        _ = store.0.save(&data);
+    }
+}
+
+/// Insert or replace a cipher in the stored vault
+pub(crate) fn store_cipher(client: &Client, cipher: &Cipher) {
+    update_stored(client, |data| {
+        data.ciphers.retain(|c| c.id != cipher.id);
+        data.ciphers.push(cipher.clone());
+    });
+}
+
+/// Insert or replace a folder in the stored vault
+pub(crate) fn store_folder(client: &Client, folder: &Folder) {
+    update_stored(client, |data| {
+        data.folders.retain(|f| f.id != folder.id);
+        data.folders.push(folder.clone());
+    });
+}
+
+/// Insert or replace a collection in the stored vault
+pub(crate) fn store_collection(client: &Client, collection: &Collection) {
+    update_stored(client, |data| {
+        data.collections.retain(|c| c.id != collection.id);
+        data.collections.push(collection.clone());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_account() {
+        let user_id: Uuid = "060000fb-0922-4dd3-b170-6e15cb5df8c8".parse().unwrap();
+        let data = VaultData {
+            user_id,
+            revision_date: "2024-05-31T09:35:55.12Z".parse().unwrap(),
+            organization_keys: HashMap::new(),
+            ciphers: vec![],
+            folders: vec![],
+            collections: vec![],
+        };
+
+        let data = data.check_account(Some(user_id)).unwrap();
+        let data = data.check_account(None).unwrap();
+        assert!(matches!(
+            data.check_account(Some(Uuid::new_v4())),
+            Err(VaultStoreError::OtherAccount)
+        ));
+    }
+}
@@ -1,15 +1,24 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
 use bitwarden_api_api::models::{
     DomainsResponseModel, ProfileOrganizationResponseModel, ProfileResponseModel, SyncResponseModel,
 };
 use bitwarden_core::{
     client::encryption_settings::EncryptionSettings, require, Client, Error, MissingFieldError,
 };
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::{Cipher, Collection, Folder, GlobalDomains, VaultParseError};
+use crate::{
+    store::{RegisteredVaultStore, VaultData, VaultStoreError},
+    Cipher, Collection, Folder, GlobalDomains, VaultParseError,
+};
 
 #[derive(Debug, Error)]
 pub enum SyncError {
@@ -21,6 +30,9 @@ pub enum SyncError {
 
     #[error(transparent)]
     VaultParse(#[from] VaultParseError),
+
+    #[error(transparent)]
+    Store(#[from] VaultStoreError),
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -31,6 +43,72 @@ pub struct SyncRequest {
 }
 
 pub(crate) async fn sync(client: &Client, input: &SyncRequest) -> Result<SyncResponse, SyncError> {
+    let store = client.internal.get_store::<RegisteredVaultStore>();
+
+    // Fetched before syncing, so changes made during the sync are picked up by the next one
+    let store = match store {
+        Some(store) => Some((store, get_revision_date(client).await?)),
+        None => None,
+    };
+
+    full_sync(client, input, store).await
+}
+
+/// Only download the vault when the account revision date changed since the stored snapshot,
+/// reporting the differences to it. Requires a vault store.
+pub(crate) async fn sync_incremental(
+    client: &Client,
+    input: &SyncRequest,
+) -> Result<SyncResult, SyncError> {
+    let store = client
+        .internal
+        .get_store::<RegisteredVaultStore>()
+        .ok_or(VaultStoreError::NotConfigured)?;
+
+    // A snapshot written by another account is replaced by a full sync
+    let user_id = client.internal.get_user_id();
+    let previous = store.0.load()?.filter(|p| Some(p.user_id) == user_id);
+    let revision_date = get_revision_date(client).await?;
+
+    if let Some(previous) = &previous {
+        if previous.revision_date >= revision_date {
+            previous.initialize_org_crypto(client)?;
+
+            return Ok(SyncResult {
+                changed: false,
+                revision_date: previous.revision_date,
+                ciphers: SyncChanges::default(),
+                folders: SyncChanges::default(),
+                collections: SyncChanges::default(),
+            });
+        }
+    }
+
+    let response = full_sync(client, input, Some((store, revision_date))).await?;
+
+    let previous = previous.unwrap_or_else(|| VaultData {
+        user_id: response.profile.id,
+        revision_date,
+        organization_keys: HashMap::new(),
+        ciphers: Vec::new(),
+        folders: Vec::new(),
+        collections: Vec::new(),
+    });
+
+    Ok(SyncResult {
+        changed: true,
+        revision_date,
+        ciphers: SyncChanges::diff(&previous.ciphers, &response.ciphers, |c| c.id),
+        folders: SyncChanges::diff(&previous.folders, &response.folders, |f| f.id),
+        collections: SyncChanges::diff(&previous.collections, &response.collections, |c| c.id),
+    })
+}
+
+async fn full_sync(
+    client: &Client,
+    input: &SyncRequest,
+    store: Option<(Arc<RegisteredVaultStore>, DateTime<Utc>)>,
+) -> Result<SyncResponse, SyncError> {
     let config = client.internal.get_api_configurations().await;
     let sync = bitwarden_api_api::apis::sync_api::sync_get(&config.api, input.exclude_subdomains)
         .await
@@ -46,10 +124,96 @@ pub(crate) async fn sync(client: &Client, input: &SyncRequest) -> Result<SyncRes
 
     let enc = client
         .internal
-        .initialize_org_crypto(org_keys)
+        .initialize_org_crypto(org_keys.clone())
         .map_err(bitwarden_core::Error::EncryptionSettings)?;
 
-    SyncResponse::process_response(sync, &enc)
+    let response = SyncResponse::process_response(sync, &enc)?;
+
+    if let Some((store, revision_date)) = store {
+        store.0.save(&VaultData {
+            user_id: response.profile.id,
+            revision_date,
+            organization_keys: org_keys.into_iter().collect(),
+            ciphers: response.ciphers.clone(),
+            folders: response.folders.clone(),
+            collections: response.collections.clone(),
+        })?;
+    }
+
+    Ok(response)
+}
+
+/// Get the date of the last change to the account's vault from the server
+pub(crate) async fn get_revision_date(client: &Client) -> Result<DateTime<Utc>, SyncError> {
+    let config = client.internal.get_api_configurations().await;
+    let revision_date =
+        bitwarden_api_api::apis::accounts_api::accounts_revision_date_get(&config.api)
+            .await
+            .map_err(|e| SyncError::Core(e.into()))?;
+
+    DateTime::from_timestamp_millis(revision_date)
+        .ok_or_else(|| SyncError::Core("Invalid account revision date".into()))
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SyncResult {
+    /// False when the server reported no changes since the stored snapshot, in which case the
+    /// vault wasn't downloaded
+    pub changed: bool,
+    /// Account revision date of the stored snapshot
+    pub revision_date: DateTime<Utc>,
+    pub ciphers: SyncChanges,
+    pub folders: SyncChanges,
+    pub collections: SyncChanges,
+}
+
+/// Ids of the items that changed compared to the previously stored snapshot
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Default, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SyncChanges {
+    pub added: Vec<Uuid>,
+    pub modified: Vec<Uuid>,
+    pub removed: Vec<Uuid>,
+}
+
+impl SyncChanges {
+    fn diff<T: Serialize>(
+        previous: &[T],
+        current: &[T],
+        id: impl Fn(&T) -> Option<Uuid>,
+    ) -> SyncChanges {
+        // Items are compared by their encrypted contents, which only change when they are edited
+        let previous: HashMap<Uuid, serde_json::Value> = previous
+            .iter()
+            .filter_map(|i| Some((id(i)?, serde_json::to_value(i).ok()?)))
+            .collect();
+
+        let mut changes = SyncChanges::default();
+        let mut current_ids = HashSet::with_capacity(current.len());
+        for item in current {
+            let Some(item_id) = id(item) else {
+                continue;
+            };
+            current_ids.insert(item_id);
+
+            match previous.get(&item_id) {
+                None => changes.added.push(item_id),
+                Some(value) if serde_json::to_value(item).ok().as_ref() != Some(value) => {
+                    changes.modified.push(item_id)
+                }
+                Some(_) => {}
+            }
+        }
+
+        changes.removed = previous
+            .into_keys()
+            .filter(|i| !current_ids.contains(i))
+            .collect();
+        changes.removed.sort();
+
+        changes
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -152,6 +316,23 @@ impl ProfileResponse {
     }
 }
 
+impl DomainResponse {
+    /// All sets of domains the user considers equivalent, combining their custom rules with the
+    /// global rules they haven't excluded. Used when matching login URIs.
+    pub fn equivalent_domains(&self) -> Vec<Vec<String>> {
+        self.equivalent_domains
+            .iter()
+            .cloned()
+            .chain(
+                self.global_equivalent_domains
+                    .iter()
+                    .filter(|g| !g.excluded)
+                    .map(|g| g.domains.clone()),
+            )
+            .collect()
+    }
+}
+
 impl TryFrom<DomainsResponseModel> for DomainResponse {
     type Error = SyncError;
 
@@ -167,3 +348,79 @@ impl TryFrom<DomainsResponseModel> for DomainResponse {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Item {
+        id: Option<Uuid>,
+        name: &'static str,
+    }
+
+    #[test]
+    fn test_sync_changes_diff() {
+        let [a, b, c, d] = [
+            "6a73fa32-9a4d-4a93-9b5a-7e4c0b0b2a01",
+            "6a73fa32-9a4d-4a93-9b5a-7e4c0b0b2a02",
+            "6a73fa32-9a4d-4a93-9b5a-7e4c0b0b2a03",
+            "6a73fa32-9a4d-4a93-9b5a-7e4c0b0b2a04",
+        ]
+        .map(|id| Some(id.parse().unwrap()));
+
+        let previous = vec![
+            Item { id: a, name: "a" },
+            Item { id: b, name: "b" },
+            Item { id: c, name: "c" },
+        ];
+        let current = vec![
+            Item { id: a, name: "a" },
+            Item {
+                id: b,
+                name: "b (edited)",
+            },
+            Item { id: d, name: "d" },
+            Item {
+                id: None,
+                name: "e",
+            },
+        ];
+
+        assert_eq!(
+            SyncChanges::diff(&previous, &current, |i| i.id),
+            SyncChanges {
+                added: vec![d.unwrap()],
+                modified: vec![b.unwrap()],
+                removed: vec![c.unwrap()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_equivalent_domains_skip_excluded() {
+        let domains = DomainResponse {
+            equivalent_domains: vec![vec!["example.com".to_owned(), "example.net".to_owned()]],
+            global_equivalent_domains: vec![
+                GlobalDomains {
+                    r#type: 0,
+                    domains: vec!["google.com".to_owned(), "youtube.com".to_owned()],
+                    excluded: false,
+                },
+                GlobalDomains {
+                    r#type: 1,
+                    domains: vec!["apple.com".to_owned(), "icloud.com".to_owned()],
+                    excluded: true,
+                },
+            ],
+        };
+
+        assert_eq!(
+            domains.equivalent_domains(),
+            vec![
+                vec!["example.com".to_owned(), "example.net".to_owned()],
+                vec!["google.com".to_owned(), "youtube.com".to_owned()],
+            ]
+        );
+    }
+}
@@ -348,6 +348,7 @@ mod tests {
             r#type: CipherListViewType::Login {
                 has_fido2: true,
                 totp: Some("2.hqdioUAc81FsKQmO1XuLQg==|oDRdsJrQjoFu9NrFVy8tcJBAFKBx95gHaXZnWdXbKpsxWnOr2sKipIG43pKKUFuq|3gKZMiboceIB5SLVOULKg2iuyu6xzos22dfJbvx0EHk=".parse().unwrap()),
+                uris: None,
             },
             favorite: false,
             reprompt: CipherRepromptType::None,
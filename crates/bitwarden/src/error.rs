@@ -3,7 +3,7 @@
 use std::{borrow::Cow, fmt::Debug};
 
 #[cfg(feature = "internal")]
-use bitwarden_exporters::ExportError;
+use bitwarden_exporters::{ExportError, ImportError};
 #[cfg(feature = "internal")]
 use bitwarden_generators::{PassphraseError, PasswordError, UsernameError};
 use thiserror::Error;
@@ -30,11 +30,17 @@ pub enum Error {
     Cipher(#[from] bitwarden_vault::CipherError),
     #[cfg(feature = "internal")]
     #[error(transparent)]
+    Attachment(#[from] bitwarden_vault::AttachmentError),
+    #[cfg(feature = "internal")]
+    #[error(transparent)]
     Totp(#[from] bitwarden_vault::TotpError),
 
     #[cfg(feature = "internal")]
     #[error(transparent)]
     ExportError(#[from] ExportError),
+    #[cfg(feature = "internal")]
+    #[error(transparent)]
+    ImportError(#[from] ImportError),
 
     // Fido
     #[cfg(all(feature = "uniffi", feature = "internal"))]
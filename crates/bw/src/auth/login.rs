@@ -93,14 +93,18 @@ pub(crate) async fn login_password(client: Client, email: Option<String>) -> Res
 }
 
 pub(crate) async fn login_api_key(
-    client: Client,
+    client: &Client,
     client_id: Option<String>,
     client_secret: Option<String>,
+    password: Option<String>,
 ) -> Result<()> {
     let client_id = text_prompt_when_none("Client ID", client_id)?;
     let client_secret = text_prompt_when_none("Client Secret", client_secret)?;
 
-    let password = Password::new("Password").without_confirmation().prompt()?;
+    let password = match password {
+        Some(password) => password,
+        None => Password::new("Password").without_confirmation().prompt()?,
+    };
 
     let result = client
         .auth()
@@ -112,6 +116,9 @@ pub(crate) async fn login_api_key(
         .await?;
 
     debug!("{:?}", result);
+    if !result.authenticated {
+        bail!("Login failed");
+    }
 
     Ok(())
 }
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bitwarden::{
     auth::RegisterRequest,
     generators::{ClientGeneratorExt, PassphraseGeneratorRequest, PasswordGeneratorRequest},
@@ -8,9 +10,12 @@ use clap::{command, Args, CommandFactory, Parser, Subcommand};
 use color_eyre::eyre::Result;
 use inquire::Password;
 use render::Output;
+use uuid::Uuid;
 
 mod auth;
 mod render;
+mod send;
+mod vault;
 
 #[derive(Parser, Clone)]
 #[command(name = "Bitwarden CLI", version, about = "Bitwarden CLI", long_about = None)]
@@ -45,18 +50,36 @@ enum Commands {
 
     #[command(long_about = "Manage vault items")]
     Item {
+        #[command(flatten)]
+        vault: VaultArgs,
+
         #[command(subcommand)]
         command: ItemCommands,
     },
 
     #[command(long_about = "Pull the latest vault data from the server")]
-    Sync {},
+    Sync {
+        #[command(flatten)]
+        vault: VaultArgs,
+
+        #[arg(
+            long,
+            help = "Download the vault even when it didn't change since the last sync"
+        )]
+        force: bool,
+    },
 
     #[command(long_about = "Password and passphrase generators")]
     Generate {
         #[command(subcommand)]
         command: GeneratorCommands,
     },
+
+    #[command(long_about = "Work with Bitwarden Sends")]
+    Send {
+        #[command(subcommand)]
+        command: SendCommands,
+    },
 }
 
 #[derive(Args, Clone)]
@@ -68,6 +91,48 @@ struct LoginArgs {
     server: Option<String>,
 }
 
+/// The vault commands log in with a personal API key on every invocation, and read the vault from
+/// the file written by `bw sync`
+#[derive(Args, Clone)]
+struct VaultArgs {
+    #[arg(
+        long,
+        global = true,
+        env = "BW_CLIENTID",
+        help = "Client ID of the personal API key"
+    )]
+    client_id: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "BW_CLIENTSECRET",
+        hide_env_values = true,
+        help = "Client secret of the personal API key"
+    )]
+    client_secret: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "BW_PASSWORD",
+        hide_env_values = true,
+        help = "Master password, prompted for when not set"
+    )]
+    password: Option<String>,
+
+    #[arg(short = 's', long, global = true, help = "Server URL")]
+    server: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "BW_VAULT_FILE",
+        help = "Where the vault is stored between invocations, defaults to ~/.config/bw/vault.json"
+    )]
+    vault_file: Option<PathBuf>,
+}
+
 #[derive(Subcommand, Clone)]
 enum LoginCommands {
     Password {
@@ -87,8 +152,39 @@ enum LoginCommands {
 
 #[derive(Subcommand, Clone)]
 enum ItemCommands {
-    Get { id: String },
-    Create {},
+    #[command(long_about = "List the items of the stored vault")]
+    List {},
+    #[command(long_about = "Get an item of the stored vault")]
+    Get { id: Uuid },
+    #[command(long_about = "Create an item from the JSON printed by `bw item get`")]
+    Create {
+        #[arg(help = "File containing the item, read from stdin when omitted")]
+        file: Option<PathBuf>,
+    },
+    #[command(long_about = "Replace an item with the JSON printed by `bw item get`")]
+    Edit {
+        id: Uuid,
+
+        #[arg(help = "File containing the item, read from stdin when omitted")]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum SendCommands {
+    #[command(long_about = "Access a Send from its share link, no login required")]
+    Receive {
+        url: String,
+
+        #[arg(long, help = "Password of the Send, prompted for when required")]
+        password: Option<String>,
+
+        #[arg(
+            long,
+            help = "Where to save a file Send, defaults to the sent file name"
+        )]
+        path: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand, Clone)]
@@ -167,7 +263,7 @@ async fn process_commands() -> Result<()> {
                 LoginCommands::ApiKey {
                     client_id,
                     client_secret,
-                } => auth::login_api_key(client, client_id, client_secret).await?,
+                } => auth::login_api_key(&client, client_id, client_secret, None).await?,
                 LoginCommands::Device {
                     email,
                     device_identifier,
@@ -203,6 +299,36 @@ async fn process_commands() -> Result<()> {
                 })
                 .await?;
         }
+        Commands::Send {
+            command:
+                SendCommands::Receive {
+                    url,
+                    password,
+                    path,
+                },
+        } => {
+            let client = bitwarden::Client::new(None);
+            send::receive(client, url, password, path).await?;
+            return Ok(());
+        }
+        Commands::Item {
+            vault: args,
+            command,
+        } => {
+            let client = vault::login(args).await?;
+            match command {
+                ItemCommands::List {} => vault::list_items(&client)?,
+                ItemCommands::Get { id } => vault::get_item(&client, id)?,
+                ItemCommands::Create { file } => vault::create_item(&client, file).await?,
+                ItemCommands::Edit { id, file } => vault::edit_item(&client, id, file).await?,
+            }
+            return Ok(());
+        }
+        Commands::Sync { vault: args, force } => {
+            let client = vault::login(args).await?;
+            vault::sync(&client, force).await?;
+            return Ok(());
+        }
         _ => {}
     }
 
@@ -213,8 +339,9 @@ async fn process_commands() -> Result<()> {
     match command {
         Commands::Login(_) => unreachable!(),
         Commands::Register { .. } => unreachable!(),
-        Commands::Item { command: _ } => todo!(),
-        Commands::Sync {} => todo!(),
+        Commands::Send { .. } => unreachable!(),
+        Commands::Item { .. } => unreachable!(),
+        Commands::Sync { .. } => unreachable!(),
         Commands::Generate { command } => match command {
             GeneratorCommands::Password(args) => {
                 let password = client.generator().password(PasswordGeneratorRequest {
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use bitwarden::{
+    send::{ClientSendsExt, SendError, SendLink, SendType},
+    Client,
+};
+use color_eyre::eyre::{eyre, Result};
+use inquire::Password;
+use log::info;
+
+/// Open a Send from its share link, printing text Sends and saving file Sends to `path`, or to
+/// the name of the sent file when no path is given
+pub(crate) async fn receive(
+    client: Client,
+    url: String,
+    mut password: Option<String>,
+    path: Option<PathBuf>,
+) -> Result<()> {
+    let link = SendLink::parse(&url)?;
+    let send = loop {
+        match client.sends().receive(&link, password.as_deref()).await {
+            Err(SendError::PasswordRequired) if password.is_none() => {
+                password = Some(Password::new("Password").without_confirmation().prompt()?);
+            }
+            result => break result?,
+        }
+    };
+
+    match send.r#type {
+        SendType::Text => {
+            let text = send.text.and_then(|t| t.text).unwrap_or_default();
+            println!("{}", text);
+        }
+        SendType::File => {
+            let path = match path {
+                Some(path) => path,
+                // The file name is chosen by the sender, never let it point outside of the
+                // current directory
+                None => send
+                    .file
+                    .as_ref()
+                    .and_then(|f| Path::new(&f.file_name).file_name())
+                    .map(PathBuf::from)
+                    .ok_or_else(|| eyre!("The Send has no file name"))?,
+            };
+
+            client
+                .sends()
+                .receive_file(&link, &send, password.as_deref(), &path)
+                .await?;
+            info!("Saved {} to {}", send.name, path.display());
+        }
+    }
+
+    Ok(())
+}
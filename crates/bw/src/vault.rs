@@ -0,0 +1,129 @@
+use std::{io::Read, path::PathBuf};
+
+use bitwarden::{
+    vault::{CipherView, ClientVaultExt, FileVaultStore, SyncRequest},
+    Client, ClientSettings,
+};
+use color_eyre::eyre::{bail, eyre, Result};
+use directories::BaseDirs;
+use log::info;
+use uuid::Uuid;
+
+use crate::{auth, VaultArgs};
+
+const DEFAULT_VAULT_FILE: &str = ".config/bw/vault.json";
+
+/// Log in with the personal API key and use the vault stored by previous invocations, so reading
+/// items doesn't require syncing
+pub(crate) async fn login(args: VaultArgs) -> Result<Client> {
+    let settings = args.server.map(|server| ClientSettings {
+        api_url: format!("{}/api", server),
+        identity_url: format!("{}/identity", server),
+        ..Default::default()
+    });
+    let client = Client::new(settings);
+
+    auth::login_api_key(&client, args.client_id, args.client_secret, args.password).await?;
+
+    let vault_file = get_vault_file(args.vault_file)?;
+    client
+        .vault()
+        .set_store(Some(Box::new(FileVaultStore::new(vault_file))));
+
+    Ok(client)
+}
+
+fn get_vault_file(vault_file: Option<PathBuf>) -> Result<PathBuf> {
+    let vault_file = match vault_file {
+        Some(path) => path,
+        None => {
+            let Some(base_dirs) = BaseDirs::new() else {
+                bail!("A valid home directory doesn't exist");
+            };
+            base_dirs.home_dir().join(DEFAULT_VAULT_FILE)
+        }
+    };
+
+    if let Some(parent_folder) = vault_file.parent() {
+        std::fs::create_dir_all(parent_folder)?;
+    }
+
+    Ok(vault_file)
+}
+
+/// Sync the stored vault, only downloading it when it changed unless `force` is set
+pub(crate) async fn sync(client: &Client, force: bool) -> Result<()> {
+    let request = SyncRequest {
+        exclude_subdomains: Some(true),
+    };
+
+    if force {
+        client.vault().sync(&request).await?;
+        info!("Synced the vault");
+        return Ok(());
+    }
+
+    let result = client.vault().sync_incremental(&request).await?;
+    if result.changed {
+        info!(
+            "Synced the vault, {} items added, {} modified and {} removed",
+            result.ciphers.added.len(),
+            result.ciphers.modified.len(),
+            result.ciphers.removed.len()
+        );
+    } else {
+        info!("The vault is up to date");
+    }
+
+    Ok(())
+}
+
+pub(crate) fn list_items(client: &Client) -> Result<()> {
+    let ciphers = client.vault().list_ciphers()?;
+    let items = client.vault().ciphers().decrypt_list(ciphers)?;
+
+    println!("{}", serde_json::to_string_pretty(&items)?);
+    Ok(())
+}
+
+pub(crate) fn get_item(client: &Client, id: Uuid) -> Result<()> {
+    let cipher = client
+        .vault()
+        .get_cipher(id)?
+        .ok_or_else(|| eyre!("Item {id} was not found, run `bw sync` if it was just created"))?;
+    let item = client.vault().ciphers().decrypt(cipher)?;
+
+    println!("{}", serde_json::to_string_pretty(&item)?);
+    Ok(())
+}
+
+pub(crate) async fn create_item(client: &Client, file: Option<PathBuf>) -> Result<()> {
+    let mut item = read_item(file)?;
+    item.id = None;
+    let item = client.vault().ciphers().create(item).await?;
+
+    println!("{}", serde_json::to_string_pretty(&item)?);
+    Ok(())
+}
+
+pub(crate) async fn edit_item(client: &Client, id: Uuid, file: Option<PathBuf>) -> Result<()> {
+    let mut item = read_item(file)?;
+    item.id = Some(id);
+    let item = client.vault().ciphers().update(item).await?;
+
+    println!("{}", serde_json::to_string_pretty(&item)?);
+    Ok(())
+}
+
+fn read_item(file: Option<PathBuf>) -> Result<CipherView> {
+    let content = match file {
+        Some(file) => std::fs::read_to_string(file)?,
+        None => {
+            let mut buffer = String::new();
+            std::io::stdin().read_to_string(&mut buffer)?;
+            buffer
+        }
+    };
+
+    Ok(serde_json::from_str(&content)?)
+}
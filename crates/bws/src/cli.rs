@@ -22,6 +22,8 @@ pub(crate) enum ProfileKey {
     server_identity,
     state_dir,
     state_opt_out,
+    secrets_cache,
+    secrets_cache_max_staleness,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -35,6 +37,37 @@ pub(crate) enum Output {
     None,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub(crate) enum OnChange {
+    Restart,
+    Signal,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+pub(crate) enum Signal {
+    HUP,
+    INT,
+    QUIT,
+    TERM,
+    USR1,
+    USR2,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub(crate) enum ImportFormat {
+    Dotenv,
+    Json,
+    Yaml,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub(crate) enum GranteeType {
+    User,
+    Group,
+    ServiceAccount,
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "bws", version, about = "Bitwarden Secrets CLI", long_about = None)]
 pub(crate) struct Cli {
@@ -91,6 +124,47 @@ pub(crate) enum Commands {
         #[command(subcommand)]
         cmd: SecretCommand,
     },
+    #[command(long_about = "Commands available on Service Accounts")]
+    ServiceAccount {
+        #[command(subcommand)]
+        cmd: ServiceAccountCommand,
+    },
+    #[command(long_about = "List the events of a Service Account, like the secrets it retrieved")]
+    Events {
+        service_account_id: Uuid,
+        #[arg(
+            long,
+            help = "Only list events on or after this RFC 3339 date. Defaults to 30 days before --end"
+        )]
+        start: Option<String>,
+        #[arg(
+            long,
+            help = "Only list events on or before this RFC 3339 date. Defaults to now"
+        )]
+        end: Option<String>,
+    },
+    #[command(
+        long_about = "Export all the projects and secrets of the organization as a JSON document"
+    )]
+    Export {
+        #[arg(
+            long,
+            help = "Write the export to this file, readable only by the current user, instead of printing it"
+        )]
+        output_file: Option<PathBuf>,
+    },
+    #[command(
+        long_about = "Import projects and secrets from a JSON document created by `bws export`"
+    )]
+    Import {
+        #[arg(help = "The export file to import")]
+        file: PathBuf,
+    },
+    #[command(long_about = "Commands available on Templates")]
+    Template {
+        #[command(subcommand)]
+        cmd: TemplateCommand,
+    },
     #[command(long_about = "Run a command with secrets injected")]
     Run {
         #[arg(help = "The command to run")]
@@ -111,6 +185,40 @@ pub(crate) enum Commands {
             help = "Use the secret UUID (in its POSIX form) instead of the key name for the environment variable"
         )]
         uuids_as_keynames: bool,
+        #[arg(
+            long,
+            help = "Keep checking for secret changes and restart or signal the command when they change"
+        )]
+        watch: bool,
+        #[arg(
+            long,
+            default_value_t = 30,
+            requires = "watch",
+            help = "The number of seconds to wait between checks for changes"
+        )]
+        watch_interval: u64,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = OnChange::Restart,
+            requires = "watch",
+            help = "What to do with the command when a secret changes"
+        )]
+        on_change: OnChange,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = Signal::HUP,
+            requires = "watch",
+            help = "The signal sent to the command when using `--on-change signal`"
+        )]
+        signal: Signal,
+        #[arg(
+            long,
+            requires = "watch",
+            help = "The maximum number of times the command is restarted, unlimited by default"
+        )]
+        max_restarts: Option<u32>,
     },
 }
 
@@ -147,6 +255,67 @@ pub(crate) enum SecretCommand {
     List {
         project_id: Option<Uuid>,
     },
+    #[command(
+        long_about = "Watch for secret changes, printing added, changed and removed secrets as JSON lines, or as an environment diff with `--output env`"
+    )]
+    Watch {
+        #[arg(long, help = "The ID of the project to limit the watched secrets to")]
+        project_id: Option<Uuid>,
+        #[arg(
+            long,
+            default_value_t = 30,
+            help = "The number of seconds to wait between checks for changes"
+        )]
+        interval: u64,
+    },
+    #[command(
+        long_about = "Create, update and optionally delete the secrets of a project to match a dotenv, JSON or YAML file"
+    )]
+    Import {
+        #[arg(help = "The file containing the secret names and values")]
+        file: PathBuf,
+        #[arg(
+            long = "project",
+            help = "The ID of the project to import the secrets into"
+        )]
+        project_id: Uuid,
+        #[arg(
+            long,
+            value_enum,
+            help = "The format of the file, detected from its extension by default"
+        )]
+        format: Option<ImportFormat>,
+        #[arg(long, help = "Delete secrets of the project that are not in the file")]
+        delete_missing: bool,
+        #[arg(long, help = "Only show the changes that would be made")]
+        dry_run: bool,
+    },
+    #[command(long_about = "Commands available on deleted Secrets")]
+    Trash {
+        #[command(subcommand)]
+        cmd: SecretTrashCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum SecretTrashCommand {
+    List,
+    Restore {
+        #[arg(required = true)]
+        secret_ids: Vec<Uuid>,
+    },
+    #[clap(group = ArgGroup::new("secrets").required(true))]
+    #[command(long_about = "Permanently delete secrets from the trash")]
+    Empty {
+        #[arg(group = "secrets")]
+        secret_ids: Vec<Uuid>,
+        #[arg(
+            long,
+            group = "secrets",
+            help = "Permanently delete every secret in the trash"
+        )]
+        all: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -166,4 +335,117 @@ pub(crate) enum ProjectCommand {
         project_id: Uuid,
     },
     List,
+    #[command(long_about = "Commands available on the access policies of a Project")]
+    Access {
+        #[command(subcommand)]
+        cmd: ProjectAccessCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ProjectAccessCommand {
+    #[clap(group = ArgGroup::new("permission").required(true).multiple(true))]
+    #[command(
+        long_about = "Grant a user, group or service account access to a project, replacing its existing access"
+    )]
+    Grant {
+        project_id: Uuid,
+        #[arg(help = "The ID of the grantee. For users, this is their organization user ID")]
+        grantee_id: Uuid,
+        #[arg(long = "type", value_enum, help = "The type of the grantee")]
+        grantee_type: GranteeType,
+        #[arg(
+            long,
+            group = "permission",
+            help = "Allow reading the project's secrets"
+        )]
+        read: bool,
+        #[arg(
+            long,
+            group = "permission",
+            help = "Allow modifying the project and its secrets, implies --read"
+        )]
+        write: bool,
+    },
+    List {
+        project_id: Uuid,
+    },
+    Revoke {
+        project_id: Uuid,
+        grantee_id: Uuid,
+        #[arg(long = "type", value_enum, help = "The type of the grantee")]
+        grantee_type: GranteeType,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ServiceAccountCommand {
+    Create {
+        name: String,
+    },
+    Delete {
+        service_account_ids: Vec<Uuid>,
+    },
+    Edit {
+        service_account_id: Uuid,
+        #[arg(long, group = "edit_field")]
+        name: String,
+    },
+    Get {
+        service_account_id: Uuid,
+    },
+    List,
+    #[command(long_about = "Commands available on the access tokens of a Service Account")]
+    AccessToken {
+        #[command(subcommand)]
+        cmd: AccessTokenCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum AccessTokenCommand {
+    #[command(
+        long_about = "Create a new access token. The access token is only shown once, store it right away"
+    )]
+    Create {
+        service_account_id: Uuid,
+        #[arg(long, help = "The name of the access token")]
+        name: String,
+        #[arg(
+            long,
+            help = "The date and time, in RFC 3339 format, after which the access token expires. Never expires by default"
+        )]
+        expires_at: Option<String>,
+    },
+    List {
+        service_account_id: Uuid,
+    },
+    Revoke {
+        service_account_id: Uuid,
+        access_token_ids: Vec<Uuid>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum TemplateCommand {
+    #[command(
+        long_about = "Render a template, replacing `{{ secret \"KEY\" }}` and `{{ secret_id \"ID\" }}` tags with the value of the referenced secret"
+    )]
+    Render {
+        #[arg(help = "The template file to render")]
+        file: PathBuf,
+        #[arg(
+            long,
+            help = "Write the rendered template to this file instead of printing it"
+        )]
+        destination: Option<PathBuf>,
+        #[arg(
+            long,
+            default_value = "600",
+            help = "The permissions, in octal, of the rendered file on Unix systems"
+        )]
+        mode: String,
+        #[arg(long, help = "The ID of the project to look up secret names in")]
+        project_id: Option<Uuid>,
+    },
 }
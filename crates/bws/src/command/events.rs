@@ -0,0 +1,48 @@
+use bitwarden::{
+    secrets_manager::{events::ServiceAccountEventsRequest, ClientEventsExt},
+    Client,
+};
+use color_eyre::eyre::{bail, eyre, Result};
+use uuid::Uuid;
+
+use crate::{
+    render::{serialize_response, OutputSettings},
+    util::parse_date,
+};
+
+pub(crate) async fn list(
+    client: Client,
+    service_account_id: Uuid,
+    start: Option<String>,
+    end: Option<String>,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let start = start
+        .map(|date| parse_date(&date))
+        .transpose()
+        .map_err(|e| eyre!(e))?;
+    let end = end
+        .map(|date| parse_date(&date))
+        .transpose()
+        .map_err(|e| eyre!(e))?;
+
+    if let (Some(start), Some(end)) = (start, end) {
+        if start > end {
+            bail!("--start must be before --end");
+        }
+    }
+
+    let events = client
+        .events()
+        .list_all_service_account(&ServiceAccountEventsRequest {
+            service_account_id,
+            start,
+            end,
+            continuation_token: None,
+        })
+        .await?
+        .data;
+    serialize_response(events, output_settings);
+
+    Ok(())
+}
@@ -1,6 +1,11 @@
+pub(crate) mod events;
+pub(crate) mod porting;
 pub(crate) mod project;
 pub(crate) mod run;
 pub(crate) mod secret;
+pub(crate) mod secret_import;
+pub(crate) mod service_account;
+pub(crate) mod template;
 
 use std::{path::PathBuf, str::FromStr};
 
@@ -56,6 +61,22 @@ pub(crate) fn config(
                     (ProfileKey::state_opt_out, value)
                 }
             }
+            (Some(ProfileKey::secrets_cache), Some(value)) => {
+                if util::string_to_bool(value.as_str()).is_err() {
+                    bail!("Profile key \"secrets_cache\" must be \"true\" or \"false\"");
+                } else {
+                    (ProfileKey::secrets_cache, value)
+                }
+            }
+            (Some(ProfileKey::secrets_cache_max_staleness), Some(value)) => {
+                if value.parse::<u64>().is_err() {
+                    bail!(
+                        "Profile key \"secrets_cache_max_staleness\" must be a number of seconds"
+                    );
+                } else {
+                    (ProfileKey::secrets_cache_max_staleness, value)
+                }
+            }
             (Some(name), Some(value)) => (name, value),
         };
 
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use bitwarden::{
+    secrets_manager::{
+        porting::{
+            SecretsManagerExport, SecretsManagerExportRequest, SecretsManagerImportRequest,
+            SECRETS_MANAGER_EXPORT_VERSION,
+        },
+        ClientSecretsExt,
+    },
+    Client,
+};
+use color_eyre::eyre::{bail, Result};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::command::template::write_restricted;
+
+#[derive(Deserialize)]
+struct ExportVersion {
+    version: u32,
+}
+
+pub(crate) async fn export(
+    client: Client,
+    organization_id: Uuid,
+    output_file: Option<PathBuf>,
+) -> Result<()> {
+    let export = client
+        .secrets()
+        .export(&SecretsManagerExportRequest { organization_id })
+        .await?;
+
+    let serialized = serde_json::to_string_pretty(&export)?;
+
+    match output_file {
+        Some(path) => {
+            write_restricted(&path, serialized.as_bytes(), 0o600)?;
+            eprintln!(
+                "Exported {} projects and {} secrets to {}",
+                export.projects.len(),
+                export.secrets.len(),
+                path.display()
+            );
+        }
+        None => println!("{}", serialized),
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn import(client: Client, organization_id: Uuid, file: PathBuf) -> Result<()> {
+    let content = std::fs::read_to_string(&file)?;
+
+    // Check the version first, to give a clear error instead of a parsing error for documents
+    // created by a newer version of the CLI
+    match serde_json::from_str::<ExportVersion>(&content) {
+        Ok(ExportVersion { version }) if version == SECRETS_MANAGER_EXPORT_VERSION => {}
+        Ok(ExportVersion { version }) => bail!(
+            "Unsupported export version {}, this version of bws supports version {}",
+            version,
+            SECRETS_MANAGER_EXPORT_VERSION
+        ),
+        Err(e) => bail!("'{}' is not a valid export file: {}", file.display(), e),
+    }
+
+    let data: SecretsManagerExport = serde_json::from_str(&content)?;
+    let (projects, secrets) = (data.projects.len(), data.secrets.len());
+
+    client
+        .secrets()
+        .import(&SecretsManagerImportRequest {
+            organization_id,
+            data,
+        })
+        .await?;
+
+    println!("Imported {} projects and {} secrets.", projects, secrets);
+
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use bitwarden::{
     secrets_manager::{
+        access_policies::{
+            AccessPolicyGranteeType, ProjectAccessGrantRequest, ProjectAccessPoliciesGetRequest,
+            ProjectAccessRevokeRequest,
+        },
         projects::{
             ProjectCreateRequest, ProjectGetRequest, ProjectPutRequest, ProjectsDeleteRequest,
             ProjectsListRequest,
         },
-        ClientProjectsExt,
+        ClientAccessPoliciesExt, ClientProjectsExt,
     },
     Client,
 };
@@ -13,7 +17,7 @@ use uuid::Uuid;
 
 use crate::{
     render::{serialize_response, OutputSettings},
-    ProjectCommand,
+    GranteeType, ProjectAccessCommand, ProjectCommand,
 };
 
 pub(crate) async fn process_command(
@@ -32,6 +36,49 @@ pub(crate) async fn process_command(
             edit(client, organization_id, project_id, name, output_settings).await
         }
         ProjectCommand::Delete { project_ids } => delete(client, project_ids).await,
+        ProjectCommand::Access { cmd } => match cmd {
+            ProjectAccessCommand::List { project_id } => {
+                list_access(client, organization_id, project_id, output_settings).await
+            }
+            ProjectAccessCommand::Grant {
+                project_id,
+                grantee_id,
+                grantee_type,
+                read,
+                write,
+            } => {
+                grant_access(
+                    client,
+                    ProjectAccessGrantRequest {
+                        organization_id,
+                        project_id,
+                        grantee_id,
+                        grantee_type: grantee_type.into(),
+                        read: read || write,
+                        write,
+                    },
+                    output_settings,
+                )
+                .await
+            }
+            ProjectAccessCommand::Revoke {
+                project_id,
+                grantee_id,
+                grantee_type,
+            } => {
+                revoke_access(
+                    client,
+                    ProjectAccessRevokeRequest {
+                        organization_id,
+                        project_id,
+                        grantee_id,
+                        grantee_type: grantee_type.into(),
+                    },
+                    output_settings,
+                )
+                .await
+            }
+        },
     }
 }
 
@@ -139,3 +186,58 @@ pub(crate) async fn delete(client: Client, project_ids: Vec<Uuid>) -> Result<()>
 
     Ok(())
 }
+
+impl From<GranteeType> for AccessPolicyGranteeType {
+    fn from(grantee_type: GranteeType) -> Self {
+        match grantee_type {
+            GranteeType::User => AccessPolicyGranteeType::User,
+            GranteeType::Group => AccessPolicyGranteeType::Group,
+            GranteeType::ServiceAccount => AccessPolicyGranteeType::ServiceAccount,
+        }
+    }
+}
+
+pub(crate) async fn list_access(
+    client: Client,
+    organization_id: Uuid,
+    project_id: Uuid,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let policies = client
+        .access_policies()
+        .get_project(&ProjectAccessPoliciesGetRequest {
+            organization_id,
+            project_id,
+        })
+        .await?
+        .data;
+    serialize_response(policies, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn grant_access(
+    client: Client,
+    request: ProjectAccessGrantRequest,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let policies = client.access_policies().grant_project(&request).await?.data;
+    serialize_response(policies, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn revoke_access(
+    client: Client,
+    request: ProjectAccessRevokeRequest,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let policies = client
+        .access_policies()
+        .revoke_project(&request)
+        .await?
+        .data;
+    serialize_response(policies, output_settings);
+
+    Ok(())
+}
@@ -1,29 +1,56 @@
 use std::{
     collections::HashMap,
     io::{IsTerminal, Read},
-    process,
+    process::Stdio,
+    time::{Duration, Instant},
 };
 
 use bitwarden::{
     secrets_manager::{
-        secrets::{SecretIdentifiersByProjectRequest, SecretIdentifiersRequest, SecretsGetRequest},
+        secrets::{
+            SecretIdentifiersByProjectRequest, SecretIdentifiersRequest, SecretResponse,
+            SecretsGetRequest, SecretsSyncRequest, SecretsWatchRequest,
+        },
         ClientSecretsExt,
     },
     Client,
 };
 use color_eyre::eyre::{bail, Result};
 use itertools::Itertools;
+use tokio::process::{Child, Command};
 use uuid::Uuid;
 use which::which;
 
 use crate::{
     util::{is_valid_posix_name, uuid_to_posix},
-    ACCESS_TOKEN_KEY_VAR_NAME,
+    OnChange, Signal, ACCESS_TOKEN_KEY_VAR_NAME,
 };
 
 // Essential environment variables that should be preserved even when `--no-inherit-env` is used
 const WINDOWS_ESSENTIAL_VARS: &[&str] = &["SystemRoot", "ComSpec", "windir"];
 
+// How long a restarted process gets to exit after being asked to terminate, before it's killed
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(10);
+// Restart delays double on every consecutive restart, up to this value
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(64);
+// A process that stays up for this long resets the restart delay
+const RESTART_DELAY_RESET: Duration = Duration::from_secs(300);
+
+pub(crate) struct RunWatchSettings {
+    pub(crate) interval: u64,
+    pub(crate) on_change: OnChange,
+    pub(crate) signal: Signal,
+    pub(crate) max_restarts: Option<u32>,
+}
+
+struct ChildSettings {
+    shell: String,
+    user_command: String,
+    no_inherit_env: bool,
+    uuids_as_keynames: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn run(
     client: Client,
     organization_id: Uuid,
@@ -32,6 +59,7 @@ pub(crate) async fn run(
     no_inherit_env: bool,
     shell: Option<String>,
     command: Vec<String>,
+    watch: Option<RunWatchSettings>,
 ) -> Result<i32> {
     let is_windows = std::env::consts::OS == "windows";
 
@@ -47,6 +75,14 @@ pub(crate) async fn run(
         bail!("Shell '{}' not found", shell);
     }
 
+    if !cfg!(unix)
+        && watch
+            .as_ref()
+            .is_some_and(|w| w.on_change == OnChange::Signal)
+    {
+        bail!("`--on-change signal` is only supported on Unix systems");
+    }
+
     let user_command = if command.is_empty() {
         if std::io::stdin().is_terminal() {
             bail!("No command provided");
@@ -59,6 +95,47 @@ pub(crate) async fn run(
         command.join(" ")
     };
 
+    let settings = ChildSettings {
+        shell,
+        user_command,
+        no_inherit_env,
+        uuids_as_keynames,
+    };
+
+    match watch {
+        Some(watch) => run_watch(client, organization_id, project_id, settings, watch).await,
+        None => {
+            let secrets = get_secrets(&client, organization_id, project_id).await?;
+            let mut child = spawn(&settings, build_environment(secrets, uuids_as_keynames)?)?;
+
+            wait(&mut child).await
+        }
+    }
+}
+
+async fn get_secrets(
+    client: &Client,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+) -> Result<Vec<SecretResponse>> {
+    // The secrets cache is only updated by syncs, so use them when the cache is enabled
+    if client.internal.is_secrets_cache_enabled() {
+        let secrets = client
+            .secrets()
+            .sync(&SecretsSyncRequest {
+                organization_id,
+                last_synced_date: None,
+            })
+            .await?
+            .secrets
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| project_id.is_none() || s.project_id == project_id)
+            .collect();
+
+        return Ok(secrets);
+    }
+
     let res = if let Some(project_id) = project_id {
         client
             .secrets()
@@ -78,13 +155,20 @@ pub(crate) async fn run(
         .await?
         .data;
 
+    Ok(secrets)
+}
+
+fn build_environment(
+    secrets: Vec<SecretResponse>,
+    uuids_as_keynames: bool,
+) -> Result<HashMap<String, String>> {
     if !uuids_as_keynames {
         if let Some(duplicate) = secrets.iter().map(|s| &s.key).duplicates().next() {
             bail!("Multiple secrets with name: '{}'. Use --uuids-as-keynames or use unique names for secrets", duplicate);
         }
     }
 
-    let environment: HashMap<String, String> = secrets
+    let environment = secrets
         .into_iter()
         .map(|s| {
             if uuids_as_keynames {
@@ -103,14 +187,20 @@ pub(crate) async fn run(
         })
         .collect();
 
-    let mut command = process::Command::new(shell);
+    Ok(environment)
+}
+
+fn spawn(settings: &ChildSettings, environment: HashMap<String, String>) -> Result<Child> {
+    let is_windows = std::env::consts::OS == "windows";
+
+    let mut command = Command::new(&settings.shell);
     command
         .arg("-c")
-        .arg(&user_command)
-        .stdout(process::Stdio::inherit())
-        .stderr(process::Stdio::inherit());
+        .arg(&settings.user_command)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
 
-    if no_inherit_env {
+    if settings.no_inherit_env {
         let path = std::env::var("PATH").unwrap_or_else(|_| match is_windows {
             true => "C:\\Windows;C:\\Windows\\System32".to_string(),
             false => "/bin:/usr/bin".to_string(),
@@ -134,16 +224,163 @@ pub(crate) async fn run(
         command.envs(environment);
     }
 
-    // propagate the exit status from the child process
     match command.spawn() {
-        Ok(mut child) => match child.wait() {
-            Ok(exit_status) => Ok(exit_status.code().unwrap_or(1)),
-            Err(e) => {
-                bail!("Failed to wait for process: {}", e)
-            }
-        },
+        Ok(child) => Ok(child),
         Err(e) => {
             bail!("Failed to execute process: {}", e)
         }
     }
 }
+
+// propagate the exit status from the child process
+async fn wait(child: &mut Child) -> Result<i32> {
+    match child.wait().await {
+        Ok(exit_status) => Ok(exit_status.code().unwrap_or(1)),
+        Err(e) => {
+            bail!("Failed to wait for process: {}", e)
+        }
+    }
+}
+
+async fn run_watch(
+    client: Client,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+    settings: ChildSettings,
+    watch: RunWatchSettings,
+) -> Result<i32> {
+    let mut watcher = client.secrets().watch(&SecretsWatchRequest {
+        organization_id,
+        project_id,
+        interval_seconds: Some(watch.interval),
+    });
+
+    let mut secrets: HashMap<Uuid, SecretResponse> = watcher
+        .poll()
+        .await?
+        .added
+        .into_iter()
+        .map(|s| (s.id, s))
+        .collect();
+
+    let environment = build_environment(
+        secrets.values().cloned().collect(),
+        settings.uuids_as_keynames,
+    )?;
+    let mut child = spawn(&settings, environment)?;
+    let mut started = Instant::now();
+    let mut restarts = 0;
+    let mut restart_delay = Duration::from_secs(1);
+
+    loop {
+        tokio::select! {
+            exit_code = wait(&mut child) => return exit_code,
+            _ = tokio::time::sleep(watcher.interval()) => {}
+        }
+
+        let changes = match watcher.poll().await {
+            Ok(changes) => changes,
+            Err(e) => {
+                eprintln!("Warning: checking for secret changes failed: {}", e);
+                continue;
+            }
+        };
+
+        if changes.is_empty() {
+            continue;
+        }
+
+        for secret in changes.removed {
+            secrets.remove(&secret.id);
+        }
+        for secret in changes.added.into_iter().chain(changes.changed) {
+            secrets.insert(secret.id, secret);
+        }
+
+        match watch.on_change {
+            OnChange::Signal => send_signal(&child, watch.signal)?,
+            OnChange::Restart => {
+                if watch.max_restarts.is_some_and(|max| restarts >= max) {
+                    eprintln!(
+                        "Warning: secrets changed, but the command was already restarted {} times. No longer watching for changes",
+                        restarts
+                    );
+                    return wait(&mut child).await;
+                }
+
+                let environment = match build_environment(
+                    secrets.values().cloned().collect(),
+                    settings.uuids_as_keynames,
+                ) {
+                    Ok(environment) => environment,
+                    Err(e) => {
+                        eprintln!("Warning: {}. Keeping the command running", e);
+                        continue;
+                    }
+                };
+
+                if started.elapsed() >= RESTART_DELAY_RESET {
+                    restart_delay = Duration::from_secs(1);
+                }
+
+                terminate(&mut child).await?;
+                tokio::time::sleep(restart_delay).await;
+
+                child = spawn(&settings, environment)?;
+                started = Instant::now();
+                restarts += 1;
+                restart_delay = (restart_delay * 2).min(MAX_RESTART_DELAY);
+            }
+        }
+    }
+}
+
+/// Ask the process to exit, and kill it if it doesn't within [TERMINATE_TIMEOUT]
+async fn terminate(child: &mut Child) -> Result<()> {
+    #[cfg(unix)]
+    {
+        send_signal(child, Signal::TERM)?;
+
+        if let Ok(exit_status) = tokio::time::timeout(TERMINATE_TIMEOUT, child.wait()).await {
+            exit_status?;
+            return Ok(());
+        }
+    }
+
+    child.kill().await?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn send_signal(child: &Child, signal: Signal) -> Result<()> {
+    // The process has already exited, which will be picked up by the next `wait`
+    let Some(pid) = child.id() else {
+        return Ok(());
+    };
+
+    let signal = match signal {
+        Signal::HUP => libc::SIGHUP,
+        Signal::INT => libc::SIGINT,
+        Signal::QUIT => libc::SIGQUIT,
+        Signal::TERM => libc::SIGTERM,
+        Signal::USR1 => libc::SIGUSR1,
+        Signal::USR2 => libc::SIGUSR2,
+    };
+
+    // SAFETY: `kill` doesn't access any memory, and the pid belongs to a child process that
+    // hasn't been reaped yet
+    if unsafe { libc::kill(libc::pid_t::try_from(pid)?, signal) } != 0 {
+        bail!(
+            "Failed to send signal to process: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_signal(_child: &Child, _signal: Signal) -> Result<()> {
+    bail!("Sending signals is only supported on Unix systems")
+}
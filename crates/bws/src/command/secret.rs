@@ -3,6 +3,8 @@ use bitwarden::{
         secrets::{
             SecretCreateRequest, SecretGetRequest, SecretIdentifiersByProjectRequest,
             SecretIdentifiersRequest, SecretPutRequest, SecretsDeleteRequest, SecretsGetRequest,
+            SecretsRestoreRequest, SecretsTrashEmptyRequest, SecretsTrashRequest,
+            SecretsWatchRequest,
         },
         ClientSecretsExt,
     },
@@ -12,8 +14,9 @@ use color_eyre::eyre::{bail, Result};
 use uuid::Uuid;
 
 use crate::{
-    render::{serialize_response, OutputSettings},
-    SecretCommand,
+    command::secret_import::{self, SecretImportSettings},
+    render::{serialize_changes, serialize_response, OutputSettings},
+    Output, SecretCommand, SecretTrashCommand,
 };
 
 #[derive(Debug)]
@@ -85,6 +88,49 @@ pub(crate) async fn process_command(
             .await
         }
         SecretCommand::Delete { secret_ids } => delete(client, secret_ids).await,
+        SecretCommand::Watch {
+            project_id,
+            interval,
+        } => {
+            watch(
+                client,
+                organization_id,
+                project_id,
+                interval,
+                output_settings,
+            )
+            .await
+        }
+        SecretCommand::Import {
+            file,
+            project_id,
+            format,
+            delete_missing,
+            dry_run,
+        } => {
+            secret_import::import(
+                client,
+                organization_id,
+                project_id,
+                &file,
+                SecretImportSettings {
+                    format,
+                    delete_missing,
+                    dry_run,
+                },
+                output_settings,
+            )
+            .await
+        }
+        SecretCommand::Trash { cmd } => match cmd {
+            SecretTrashCommand::List => list_trash(client, organization_id, output_settings).await,
+            SecretTrashCommand::Restore { secret_ids } => {
+                restore(client, organization_id, secret_ids).await
+            }
+            SecretTrashCommand::Empty { secret_ids, all } => {
+                empty_trash(client, organization_id, secret_ids, all).await
+            }
+        },
     }
 }
 
@@ -219,3 +265,113 @@ pub(crate) async fn delete(client: Client, secret_ids: Vec<Uuid>) -> Result<()>
 
     Ok(())
 }
+
+pub(crate) async fn watch(
+    client: Client,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+    interval: u64,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    if !matches!(
+        output_settings.output,
+        Output::JSON | Output::Env | Output::None
+    ) {
+        bail!("The watch command only supports the `json`, `env` and `none` output formats");
+    }
+
+    let mut watcher = client.secrets().watch(&SecretsWatchRequest {
+        organization_id,
+        project_id,
+        interval_seconds: Some(interval),
+    });
+
+    loop {
+        match watcher.poll().await {
+            Ok(changes) => serialize_changes(&changes, output_settings.output),
+            Err(e) => eprintln!("Warning: checking for secret changes failed: {}", e),
+        }
+
+        tokio::time::sleep(watcher.interval()).await;
+    }
+}
+
+pub(crate) async fn list_trash(
+    client: Client,
+    organization_id: Uuid,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let secrets = client
+        .secrets()
+        .list_trash(&SecretsTrashRequest { organization_id })
+        .await?
+        .data;
+    serialize_response(secrets, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn restore(
+    client: Client,
+    organization_id: Uuid,
+    secret_ids: Vec<Uuid>,
+) -> Result<()> {
+    let count = secret_ids.len();
+
+    client
+        .secrets()
+        .restore(SecretsRestoreRequest {
+            organization_id,
+            ids: secret_ids,
+        })
+        .await?;
+
+    match count {
+        2.. => println!("{} secrets restored successfully.", count),
+        1 => println!("{} secret restored successfully.", count),
+        _ => (),
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn empty_trash(
+    client: Client,
+    organization_id: Uuid,
+    secret_ids: Vec<Uuid>,
+    all: bool,
+) -> Result<()> {
+    let secret_ids = if all {
+        client
+            .secrets()
+            .list_trash(&SecretsTrashRequest { organization_id })
+            .await?
+            .data
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+    } else {
+        secret_ids
+    };
+
+    let count = secret_ids.len();
+    if count == 0 {
+        println!("The trash is already empty.");
+        return Ok(());
+    }
+
+    client
+        .secrets()
+        .empty_trash(SecretsTrashEmptyRequest {
+            organization_id,
+            ids: secret_ids,
+        })
+        .await?;
+
+    match count {
+        2.. => println!("{} secrets permanently deleted.", count),
+        _ => println!("{} secret permanently deleted.", count),
+    }
+
+    Ok(())
+}
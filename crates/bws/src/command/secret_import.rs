@@ -0,0 +1,522 @@
+use std::{collections::HashMap, path::Path};
+
+use bitwarden::{
+    secrets_manager::{
+        secrets::{
+            SecretCreateRequest, SecretIdentifiersByProjectRequest, SecretPutRequest,
+            SecretResponse, SecretsDeleteRequest, SecretsGetRequest,
+        },
+        ClientSecretsExt,
+    },
+    Client,
+};
+use color_eyre::eyre::{bail, Result};
+use itertools::Itertools;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    render::{serialize_response, OutputSettings, TableSerialize},
+    ImportFormat,
+};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ImportAction {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ImportPlanEntry {
+    action: ImportAction,
+    key: String,
+    id: Option<Uuid>,
+    #[serde(skip)]
+    value: String,
+    #[serde(skip)]
+    note: String,
+}
+
+impl TableSerialize<3> for ImportPlanEntry {
+    fn get_headers() -> [&'static str; 3] {
+        ["ID", "Key", "Action"]
+    }
+
+    fn get_values(&self) -> Vec<[String; 3]> {
+        let action = match self.action {
+            ImportAction::Create => "create",
+            ImportAction::Update => "update",
+            ImportAction::Delete => "delete",
+            ImportAction::Unchanged => "unchanged",
+        };
+
+        vec![[
+            self.id.map(|id| id.to_string()).unwrap_or_default(),
+            self.key.clone(),
+            action.to_owned(),
+        ]]
+    }
+}
+
+pub(crate) struct SecretImportSettings {
+    pub(crate) format: Option<ImportFormat>,
+    pub(crate) delete_missing: bool,
+    pub(crate) dry_run: bool,
+}
+
+pub(crate) async fn import(
+    client: Client,
+    organization_id: Uuid,
+    project_id: Uuid,
+    file: &Path,
+    settings: SecretImportSettings,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let format = match settings.format {
+        Some(format) => format,
+        None => detect_format(file),
+    };
+    let content = std::fs::read_to_string(file)?;
+    let imported = parse(&content, format)?;
+
+    let existing = get_project_secrets(&client, project_id).await?;
+    let plan = plan(&existing, imported, settings.delete_missing)?;
+
+    if !settings.dry_run {
+        apply(&client, organization_id, project_id, &plan).await?;
+    }
+
+    serialize_response(plan, output_settings);
+
+    Ok(())
+}
+
+fn detect_format(file: &Path) -> ImportFormat {
+    match file
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .as_deref()
+    {
+        Some("json") => ImportFormat::Json,
+        Some("yaml" | "yml") => ImportFormat::Yaml,
+        _ => ImportFormat::Dotenv,
+    }
+}
+
+fn parse(content: &str, format: ImportFormat) -> Result<Vec<(String, String)>> {
+    let secrets = match format {
+        ImportFormat::Dotenv => parse_dotenv(content)?,
+        ImportFormat::Json => {
+            let value: serde_json::Value = serde_json::from_str(content)?;
+            let serde_json::Value::Object(map) = value else {
+                bail!("The JSON file must contain an object of secret names and values");
+            };
+
+            map.into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(s) => Ok((key, s)),
+                    serde_json::Value::Number(n) => Ok((key, n.to_string())),
+                    serde_json::Value::Bool(b) => Ok((key, b.to_string())),
+                    _ => bail!("The value of '{}' must be a string, number or boolean", key),
+                })
+                .collect::<Result<_>>()?
+        }
+        ImportFormat::Yaml => {
+            let value: serde_yaml::Value = serde_yaml::from_str(content)?;
+            let serde_yaml::Value::Mapping(map) = value else {
+                bail!("The YAML file must contain a mapping of secret names and values");
+            };
+
+            map.into_iter()
+                .map(|(key, value)| {
+                    let Some(key) = yaml_scalar(key) else {
+                        bail!("Secret names must be strings, numbers or booleans");
+                    };
+                    match yaml_scalar(value) {
+                        Some(value) => Ok((key, value)),
+                        None => bail!("The value of '{}' must be a string, number or boolean", key),
+                    }
+                })
+                .collect::<Result<_>>()?
+        }
+    };
+
+    if let Some(duplicate) = secrets.iter().map(|(k, _)| k).duplicates().next() {
+        bail!(
+            "The file contains the secret '{}' more than once",
+            duplicate
+        );
+    }
+
+    Ok(secrets)
+}
+
+fn yaml_scalar(value: serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse `KEY=value` lines, with optional `export` prefixes, `#` comments, and single or double
+/// quoted values. Double quoted values support escapes and can span multiple lines.
+fn parse_dotenv(content: &str) -> Result<Vec<(String, String)>> {
+    let mut secrets = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("Line {} is not a KEY=value pair", number + 1);
+        };
+        let key = key.trim().to_owned();
+        if key.is_empty() {
+            bail!("Line {} is missing a secret name", number + 1);
+        }
+
+        let value = value.trim_start();
+        let value = if let Some(rest) = value.strip_prefix('"') {
+            let mut raw = rest.to_owned();
+            while !has_closing_quote(&raw) {
+                let Some((_, next)) = lines.next() else {
+                    bail!("The value of '{}' is missing a closing quote", key);
+                };
+                raw.push('\n');
+                raw.push_str(next);
+            }
+            unescape_double_quoted(&raw)
+        } else if let Some(rest) = value.strip_prefix('\'') {
+            match rest.find('\'') {
+                Some(end) => rest[..end].to_owned(),
+                None => bail!("The value of '{}' is missing a closing quote", key),
+            }
+        } else {
+            match value.find(" #") {
+                Some(comment) => value[..comment].trim_end().to_owned(),
+                None => value.trim_end().to_owned(),
+            }
+        };
+
+        secrets.push((key, value));
+    }
+
+    Ok(secrets)
+}
+
+fn has_closing_quote(raw: &str) -> bool {
+    let mut escaped = false;
+    for c in raw.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn unescape_double_quoted(raw: &str) -> String {
+    let mut value = String::new();
+    let mut chars = raw.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some(other) => value.push(other),
+                None => value.push('\\'),
+            },
+            _ => value.push(c),
+        }
+    }
+
+    value
+}
+
+async fn get_project_secrets(client: &Client, project_id: Uuid) -> Result<Vec<SecretResponse>> {
+    let ids = client
+        .secrets()
+        .list_by_project(&SecretIdentifiersByProjectRequest { project_id })
+        .await?
+        .data
+        .into_iter()
+        .map(|s| s.id)
+        .collect::<Vec<_>>();
+
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(client
+        .secrets()
+        .get_by_ids(SecretsGetRequest { ids })
+        .await?
+        .data)
+}
+
+fn plan(
+    existing: &[SecretResponse],
+    imported: Vec<(String, String)>,
+    delete_missing: bool,
+) -> Result<Vec<ImportPlanEntry>> {
+    if let Some(duplicate) = existing.iter().map(|s| &s.key).duplicates().next() {
+        bail!(
+            "The project contains multiple secrets with name: '{}'. Use unique names for secrets before importing",
+            duplicate
+        );
+    }
+
+    let mut by_key: HashMap<&str, &SecretResponse> =
+        existing.iter().map(|s| (s.key.as_str(), s)).collect();
+
+    let mut plan: Vec<ImportPlanEntry> = imported
+        .into_iter()
+        .map(|(key, value)| match by_key.remove(key.as_str()) {
+            Some(secret) => ImportPlanEntry {
+                action: if secret.value == value {
+                    ImportAction::Unchanged
+                } else {
+                    ImportAction::Update
+                },
+                id: Some(secret.id),
+                note: secret.note.clone(),
+                key,
+                value,
+            },
+            None => ImportPlanEntry {
+                action: ImportAction::Create,
+                id: None,
+                note: String::new(),
+                key,
+                value,
+            },
+        })
+        .collect();
+
+    if delete_missing {
+        plan.extend(
+            by_key
+                .into_values()
+                .sorted_by(|a, b| a.key.cmp(&b.key))
+                .map(|secret| ImportPlanEntry {
+                    action: ImportAction::Delete,
+                    key: secret.key.clone(),
+                    id: Some(secret.id),
+                    value: String::new(),
+                    note: String::new(),
+                }),
+        );
+    }
+
+    Ok(plan)
+}
+
+async fn apply(
+    client: &Client,
+    organization_id: Uuid,
+    project_id: Uuid,
+    plan: &[ImportPlanEntry],
+) -> Result<()> {
+    for entry in plan {
+        match (entry.action, entry.id) {
+            (ImportAction::Create, _) => {
+                client
+                    .secrets()
+                    .create(&SecretCreateRequest {
+                        organization_id,
+                        key: entry.key.clone(),
+                        value: entry.value.clone(),
+                        note: entry.note.clone(),
+                        project_ids: Some(vec![project_id]),
+                    })
+                    .await?;
+            }
+            (ImportAction::Update, Some(id)) => {
+                client
+                    .secrets()
+                    .update(&SecretPutRequest {
+                        id,
+                        organization_id,
+                        key: entry.key.clone(),
+                        value: entry.value.clone(),
+                        note: entry.note.clone(),
+                        project_ids: Some(vec![project_id]),
+                    })
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+
+    let delete_ids: Vec<Uuid> = plan
+        .iter()
+        .filter(|e| e.action == ImportAction::Delete)
+        .filter_map(|e| e.id)
+        .collect();
+
+    if !delete_ids.is_empty() {
+        let failed: Vec<(Uuid, String)> = client
+            .secrets()
+            .delete(SecretsDeleteRequest { ids: delete_ids })
+            .await?
+            .data
+            .into_iter()
+            .filter_map(|r| r.error.map(|e| (r.id, e)))
+            .collect();
+
+        for (id, error) in &failed {
+            eprintln!("{}: {}", id, error);
+        }
+
+        if !failed.is_empty() {
+            bail!("Errors when attempting to delete secrets.");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(key: &str, value: &str) -> SecretResponse {
+        let date = "2024-01-01T00:00:00Z".parse().unwrap();
+        SecretResponse {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::nil(),
+            project_id: None,
+            key: key.to_owned(),
+            value: value.to_owned(),
+            note: "note".to_owned(),
+            creation_date: date,
+            revision_date: date,
+        }
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let content = r#"
+# Database settings
+export DB_HOST=localhost # the host
+DB_USER = 'admin # not a comment'
+DB_PASSWORD="p\"ss\nword"
+CERT="-----BEGIN-----
+abc
+-----END-----"
+EMPTY=
+"#;
+
+        assert_eq!(
+            parse(content, ImportFormat::Dotenv).unwrap(),
+            pairs(&[
+                ("DB_HOST", "localhost"),
+                ("DB_USER", "admin # not a comment"),
+                ("DB_PASSWORD", "p\"ss\nword"),
+                ("CERT", "-----BEGIN-----\nabc\n-----END-----"),
+                ("EMPTY", ""),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_errors() {
+        assert_eq!(
+            parse("NOT_A_PAIR", ImportFormat::Dotenv)
+                .unwrap_err()
+                .to_string(),
+            "Line 1 is not a KEY=value pair"
+        );
+        assert_eq!(
+            parse("A=1\nA=2", ImportFormat::Dotenv)
+                .unwrap_err()
+                .to_string(),
+            "The file contains the secret 'A' more than once"
+        );
+    }
+
+    #[test]
+    fn test_parse_json_and_yaml() {
+        let expected = pairs(&[("ENABLED", "true"), ("PORT", "5432")]);
+
+        assert_eq!(
+            parse(r#"{"ENABLED": true, "PORT": 5432}"#, ImportFormat::Json).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse("ENABLED: true\nPORT: 5432\n", ImportFormat::Yaml).unwrap(),
+            expected
+        );
+        assert!(parse(r#"{"NESTED": {"A": 1}}"#, ImportFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format(Path::new("prod.env")), ImportFormat::Dotenv);
+        assert_eq!(detect_format(Path::new(".env")), ImportFormat::Dotenv);
+        assert_eq!(detect_format(Path::new("a.JSON")), ImportFormat::Json);
+        assert_eq!(detect_format(Path::new("a.yml")), ImportFormat::Yaml);
+    }
+
+    #[test]
+    fn test_plan() {
+        let existing = vec![
+            secret("SAME", "1"),
+            secret("CHANGED", "1"),
+            secret("MISSING", "1"),
+        ];
+        let imported = pairs(&[("SAME", "1"), ("CHANGED", "2"), ("NEW", "1")]);
+
+        let actions = |plan: Vec<ImportPlanEntry>| {
+            plan.into_iter()
+                .map(|e| (e.action, e.key))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            actions(plan(&existing, imported.clone(), false).unwrap()),
+            vec![
+                (ImportAction::Unchanged, "SAME".to_owned()),
+                (ImportAction::Update, "CHANGED".to_owned()),
+                (ImportAction::Create, "NEW".to_owned()),
+            ]
+        );
+
+        let with_delete = plan(&existing, imported, true).unwrap();
+        assert_eq!(with_delete[1].note, "note");
+        assert_eq!(
+            actions(with_delete)[3],
+            (ImportAction::Delete, "MISSING".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_plan_duplicate_existing_keys() {
+        let existing = vec![secret("DUP", "1"), secret("DUP", "2")];
+
+        assert!(plan(&existing, pairs(&[("DUP", "3")]), false).is_err());
+    }
+}
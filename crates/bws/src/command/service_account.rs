@@ -0,0 +1,270 @@
+use bitwarden::{
+    secrets_manager::{
+        service_accounts::{
+            access_tokens::{
+                AccessTokenCreateRequest, AccessTokensListRequest, AccessTokensRevokeRequest,
+            },
+            ServiceAccountCreateRequest, ServiceAccountGetRequest, ServiceAccountPutRequest,
+            ServiceAccountsDeleteRequest, ServiceAccountsListRequest,
+        },
+        ClientServiceAccountsExt,
+    },
+    Client,
+};
+use color_eyre::eyre::{bail, eyre, Result};
+use uuid::Uuid;
+
+use crate::{
+    render::{serialize_response, OutputSettings},
+    util::parse_date,
+    AccessTokenCommand, ServiceAccountCommand,
+};
+
+pub(crate) async fn process_command(
+    command: ServiceAccountCommand,
+    client: Client,
+    organization_id: Uuid,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    match command {
+        ServiceAccountCommand::List => list(client, organization_id, output_settings).await,
+        ServiceAccountCommand::Get { service_account_id } => {
+            get(client, service_account_id, output_settings).await
+        }
+        ServiceAccountCommand::Create { name } => {
+            create(client, organization_id, name, output_settings).await
+        }
+        ServiceAccountCommand::Edit {
+            service_account_id,
+            name,
+        } => {
+            edit(
+                client,
+                organization_id,
+                service_account_id,
+                name,
+                output_settings,
+            )
+            .await
+        }
+        ServiceAccountCommand::Delete {
+            service_account_ids,
+        } => delete(client, service_account_ids).await,
+        ServiceAccountCommand::AccessToken { cmd } => match cmd {
+            AccessTokenCommand::List { service_account_id } => {
+                list_access_tokens(client, organization_id, service_account_id, output_settings)
+                    .await
+            }
+            AccessTokenCommand::Create {
+                service_account_id,
+                name,
+                expires_at,
+            } => {
+                create_access_token(
+                    client,
+                    organization_id,
+                    service_account_id,
+                    name,
+                    expires_at,
+                    output_settings,
+                )
+                .await
+            }
+            AccessTokenCommand::Revoke {
+                service_account_id,
+                access_token_ids,
+            } => revoke_access_tokens(client, service_account_id, access_token_ids).await,
+        },
+    }
+}
+
+pub(crate) async fn list(
+    client: Client,
+    organization_id: Uuid,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let service_accounts = client
+        .service_accounts()
+        .list(&ServiceAccountsListRequest { organization_id })
+        .await?
+        .data;
+    serialize_response(service_accounts, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn get(
+    client: Client,
+    service_account_id: Uuid,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let service_account = client
+        .service_accounts()
+        .get(&ServiceAccountGetRequest {
+            id: service_account_id,
+        })
+        .await?;
+    serialize_response(service_account, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn create(
+    client: Client,
+    organization_id: Uuid,
+    name: String,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let service_account = client
+        .service_accounts()
+        .create(&ServiceAccountCreateRequest {
+            organization_id,
+            name,
+        })
+        .await?;
+    serialize_response(service_account, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn edit(
+    client: Client,
+    organization_id: Uuid,
+    service_account_id: Uuid,
+    name: String,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let service_account = client
+        .service_accounts()
+        .update(&ServiceAccountPutRequest {
+            id: service_account_id,
+            organization_id,
+            name,
+        })
+        .await?;
+    serialize_response(service_account, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn delete(client: Client, service_account_ids: Vec<Uuid>) -> Result<()> {
+    let count = service_account_ids.len();
+
+    let result = client
+        .service_accounts()
+        .delete(ServiceAccountsDeleteRequest {
+            ids: service_account_ids,
+        })
+        .await?;
+
+    let service_accounts_failed: Vec<(Uuid, String)> = result
+        .data
+        .into_iter()
+        .filter_map(|r| r.error.map(|e| (r.id, e)))
+        .collect();
+    let deleted_service_accounts = count - service_accounts_failed.len();
+
+    match deleted_service_accounts {
+        2.. => println!(
+            "{} service accounts deleted successfully.",
+            deleted_service_accounts
+        ),
+        1 => println!(
+            "{} service account deleted successfully.",
+            deleted_service_accounts
+        ),
+        _ => (),
+    }
+
+    match service_accounts_failed.len() {
+        2.. => eprintln!(
+            "{} service accounts had errors:",
+            service_accounts_failed.len()
+        ),
+        1 => eprintln!(
+            "{} service account had an error:",
+            service_accounts_failed.len()
+        ),
+        _ => (),
+    }
+
+    for service_account in &service_accounts_failed {
+        eprintln!("{}: {}", service_account.0, service_account.1);
+    }
+
+    if !service_accounts_failed.is_empty() {
+        bail!("Errors when attempting to delete service accounts.");
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn list_access_tokens(
+    client: Client,
+    organization_id: Uuid,
+    service_account_id: Uuid,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let access_tokens = client
+        .service_accounts()
+        .list_access_tokens(&AccessTokensListRequest {
+            organization_id,
+            service_account_id,
+        })
+        .await?
+        .data;
+    serialize_response(access_tokens, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn create_access_token(
+    client: Client,
+    organization_id: Uuid,
+    service_account_id: Uuid,
+    name: String,
+    expires_at: Option<String>,
+    output_settings: OutputSettings,
+) -> Result<()> {
+    let expire_at = expires_at
+        .map(|date| parse_date(&date))
+        .transpose()
+        .map_err(|e| eyre!(e))?;
+
+    let access_token = client
+        .service_accounts()
+        .create_access_token(&AccessTokenCreateRequest {
+            organization_id,
+            service_account_id,
+            name,
+            expire_at,
+        })
+        .await?;
+    serialize_response(access_token, output_settings);
+
+    Ok(())
+}
+
+pub(crate) async fn revoke_access_tokens(
+    client: Client,
+    service_account_id: Uuid,
+    access_token_ids: Vec<Uuid>,
+) -> Result<()> {
+    let count = access_token_ids.len();
+
+    client
+        .service_accounts()
+        .revoke_access_tokens(AccessTokensRevokeRequest {
+            service_account_id,
+            ids: access_token_ids,
+        })
+        .await?;
+
+    match count {
+        2.. => println!("{} access tokens revoked successfully.", count),
+        1 => println!("{} access token revoked successfully.", count),
+        _ => (),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use bitwarden::{
+    secrets_manager::{
+        secrets::{SecretIdentifiersByProjectRequest, SecretIdentifiersRequest, SecretsGetRequest},
+        ClientSecretsExt,
+    },
+    Client,
+};
+use color_eyre::eyre::{bail, eyre, Result};
+use itertools::Itertools;
+use regex::{Captures, Regex};
+use uuid::Uuid;
+
+use crate::TemplateCommand;
+
+const TEMPLATE_TAG_REGEX: &str = r#"\{\{\s*(secret|secret_id)\s+"([^"]*)"\s*\}\}"#;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SecretReference {
+    Key(String),
+    Id(Uuid),
+}
+
+pub(crate) async fn process_command(
+    command: TemplateCommand,
+    client: Client,
+    organization_id: Uuid,
+) -> Result<()> {
+    match command {
+        TemplateCommand::Render {
+            file,
+            destination,
+            mode,
+            project_id,
+        } => render(client, organization_id, project_id, file, destination, mode).await,
+    }
+}
+
+pub(crate) async fn render(
+    client: Client,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+    file: PathBuf,
+    destination: Option<PathBuf>,
+    mode: String,
+) -> Result<()> {
+    let Ok(mode) = u32::from_str_radix(&mode, 8) else {
+        bail!("File mode '{}' is not a valid octal number", mode);
+    };
+
+    let template = std::fs::read_to_string(&file)?;
+    let references = parse_references(&template)?;
+
+    let values = resolve_references(&client, organization_id, project_id, &references).await?;
+    let rendered = render_template(&template, &values)?;
+
+    match destination {
+        Some(destination) => write_restricted(&destination, rendered.as_bytes(), mode)?,
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn template_regex() -> Regex {
+    Regex::new(TEMPLATE_TAG_REGEX).expect("TEMPLATE_TAG_REGEX to be a valid regex")
+}
+
+fn parse_reference(captures: &Captures) -> Result<SecretReference> {
+    let value = &captures[2];
+    match &captures[1] {
+        "secret_id" => match Uuid::parse_str(value) {
+            Ok(id) => Ok(SecretReference::Id(id)),
+            Err(_) => bail!("'{}' is not a valid secret ID", value),
+        },
+        _ => Ok(SecretReference::Key(value.to_owned())),
+    }
+}
+
+fn parse_references(template: &str) -> Result<HashSet<SecretReference>> {
+    template_regex()
+        .captures_iter(template)
+        .map(|c| parse_reference(&c))
+        .collect()
+}
+
+async fn resolve_references(
+    client: &Client,
+    organization_id: Uuid,
+    project_id: Option<Uuid>,
+    references: &HashSet<SecretReference>,
+) -> Result<HashMap<SecretReference, String>> {
+    let mut ids: HashMap<SecretReference, Uuid> = HashMap::new();
+
+    if references
+        .iter()
+        .any(|r| matches!(r, SecretReference::Key(_)))
+    {
+        let identifiers = if let Some(project_id) = project_id {
+            client
+                .secrets()
+                .list_by_project(&SecretIdentifiersByProjectRequest { project_id })
+                .await?
+        } else {
+            client
+                .secrets()
+                .list(&SecretIdentifiersRequest { organization_id })
+                .await?
+        }
+        .data;
+
+        let by_key = identifiers.iter().into_group_map_by(|s| &s.key);
+
+        for reference in references {
+            let SecretReference::Key(key) = reference else {
+                continue;
+            };
+
+            match by_key.get(key).map(Vec::as_slice) {
+                Some([secret]) => {
+                    ids.insert(reference.clone(), secret.id);
+                }
+                Some(_) => bail!(
+                    "Multiple secrets with name: '{}'. Use `secret_id` or use unique names for secrets",
+                    key
+                ),
+                None => bail!("No secret with name: '{}'", key),
+            }
+        }
+    }
+
+    for reference in references {
+        if let SecretReference::Id(id) = reference {
+            ids.insert(reference.clone(), *id);
+        }
+    }
+
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let secrets = client
+        .secrets()
+        .get_by_ids(SecretsGetRequest {
+            ids: ids.values().copied().unique().collect(),
+        })
+        .await?
+        .data;
+    let values: HashMap<Uuid, String> = secrets.into_iter().map(|s| (s.id, s.value)).collect();
+
+    ids.into_iter()
+        .map(|(reference, id)| match values.get(&id) {
+            Some(value) => Ok((reference, value.clone())),
+            None => Err(eyre!("No secret with ID: '{}'", id)),
+        })
+        .collect()
+}
+
+fn render_template(template: &str, values: &HashMap<SecretReference, String>) -> Result<String> {
+    let mut error = None;
+
+    let rendered = template_regex().replace_all(template, |captures: &Captures| {
+        match parse_reference(captures).ok().and_then(|r| values.get(&r)) {
+            Some(value) => value.clone(),
+            None => {
+                error.get_or_insert_with(|| captures[0].to_owned());
+                String::new()
+            }
+        }
+    });
+
+    if let Some(tag) = error {
+        bail!("Unable to resolve template tag: {}", tag);
+    }
+
+    Ok(rendered.into_owned())
+}
+
+pub(crate) fn write_restricted(path: &Path, contents: &[u8], mode: u32) -> Result<()> {
+    #[cfg(unix)]
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(mode);
+
+    let mut file = options.open(path)?;
+
+    // The mode is only applied when creating the file, so make sure existing files are restricted
+    // as well before writing any secrets to them
+    #[cfg(unix)]
+    file.set_permissions(std::fs::Permissions::from_mode(mode))?;
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_references() {
+        let id = Uuid::new_v4();
+        let template = format!(
+            "user={{{{ secret \"DB_USER\" }}}}\npass={{{{secret \"DB_PASSWORD\"}}}}\nkey={{{{ secret_id \"{id}\" }}}}\nuser_again={{{{ secret \"DB_USER\" }}}}\n{{{{ not_a_tag }}}}"
+        );
+
+        let references = parse_references(&template).unwrap();
+
+        assert_eq!(references.len(), 3);
+        assert!(references.contains(&SecretReference::Key("DB_USER".to_owned())));
+        assert!(references.contains(&SecretReference::Key("DB_PASSWORD".to_owned())));
+        assert!(references.contains(&SecretReference::Id(id)));
+    }
+
+    #[test]
+    fn test_parse_references_invalid_id() {
+        let result = parse_references("{{ secret_id \"not-a-uuid\" }}");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "'not-a-uuid' is not a valid secret ID"
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let id = Uuid::new_v4();
+        let values = HashMap::from([
+            (
+                SecretReference::Key("DB_USER".to_owned()),
+                "admin".to_owned(),
+            ),
+            (SecretReference::Id(id), "s3cr3t".to_owned()),
+        ]);
+
+        let rendered = render_template(
+            &format!("{{{{ secret \"DB_USER\" }}}}:{{{{ secret_id \"{id}\" }}}}@localhost"),
+            &values,
+        )
+        .unwrap();
+
+        assert_eq!(rendered, "admin:s3cr3t@localhost");
+    }
+
+    #[test]
+    fn test_render_template_unresolved_tag() {
+        let result = render_template("{{ secret \"MISSING\" }}", &HashMap::new());
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unable to resolve template tag: {{ secret \"MISSING\" }}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_restricted_sets_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rendered");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_restricted(&path, b"new", 0o600).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+    }
+}
@@ -22,6 +22,8 @@ pub(crate) struct Profile {
     pub server_identity: Option<String>,
     pub state_dir: Option<String>,
     pub state_opt_out: Option<String>,
+    pub secrets_cache: Option<String>,
+    pub secrets_cache_max_staleness: Option<String>,
 }
 
 impl ProfileKey {
@@ -32,6 +34,8 @@ impl ProfileKey {
             ProfileKey::server_identity => p.server_identity = Some(value),
             ProfileKey::state_dir => p.state_dir = Some(value),
             ProfileKey::state_opt_out => p.state_opt_out = Some(value),
+            ProfileKey::secrets_cache => p.secrets_cache = Some(value),
+            ProfileKey::secrets_cache_max_staleness => p.secrets_cache_max_staleness = Some(value),
         }
     }
 }
@@ -121,6 +125,8 @@ impl Profile {
             server_identity: None,
             state_dir: None,
             state_opt_out: None,
+            secrets_cache: None,
+            secrets_cache_max_staleness: None,
         })
     }
     pub(crate) fn api_url(&self) -> Result<String> {
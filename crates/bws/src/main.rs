@@ -2,6 +2,7 @@ use std::{path::PathBuf, str::FromStr};
 
 use bitwarden::{
     auth::{login::AccessTokenLoginRequest, AccessToken},
+    secrets_manager::{secrets::SecretsCacheSettings, ClientSecretsExt},
     ClientSettings,
 };
 use bitwarden_cli::install_color_eyre;
@@ -86,6 +87,8 @@ async fn process_commands() -> Result<()> {
         })
         .transpose()?;
 
+    let secrets_cache = get_secrets_cache(&profile, &access_token_obj)?;
+
     let state_file = match get_state_opt_out(&profile) {
         true => None,
         false => match state::get_state_file(
@@ -101,6 +104,7 @@ async fn process_commands() -> Result<()> {
     };
 
     let client = bitwarden::Client::new(settings);
+    client.secrets().set_cache(secrets_cache);
 
     // Load session or return if no session exists
     let _ = client
@@ -131,13 +135,46 @@ async fn process_commands() -> Result<()> {
             command::secret::process_command(cmd, client, organization_id, output_settings).await
         }
 
+        Commands::ServiceAccount { cmd } => {
+            command::service_account::process_command(cmd, client, organization_id, output_settings)
+                .await
+        }
+
+        Commands::Events {
+            service_account_id,
+            start,
+            end,
+        } => command::events::list(client, service_account_id, start, end, output_settings).await,
+
+        Commands::Export { output_file } => {
+            command::porting::export(client, organization_id, output_file).await
+        }
+
+        Commands::Import { file } => command::porting::import(client, organization_id, file).await,
+
+        Commands::Template { cmd } => {
+            command::template::process_command(cmd, client, organization_id).await
+        }
+
         Commands::Run {
             command,
             shell,
             no_inherit_env,
             project_id,
             uuids_as_keynames,
+            watch,
+            watch_interval,
+            on_change,
+            signal,
+            max_restarts,
         } => {
+            let watch_settings = watch.then_some(command::run::RunWatchSettings {
+                interval: watch_interval,
+                on_change,
+                signal,
+                max_restarts,
+            });
+
             let exit_code = command::run::run(
                 client,
                 organization_id,
@@ -146,6 +183,7 @@ async fn process_commands() -> Result<()> {
                 no_inherit_env,
                 shell,
                 command,
+                watch_settings,
             )
             .await?;
 
@@ -184,6 +222,39 @@ fn get_config_profile(
     Ok(profile)
 }
 
+fn get_secrets_cache(
+    profile: &Option<Profile>,
+    access_token: &AccessToken,
+) -> Result<Option<SecretsCacheSettings>> {
+    let Some(profile) = profile else {
+        return Ok(None);
+    };
+
+    let enabled = profile
+        .secrets_cache
+        .as_deref()
+        .map(|v| util::string_to_bool(v).unwrap_or(false))
+        .unwrap_or(false);
+    if !enabled {
+        return Ok(None);
+    }
+
+    let max_staleness_seconds = profile
+        .secrets_cache_max_staleness
+        .as_deref()
+        .map(str::parse)
+        .transpose()?;
+    let cache_file = state::get_cache_file(
+        profile.state_dir.clone().map(Into::into),
+        access_token.access_token_id.to_string(),
+    )?;
+
+    Ok(Some(SecretsCacheSettings {
+        cache_file,
+        max_staleness_seconds,
+    }))
+}
+
 fn get_state_opt_out(profile: &Option<Profile>) -> bool {
     if let Some(profile) = profile {
         if let Some(state_opt_out) = &profile.state_opt_out {
@@ -1,4 +1,13 @@
-use bitwarden::secrets_manager::{projects::ProjectResponse, secrets::SecretResponse};
+use bitwarden::secrets_manager::{
+    access_policies::{AccessPolicyGranteeType, AccessPolicyResponse},
+    events::{EventResponse, EventType},
+    projects::ProjectResponse,
+    secrets::{SecretResponse, SecretsChangesResponse, TrashedSecretResponse},
+    service_accounts::{
+        access_tokens::{AccessTokenCreateResponse, AccessTokenResponse},
+        ServiceAccountResponse,
+    },
+};
 use bitwarden_cli::Color;
 use chrono::{DateTime, Utc};
 use comfy_table::Table;
@@ -86,6 +95,48 @@ pub(crate) fn serialize_response<T: Serialize + TableSerialize<N>, const N: usiz
     }
 }
 
+#[derive(Serialize)]
+struct SecretChangeLine<'a> {
+    change: &'static str,
+    #[serde(flatten)]
+    secret: &'a SecretResponse,
+}
+
+/// Print the changes reported by a secrets watcher, either as one JSON document per line or as
+/// a diff of environment variables which can be applied by a POSIX shell
+pub(crate) fn serialize_changes(changes: &SecretsChangesResponse, output: Output) {
+    let groups = [
+        ("added", &changes.added),
+        ("changed", &changes.changed),
+        ("removed", &changes.removed),
+    ];
+
+    for (change, secrets) in groups {
+        for secret in secrets {
+            match output {
+                Output::Env => {
+                    let line = match change {
+                        "removed" => format!("unset {}", secret.key),
+                        _ => format!("{}=\"{}\"", secret.key, secret.value),
+                    };
+
+                    if is_valid_posix_name(&secret.key) {
+                        println!("{line}");
+                    } else {
+                        println!("# {}", line.replace('\n', "\n# "));
+                    }
+                }
+                Output::None => {}
+                _ => println!(
+                    "{}",
+                    serde_json::to_string(&SecretChangeLine { change, secret })
+                        .expect("Serialize should be infallible")
+                ),
+            }
+        }
+    }
+}
+
 fn pretty_print(language: &str, data: &str, color: Color) {
     if color.is_enabled() {
         bat::PrettyPrinter::new()
@@ -138,6 +189,122 @@ impl TableSerialize<3> for ProjectResponse {
     }
 }
 
+impl TableSerialize<4> for TrashedSecretResponse {
+    fn get_headers() -> [&'static str; 4] {
+        ["ID", "Key", "Project ID", "Deletion Date"]
+    }
+
+    fn get_values(&self) -> Vec<[String; 4]> {
+        vec![[
+            self.id.to_string(),
+            self.key.clone(),
+            self.project_id.map(|id| id.to_string()).unwrap_or_default(),
+            format_date(&self.revision_date),
+        ]]
+    }
+}
+
+impl TableSerialize<5> for EventResponse {
+    fn get_headers() -> [&'static str; 5] {
+        [
+            "Date",
+            "Type",
+            "Secret ID",
+            "Service Account ID",
+            "IP Address",
+        ]
+    }
+
+    fn get_values(&self) -> Vec<[String; 5]> {
+        let event_type = match self.event_type {
+            EventType::SecretRetrieved => "Secret Retrieved".to_owned(),
+            EventType::Other(code) => code.to_string(),
+        };
+
+        vec![[
+            format_date(&self.date),
+            event_type,
+            self.secret_id.map(|id| id.to_string()).unwrap_or_default(),
+            self.service_account_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            self.ip_address.clone().unwrap_or_default(),
+        ]]
+    }
+}
+
+impl TableSerialize<5> for AccessPolicyResponse {
+    fn get_headers() -> [&'static str; 5] {
+        ["Grantee ID", "Name", "Type", "Read", "Write"]
+    }
+
+    fn get_values(&self) -> Vec<[String; 5]> {
+        let grantee_type = match self.grantee_type {
+            AccessPolicyGranteeType::User => "User",
+            AccessPolicyGranteeType::Group => "Group",
+            AccessPolicyGranteeType::ServiceAccount => "Service Account",
+        };
+
+        vec![[
+            self.grantee_id.to_string(),
+            self.grantee_name.clone().unwrap_or_default(),
+            grantee_type.to_owned(),
+            self.read.to_string(),
+            self.write.to_string(),
+        ]]
+    }
+}
+
+impl TableSerialize<3> for ServiceAccountResponse {
+    fn get_headers() -> [&'static str; 3] {
+        ["ID", "Name", "Creation Date"]
+    }
+
+    fn get_values(&self) -> Vec<[String; 3]> {
+        vec![[
+            self.id.to_string(),
+            self.name.clone(),
+            format_date(&self.creation_date),
+        ]]
+    }
+}
+
+impl TableSerialize<4> for AccessTokenResponse {
+    fn get_headers() -> [&'static str; 4] {
+        ["ID", "Name", "Expires At", "Creation Date"]
+    }
+
+    fn get_values(&self) -> Vec<[String; 4]> {
+        vec![[
+            self.id.to_string(),
+            self.name.clone(),
+            self.expire_at
+                .as_ref()
+                .map(format_date)
+                .unwrap_or_else(|| "Never".to_owned()),
+            format_date(&self.creation_date),
+        ]]
+    }
+}
+
+impl TableSerialize<4> for AccessTokenCreateResponse {
+    fn get_headers() -> [&'static str; 4] {
+        ["ID", "Name", "Access Token", "Expires At"]
+    }
+
+    fn get_values(&self) -> Vec<[String; 4]> {
+        vec![[
+            self.id.to_string(),
+            self.name.clone(),
+            self.access_token.clone(),
+            self.expire_at
+                .as_ref()
+                .map(format_date)
+                .unwrap_or_else(|| "Never".to_owned()),
+        ]]
+    }
+}
+
 impl TableSerialize<4> for SecretResponse {
     fn get_headers() -> [&'static str; 4] {
         ["ID", "Key", "Value", "Creation Date"]
@@ -6,6 +6,7 @@ use directories::BaseDirs;
 use crate::DEFAULT_CONFIG_DIRECTORY;
 
 pub(crate) const DEFAULT_STATE_DIRECTORY: &str = "state";
+pub(crate) const CACHE_FILE_EXTENSION: &str = "cache";
 
 pub(crate) fn get_state_file(
     state_dir: Option<PathBuf>,
@@ -30,3 +31,13 @@ pub(crate) fn get_state_file(
 
     Ok(state_dir)
 }
+
+pub(crate) fn get_cache_file(
+    state_dir: Option<PathBuf>,
+    access_token_id: String,
+) -> Result<PathBuf> {
+    let mut cache_file = get_state_file(state_dir, access_token_id)?;
+    cache_file.set_extension(CACHE_FILE_EXTENSION);
+
+    Ok(cache_file)
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use uuid::Uuid;
 
@@ -26,6 +27,12 @@ pub(crate) fn uuid_to_posix(uuid: &Uuid) -> String {
     format!("_{}", uuid.to_string().replace('-', "_"))
 }
 
+pub(crate) fn parse_date(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|_| format!("'{}' is not a valid RFC 3339 date", value))
+}
+
 mod tests {
     #[allow(unused_imports)]
     use super::*;
@@ -90,4 +97,18 @@ mod tests {
         let result = string_to_bool("hello world");
         assert_eq!(result, Err(STRING_TO_BOOL_ERROR_MESSAGE));
     }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("2024-05-01T14:00:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2024-05-01T12:00:00+00:00"
+        );
+        assert_eq!(
+            parse_date("2024-05-01"),
+            Err("'2024-05-01' is not a valid RFC 3339 date".to_owned())
+        );
+    }
 }
@@ -116,6 +116,12 @@ struct SchemaTypes {
     sync: Response<bitwarden::vault::SyncResponse>,
     #[cfg(feature = "internal")]
     user_api_key: Response<bitwarden::platform::UserApiKeyResponse>,
+    #[cfg(feature = "internal")]
+    folder: Response<bitwarden::vault::FolderView>,
+    #[cfg(feature = "internal")]
+    collection: Response<bitwarden::vault::CollectionView>,
+    #[cfg(feature = "internal")]
+    cipher: Response<bitwarden::vault::CipherView>,
 }
 
 fn main() -> Result<()> {